@@ -0,0 +1,67 @@
+//! Benchmarks for the collector with a large number of distinct keys
+//!
+//! Run with: cargo bench --bench key_interning --features full
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use quantum_pulse::ProfileCollector;
+
+const KEY_COUNT: usize = 10_000;
+
+fn keys() -> Vec<String> {
+    (0..KEY_COUNT)
+        .map(|i| format!("Database::query_with_a_fairly_long_operation_name_{}", i))
+        .collect()
+}
+
+fn bench_record_distinct_keys(c: &mut Criterion) {
+    let keys = keys();
+
+    c.bench_function("record 10k distinct keys", |b| {
+        b.iter(|| {
+            ProfileCollector::clear_all();
+            for key in &keys {
+                ProfileCollector::record(black_box(key), 100);
+            }
+        })
+    });
+}
+
+fn bench_record_existing_keys(c: &mut Criterion) {
+    let keys = keys();
+    ProfileCollector::clear_all();
+    for key in &keys {
+        ProfileCollector::record(key, 100);
+    }
+
+    c.bench_function("record into 10k existing keys", |b| {
+        b.iter(|| {
+            for key in &keys {
+                ProfileCollector::record(black_box(key), 100);
+            }
+        })
+    });
+}
+
+fn bench_lookup(c: &mut Criterion) {
+    let keys = keys();
+    ProfileCollector::clear_all();
+    for key in &keys {
+        ProfileCollector::record(key, 100);
+    }
+
+    c.bench_function("get_stats across 10k keys", |b| {
+        b.iter(|| {
+            for key in keys.iter().step_by(100) {
+                black_box(ProfileCollector::get_stats(key));
+            }
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_record_distinct_keys,
+    bench_record_existing_keys,
+    bench_lookup
+);
+criterion_main!(benches);
@@ -0,0 +1,107 @@
+//! Heap used by operation keys with 10k distinct keys, before and after interning
+//!
+//! Run with: cargo bench --bench key_memory --features full
+//!
+//! "Before" stores an owned `String` key in each per-key map the collector
+//! keeps (stats, categories and captured category metadata); "after" shares
+//! one interned `Arc<str>` between them. The last line is the collector's own
+//! heap growth for the same keys, histograms included.
+
+use quantum_pulse::{DefaultCategory, ProfileCollector};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::{HashMap, HashSet};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicIsize, Ordering};
+use std::sync::Arc;
+
+const KEY_COUNT: usize = 10_000;
+
+/// Maps keyed by operation key in the collector
+const MAPS: usize = 3;
+
+/// Tracks bytes currently allocated
+struct Counting;
+
+static LIVE_BYTES: AtomicIsize = AtomicIsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        LIVE_BYTES.fetch_add(layout.size() as isize, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE_BYTES.fetch_sub(layout.size() as isize, Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Heap bytes still held after building the value returned by `build`
+fn retained<T>(build: impl FnOnce() -> T) -> (isize, T) {
+    let before = LIVE_BYTES.load(Ordering::Relaxed);
+    let value = black_box(build());
+    (LIVE_BYTES.load(Ordering::Relaxed) - before, value)
+}
+
+fn keys() -> Vec<String> {
+    (0..KEY_COUNT)
+        .map(|i| format!("Database::query_with_a_fairly_long_operation_name_{}", i))
+        .collect()
+}
+
+fn report(label: &str, bytes: isize) {
+    println!(
+        "{:<40} {:>10} bytes  {:>6} bytes/key",
+        label,
+        bytes,
+        bytes / KEY_COUNT as isize
+    );
+}
+
+fn main() {
+    let keys = keys();
+
+    let (owned, maps) = retained(|| {
+        (0..MAPS)
+            .map(|_| {
+                keys.iter()
+                    .map(|key| (key.clone(), 0u8))
+                    .collect::<HashMap<String, u8>>()
+            })
+            .collect::<Vec<_>>()
+    });
+    drop(maps);
+
+    let (interned, maps) = retained(|| {
+        let interner: HashSet<Arc<str>> = keys.iter().map(|key| Arc::from(key.as_str())).collect();
+        let maps = (0..MAPS)
+            .map(|_| {
+                interner
+                    .iter()
+                    .map(|key| (Arc::clone(key), 0u8))
+                    .collect::<HashMap<Arc<str>, u8>>()
+            })
+            .collect::<Vec<_>>();
+        (interner, maps)
+    });
+    drop(maps);
+
+    ProfileCollector::clear_all();
+    let (collector, ()) = retained(|| {
+        for key in &keys {
+            ProfileCollector::record_with_category(key, DefaultCategory::IO, 100);
+        }
+    });
+    ProfileCollector::clear_all();
+
+    println!(
+        "{} distinct keys, {} maps keyed by operation",
+        KEY_COUNT, MAPS
+    );
+    report("before: String key per map", owned);
+    report("after: interned Arc<str> keys", interned);
+    report("collector with interned keys", collector);
+}
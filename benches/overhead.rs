@@ -0,0 +1,68 @@
+//! Benchmarks for the cost of profiling itself
+//!
+//! Without features this measures the stub, which should be indistinguishable
+//! from the bare workload:
+//!
+//!     cargo bench --bench overhead
+//!
+//! With the `full` feature it measures the real recording cost:
+//!
+//!     cargo bench --bench overhead --features full
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use quantum_pulse::{profile, ProfileCollector, ProfileOp};
+use std::thread;
+
+const THREADS: usize = 4;
+const RECORDS_PER_THREAD: usize = 10_000;
+
+#[derive(Debug, ProfileOp)]
+enum BenchOperation {
+    #[category(name = "Bench")]
+    Work,
+}
+
+fn bench_profile_macro(c: &mut Criterion) {
+    ProfileCollector::clear_all();
+
+    c.bench_function("bare workload", |b| b.iter(|| black_box(1 + 1)));
+
+    c.bench_function("profile! around workload", |b| {
+        b.iter(|| profile!(BenchOperation::Work, { black_box(1 + 1) }))
+    });
+}
+
+fn bench_record(c: &mut Criterion) {
+    ProfileCollector::clear_all();
+
+    c.bench_function("record into existing key", |b| {
+        b.iter(|| ProfileCollector::record(black_box("Bench::record"), 100))
+    });
+}
+
+fn bench_concurrent_record(c: &mut Criterion) {
+    ProfileCollector::clear_all();
+
+    c.bench_function("concurrent record, 4 threads x 10k", |b| {
+        b.iter(|| {
+            thread::scope(|scope| {
+                for thread_index in 0..THREADS {
+                    scope.spawn(move || {
+                        let key = format!("Bench::thread_{}", thread_index % 2);
+                        for _ in 0..RECORDS_PER_THREAD {
+                            ProfileCollector::record(black_box(&key), 100);
+                        }
+                    });
+                }
+            })
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_profile_macro,
+    bench_record,
+    bench_concurrent_record
+);
+criterion_main!(benches);
@@ -0,0 +1,48 @@
+//! Benchmarks comparing stats backends under concurrent recording
+//!
+//!     cargo bench --bench store_concurrency --features full,dashmap
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use quantum_pulse::{DashMapStore, HashMapStore, OperationStats, StatsStore};
+use std::thread;
+use std::time::Duration;
+
+const THREADS: usize = 8;
+const RECORDS_PER_THREAD: usize = 10_000;
+const KEYS: usize = 16;
+
+fn record_concurrently(store: &dyn StatsStore) {
+    thread::scope(|scope| {
+        for thread_index in 0..THREADS {
+            scope.spawn(move || {
+                for record in 0..RECORDS_PER_THREAD {
+                    let key = format!("Bench::key_{}", (thread_index + record) % KEYS);
+                    store.record(
+                        black_box(&key),
+                        &mut OperationStats::default,
+                        &mut |stats| stats.record(Duration::from_micros(100)),
+                    );
+                }
+            });
+        }
+    })
+}
+
+fn bench_stores(c: &mut Criterion) {
+    let mut group = c.benchmark_group("concurrent record, 8 threads x 10k");
+
+    let hash_map = HashMapStore::default();
+    group.bench_function("HashMapStore", |b| {
+        b.iter(|| record_concurrently(&hash_map))
+    });
+
+    let dash_map = DashMapStore::default();
+    group.bench_function("DashMapStore", |b| {
+        b.iter(|| record_concurrently(&dash_map))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_stores);
+criterion_main!(benches);
@@ -1,6 +1,6 @@
 //! Example demonstrating async profiling capabilities with enum-based operations using Operation trait
 
-use quantum_pulse::{profile_async, Category, Operation, ProfileCollector};
+use quantum_pulse::{profile_async, profile_async_block, Category, Operation, ProfileCollector};
 use std::time::Duration;
 use tokio::time::sleep;
 use tokio_stream::{self as stream, StreamExt};
@@ -421,33 +421,32 @@ async fn handle_message_queue() {
 async fn orchestrate_services() {
     println!("   Orchestrating services...");
 
+    // profile_async_block! awaits in place, so the `.await` can't be forgotten
+
     // Service coordination
     let coord_op = AsyncOperation::ServiceCoordination;
-    profile_async!(coord_op, async {
+    profile_async_block!(coord_op, {
         simulate_async_work(50).await;
         println!("     Services coordinated");
-    })
-    .await;
+    });
 
     // Workflow orchestration
     let workflow_op = AsyncOperation::WorkflowOrchestration;
-    profile_async!(workflow_op, async {
+    profile_async_block!(workflow_op, {
         // Simulate complex workflow
         for step in 1..=3 {
             simulate_async_work(30).await;
             println!("       Workflow step {} completed", step);
         }
         println!("     Workflow orchestration complete");
-    })
-    .await;
+    });
 
     // Complex analysis
     let analysis_op = AsyncOperation::ComplexAnalysis;
-    profile_async!(analysis_op, async {
+    profile_async_block!(analysis_op, {
         simulate_async_work(80).await;
         println!("     Complex analysis complete");
-    })
-    .await;
+    });
 
     println!("   Service orchestration complete");
 }
@@ -615,7 +614,8 @@ mod tests {
         let result = profile_async!(op, async {
             sleep(Duration::from_millis(1)).await;
             "authenticated"
-        });
+        })
+        .await;
 
         assert_eq!(result, "authenticated");
         assert!(ProfileCollector::has_data());
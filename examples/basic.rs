@@ -202,11 +202,7 @@ async fn main() {
 
     let all_stats = ProfileCollector::get_all_stats();
     for (operation_name, stats) in all_stats {
-        println!("📊 {}:", operation_name);
-        println!("   Calls: {}", stats.count);
-        println!("   Mean: {:?}", stats.mean());
-        println!("   Total: {:?}", stats.total);
-        println!();
+        println!("📊 {}: {}", operation_name, stats);
     }
 }
 
@@ -1,6 +1,8 @@
 //! Example demonstrating custom categories with enum operations using Operation trait
 
-use quantum_pulse::{profile, profile_async, Category, Operation, ProfileCollector};
+use quantum_pulse::{
+    define_categories, profile, profile_async, Category, Operation, ProfileCollector,
+};
 use std::thread;
 use std::time::Duration;
 use tokio::time::sleep;
@@ -41,158 +43,21 @@ enum WebAppOperation {
     BackupData,
 }
 
-/// Database category for all database operations
-#[derive(Debug)]
-struct DatabaseCategory;
-
-impl Category for DatabaseCategory {
-    fn get_name(&self) -> &str {
-        "Database"
-    }
-
-    fn get_description(&self) -> &str {
-        "All database queries, transactions, and maintenance operations"
-    }
-
-    fn color_hint(&self) -> Option<&str> {
-        Some("#FF6B6B")
-    }
-
-    fn priority(&self) -> i32 {
-        1
-    }
-}
-
-/// External API category for third-party service calls
-#[derive(Debug)]
-struct ExternalApiCategory;
-
-impl Category for ExternalApiCategory {
-    fn get_name(&self) -> &str {
-        "ExternalAPI"
-    }
-
-    fn get_description(&self) -> &str {
-        "Calls to third-party services and external APIs"
-    }
-
-    fn color_hint(&self) -> Option<&str> {
-        Some("#4ECDC4")
-    }
-
-    fn priority(&self) -> i32 {
-        2
-    }
-}
-
-/// Cache category for caching operations
-#[derive(Debug)]
-struct CacheCategory;
-
-impl Category for CacheCategory {
-    fn get_name(&self) -> &str {
-        "Cache"
-    }
-
-    fn get_description(&self) -> &str {
-        "Redis, Memcached, and other caching operations"
-    }
-
-    fn color_hint(&self) -> Option<&str> {
-        Some("#45B7D1")
-    }
-
-    fn priority(&self) -> i32 {
-        3
-    }
-}
-
-/// Business logic category for core application logic
-#[derive(Debug)]
-struct BusinessLogicCategory;
-
-impl Category for BusinessLogicCategory {
-    fn get_name(&self) -> &str {
-        "BusinessLogic"
-    }
-
-    fn get_description(&self) -> &str {
-        "Core application business logic and computations"
-    }
-
-    fn color_hint(&self) -> Option<&str> {
-        Some("#96CEB4")
-    }
-
-    fn priority(&self) -> i32 {
-        4
-    }
-}
-
-/// Authentication category for auth operations
-#[derive(Debug)]
-struct AuthCategory;
-
-impl Category for AuthCategory {
-    fn get_name(&self) -> &str {
-        "Authentication"
-    }
-
-    fn get_description(&self) -> &str {
-        "Authentication, authorization, and security operations"
-    }
-
-    fn color_hint(&self) -> Option<&str> {
-        Some("#DDA0DD")
-    }
-
-    fn priority(&self) -> i32 {
-        5
-    }
-}
-
-/// Serialization category for data transformation
-#[derive(Debug)]
-struct SerializationCategory;
-
-impl Category for SerializationCategory {
-    fn get_name(&self) -> &str {
-        "Serialization"
-    }
-
-    fn get_description(&self) -> &str {
-        "JSON, XML parsing and data serialization operations"
-    }
-
-    fn color_hint(&self) -> Option<&str> {
-        Some("#FFEAA7")
-    }
-
-    fn priority(&self) -> i32 {
-        6
-    }
-}
-
-/// File I/O category for file operations
-#[derive(Debug)]
-struct FileIOCategory;
-
-impl Category for FileIOCategory {
-    fn get_name(&self) -> &str {
-        "FileIO"
-    }
-
-    fn get_description(&self) -> &str {
-        "File system read/write operations and logging"
-    }
-
-    fn color_hint(&self) -> Option<&str> {
-        Some("#F4A460")
-    }
-
-    fn priority(&self) -> i32 {
-        7
-    }
+define_categories! {
+    /// Database category for all database operations
+    Database => "All database queries, transactions, and maintenance operations" @ "#FF6B6B" prio 1,
+    /// External API category for third-party service calls
+    ExternalAPI => "Calls to third-party services and external APIs" @ "#4ECDC4" prio 2,
+    /// Cache category for caching operations
+    Cache => "Redis, Memcached, and other caching operations" @ "#45B7D1" prio 3,
+    /// Business logic category for core application logic
+    BusinessLogic => "Core application business logic and computations" @ "#96CEB4" prio 4,
+    /// Authentication category for auth operations
+    Authentication => "Authentication, authorization, and security operations" @ "#DDA0DD" prio 5,
+    /// Serialization category for data transformation
+    Serialization => "JSON, XML parsing and data serialization operations" @ "#FFEAA7" prio 6,
+    /// File I/O category for file operations
+    FileIO => "File system read/write operations and logging" @ "#F4A460" prio 7,
 }
 
 impl Operation for WebAppOperation {
@@ -200,29 +65,29 @@ impl Operation for WebAppOperation {
         match self {
             WebAppOperation::FetchUserData
             | WebAppOperation::UpdateUserProfile
-            | WebAppOperation::VacuumDatabase => &DatabaseCategory,
+            | WebAppOperation::VacuumDatabase => &Database,
 
             WebAppOperation::PaymentGatewayApi
             | WebAppOperation::SyncWithCrm
-            | WebAppOperation::SendNotification => &ExternalApiCategory,
+            | WebAppOperation::SendNotification => &ExternalAPI,
 
             WebAppOperation::CheckCache
             | WebAppOperation::UpdateCache
-            | WebAppOperation::WarmCache => &CacheCategory,
+            | WebAppOperation::WarmCache => &Cache,
 
             WebAppOperation::CalculateRecommendations | WebAppOperation::ProcessPayment => {
-                &BusinessLogicCategory
+                &BusinessLogic
             }
 
-            WebAppOperation::CheckAuthToken | WebAppOperation::ValidatePermissions => &AuthCategory,
-
-            WebAppOperation::SerializeResponse | WebAppOperation::ParseRequest => {
-                &SerializationCategory
+            WebAppOperation::CheckAuthToken | WebAppOperation::ValidatePermissions => {
+                &Authentication
             }
 
+            WebAppOperation::SerializeResponse | WebAppOperation::ParseRequest => &Serialization,
+
             WebAppOperation::WriteAccessLog
             | WebAppOperation::ProcessUploadedFile
-            | WebAppOperation::BackupData => &FileIOCategory,
+            | WebAppOperation::BackupData => &FileIO,
         }
     }
 
@@ -557,12 +422,12 @@ mod tests {
 
     #[test]
     fn test_category_properties() {
-        let db_cat = DatabaseCategory;
+        let db_cat = Database;
         assert_eq!(db_cat.get_name(), "Database");
         assert_eq!(db_cat.priority(), 1);
         assert!(db_cat.color_hint().is_some());
 
-        let ext_cat = ExternalApiCategory;
+        let ext_cat = ExternalAPI;
         assert_eq!(ext_cat.get_name(), "ExternalAPI");
         assert_eq!(ext_cat.priority(), 2);
     }
@@ -0,0 +1,98 @@
+//! Example demonstrating a live latency monitor fed by recording events
+//!
+//! Run with: cargo run --example live_monitor --features full,tokio
+
+use quantum_pulse::{profile_async_block, ProfileCollector, ProfileOp};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+
+#[derive(Debug, ProfileOp)]
+enum ServiceOperation {
+    #[category(name = "Database", description = "Database queries")]
+    LoadUser,
+
+    #[category(name = "Cache", description = "Cache lookups")]
+    CacheLookup,
+
+    #[category(name = "Network", description = "Downstream calls")]
+    CallInventory,
+}
+
+/// Running totals for one operation
+#[derive(Default)]
+struct Tally {
+    count: u64,
+    total_micros: u64,
+    max_micros: u64,
+}
+
+async fn handle_request(request: u64) {
+    profile_async_block!(ServiceOperation::CacheLookup, {
+        tokio::time::sleep(Duration::from_millis(1)).await;
+    });
+
+    if request.is_multiple_of(3) {
+        profile_async_block!(ServiceOperation::LoadUser, {
+            tokio::time::sleep(Duration::from_millis(8)).await;
+        });
+    }
+
+    profile_async_block!(ServiceOperation::CallInventory, {
+        tokio::time::sleep(Duration::from_millis(3 + request % 5)).await;
+    });
+}
+
+#[tokio::main]
+async fn main() {
+    println!("📡 Live latency monitor\n");
+
+    // Subscribe before work starts so no recordings are missed
+    let mut recordings = ProfileCollector::subscribe();
+
+    let monitor = tokio::spawn(async move {
+        let mut tallies: BTreeMap<String, Tally> = BTreeMap::new();
+        let mut seen = 0u64;
+
+        loop {
+            match recordings.recv().await {
+                Ok((key, micros)) => {
+                    let tally = tallies.entry(key).or_default();
+                    tally.count += 1;
+                    tally.total_micros += micros;
+                    tally.max_micros = tally.max_micros.max(micros);
+
+                    seen += 1;
+                    if seen.is_multiple_of(10) {
+                        println!("── after {} recordings ──", seen);
+                        for (key, tally) in &tallies {
+                            println!(
+                                "  {:<24} {:>4} calls, avg {:>6}µs, max {:>6}µs",
+                                key,
+                                tally.count,
+                                tally.total_micros / tally.count,
+                                tally.max_micros
+                            );
+                        }
+                    }
+                }
+                // The monitor fell behind; keep going with the newest data
+                Err(RecvError::Lagged(missed)) => {
+                    println!("  ⚠️  monitor lagged, skipped {} recordings", missed);
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+
+    let requests: Vec<_> = (0..12).map(|i| tokio::spawn(handle_request(i))).collect();
+    for request in requests {
+        let _ = request.await;
+    }
+
+    // Give the monitor a moment to drain, then stop it
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    monitor.abort();
+
+    println!("\n✅ Done");
+}
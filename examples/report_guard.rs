@@ -0,0 +1,43 @@
+//! Example demonstrating automatic report printing with a drop guard
+//!
+//! Run with: cargo run --example report_guard --features full
+
+use quantum_pulse::{profile, ProfileCollector, ProfileOp};
+use std::thread;
+use std::time::Duration;
+
+#[derive(Debug, ProfileOp)]
+enum ScriptOperation {
+    #[category(name = "IO", description = "File and network access")]
+    LoadInput,
+
+    #[category(name = "Compute", description = "Data processing")]
+    Transform,
+
+    #[category(name = "IO")]
+    WriteOutput,
+}
+
+fn main() {
+    // The report is printed when `_guard` goes out of scope at the end of main,
+    // so it can never be forgotten - even on early returns.
+    let _guard = ProfileCollector::print_on_drop(Default::default());
+
+    println!("🚀 Running script with automatic report on exit\n");
+
+    profile!(ScriptOperation::LoadInput, {
+        thread::sleep(Duration::from_millis(5));
+    });
+
+    for _ in 0..3 {
+        profile!(ScriptOperation::Transform, {
+            thread::sleep(Duration::from_millis(2));
+        });
+    }
+
+    profile!(ScriptOperation::WriteOutput, {
+        thread::sleep(Duration::from_millis(3));
+    });
+
+    println!("✅ Script finished, report follows:\n");
+}
@@ -44,135 +44,28 @@ enum TradingOperation {
     OrderBookUpdate,
 }
 
-/// Order processing category for trade lifecycle operations
-#[derive(Debug)]
-struct OrderProcessingCategory;
-
-impl Category for OrderProcessingCategory {
-    fn get_name(&self) -> &str {
-        "OrderProcessing"
-    }
-
-    fn get_description(&self) -> &str {
-        "Order lifecycle operations including validation, submission, and execution"
-    }
-
-    fn color_hint(&self) -> Option<&str> {
-        Some("#FF6B6B")
-    }
-
-    fn priority(&self) -> i32 {
-        1 // Highest priority - critical path
-    }
-}
-
-/// Market data category for price feed and market information
-#[derive(Debug)]
-struct MarketDataCategory;
-
-impl Category for MarketDataCategory {
-    fn get_name(&self) -> &str {
-        "MarketData"
-    }
-
-    fn get_description(&self) -> &str {
-        "Market data processing, price feeds, and market analysis"
-    }
-
-    fn color_hint(&self) -> Option<&str> {
-        Some("#4ECDC4")
-    }
-
-    fn priority(&self) -> i32 {
-        2
-    }
-}
-
-/// Risk management category for risk assessment and control
-#[derive(Debug)]
-struct RiskManagementCategory;
-
-impl Category for RiskManagementCategory {
-    fn get_name(&self) -> &str {
-        "RiskManagement"
-    }
-
-    fn get_description(&self) -> &str {
-        "Risk assessment, position monitoring, and exposure calculations"
-    }
-
-    fn color_hint(&self) -> Option<&str> {
-        Some("#E74C3C")
-    }
-
-    fn priority(&self) -> i32 {
-        1 // Also highest priority - risk critical
-    }
-}
-
-/// Portfolio management category for portfolio operations
-#[derive(Debug)]
-struct PortfolioCategory;
-
-impl Category for PortfolioCategory {
-    fn get_name(&self) -> &str {
-        "Portfolio"
-    }
-
-    fn get_description(&self) -> &str {
-        "Portfolio management, PnL calculations, and optimization"
-    }
-
-    fn color_hint(&self) -> Option<&str> {
-        Some("#96CEB4")
-    }
-
-    fn priority(&self) -> i32 {
-        3
-    }
-}
-
-/// External communication category for exchange and data connections
-#[derive(Debug)]
-struct ExternalCommCategory;
-
-impl Category for ExternalCommCategory {
-    fn get_name(&self) -> &str {
-        "ExternalComm"
-    }
-
-    fn get_description(&self) -> &str {
-        "External communications with exchanges, data providers, and regulators"
-    }
-
-    fn color_hint(&self) -> Option<&str> {
-        Some("#F39C12")
-    }
-
-    fn priority(&self) -> i32 {
-        4
-    }
-}
-
-/// High-frequency operations category for latency-critical operations
-#[derive(Debug)]
-struct HighFrequencyCategory;
-
-impl Category for HighFrequencyCategory {
-    fn get_name(&self) -> &str {
-        "HighFrequency"
-    }
-
-    fn get_description(&self) -> &str {
-        "Ultra-low latency operations for high-frequency trading"
-    }
-
-    fn color_hint(&self) -> Option<&str> {
-        Some("#9B59B6")
-    }
-
-    fn priority(&self) -> i32 {
-        1 // Highest priority - latency critical
+/// Trading categories; in a module because `MarketData` is also the name of
+/// the simulated market data struct below
+mod categories {
+    quantum_pulse::define_categories! {
+        /// Order lifecycle operations, on the critical path
+        pub OrderProcessing => "Order lifecycle operations including validation, submission, and execution"
+            @ "#FF6B6B" prio 1,
+        /// Price feeds and market information
+        pub MarketData => "Market data processing, price feeds, and market analysis"
+            @ "#4ECDC4" prio 2,
+        /// Risk assessment and control, risk critical
+        pub RiskManagement => "Risk assessment, position monitoring, and exposure calculations"
+            @ "#E74C3C" prio 1,
+        /// Portfolio operations
+        pub Portfolio => "Portfolio management, PnL calculations, and optimization"
+            @ "#96CEB4" prio 3,
+        /// Exchange and data connections
+        pub ExternalComm => "External communications with exchanges, data providers, and regulators"
+            @ "#F39C12" prio 4,
+        /// Latency-critical operations
+        pub HighFrequency => "Ultra-low latency operations for high-frequency trading"
+            @ "#9B59B6" prio 1,
     }
 }
 
@@ -183,29 +76,29 @@ impl Operation for TradingOperation {
             | TradingOperation::OrderSubmission
             | TradingOperation::OrderExecution
             | TradingOperation::OrderConfirmation
-            | TradingOperation::OrderCancellation => &OrderProcessingCategory,
+            | TradingOperation::OrderCancellation => &categories::OrderProcessing,
 
             TradingOperation::PriceFeedParsing
             | TradingOperation::MarketDataUpdate
             | TradingOperation::VolatilityCalculation
-            | TradingOperation::TechnicalAnalysis => &MarketDataCategory,
+            | TradingOperation::TechnicalAnalysis => &categories::MarketData,
 
             TradingOperation::RiskAssessment
             | TradingOperation::PositionUpdate
             | TradingOperation::ExposureCalculation
-            | TradingOperation::LimitCheck => &RiskManagementCategory,
+            | TradingOperation::LimitCheck => &categories::RiskManagement,
 
             TradingOperation::PnlCalculation
             | TradingOperation::RebalanceOperation
-            | TradingOperation::PortfolioOptimization => &PortfolioCategory,
+            | TradingOperation::PortfolioOptimization => &categories::Portfolio,
 
             TradingOperation::ExchangeConnection
             | TradingOperation::MarketDataFeed
-            | TradingOperation::RegulatoryReporting => &ExternalCommCategory,
+            | TradingOperation::RegulatoryReporting => &categories::ExternalComm,
 
             TradingOperation::ArbitrageDetection
             | TradingOperation::LatencyMeasurement
-            | TradingOperation::OrderBookUpdate => &HighFrequencyCategory,
+            | TradingOperation::OrderBookUpdate => &categories::HighFrequency,
         }
     }
 
@@ -829,16 +722,16 @@ mod tests {
 
     #[test]
     fn test_category_priorities() {
-        let order_cat = OrderProcessingCategory;
+        let order_cat = categories::OrderProcessing;
         assert_eq!(order_cat.priority(), 1);
 
-        let risk_cat = RiskManagementCategory;
+        let risk_cat = categories::RiskManagement;
         assert_eq!(risk_cat.priority(), 1);
 
-        let hft_cat = HighFrequencyCategory;
+        let hft_cat = categories::HighFrequency;
         assert_eq!(hft_cat.priority(), 1);
 
-        let portfolio_cat = PortfolioCategory;
+        let portfolio_cat = categories::Portfolio;
         assert_eq!(portfolio_cat.priority(), 3);
     }
 
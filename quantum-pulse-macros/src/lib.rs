@@ -6,7 +6,7 @@
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
 use std::collections::HashMap;
-use syn::{parse_macro_input, Data, DeriveInput};
+use syn::{parse_macro_input, Data, DeriveInput, ItemFn, LitStr, ReturnType, Type};
 
 /// Derives the `Operation` trait for enums, automatically generating category implementations.
 ///
@@ -17,8 +17,20 @@ use syn::{parse_macro_input, Data, DeriveInput};
 /// # Attributes
 ///
 /// The macro supports the `#[category(...)]` attribute on enum variants with the following parameters:
-/// - `name`: The name of the category (optional, defaults to variant name)
+/// - `name`: The name of the category (optional, defaults to the enum's default
+///   category, or to the variant name if there is none)
 /// - `description`: A description of the category (optional, defaults to category name)
+/// - `icon`: An icon or emoji shown before the category name in reports (optional)
+/// - `id`: A stable numeric id for the variant, returned by `Operation::op_id` (optional).
+///   Ids must be unique within the enum and non-zero, since 0 means "no id"
+/// - `expected_us`: The known-good mean latency in microseconds, returned by
+///   `Operation::expected_micros` and flagged against in reports (optional)
+/// - `order`: The variant's position within its category in reports, returned by
+///   `Operation::sort_hint` (optional, defaults to 0)
+///
+/// The enum itself accepts `#[profile(default_category = "...")]`, which puts every
+/// variant without a category `name` into that category instead of one named after
+/// the variant.
 ///
 /// # Important Behavior
 ///
@@ -26,6 +38,7 @@ use syn::{parse_macro_input, Data, DeriveInput};
 /// - Only one category struct is generated per unique category name
 /// - The first `description` encountered for a category name is used
 /// - Subsequent descriptions for the same category name are ignored
+/// - The first `icon` encountered for a category name is used
 ///
 /// # Example
 ///
@@ -42,11 +55,16 @@ use syn::{parse_macro_input, Data, DeriveInput};
 ///     #[category(name = "IO", description = "This description is ignored")]
 ///     WriteFile,
 ///
+///     // Category with an icon shown in report headers
+///     #[category(name = "Metrics", icon = "📊")]
+///     Collect,
+///
 ///     // Category with only name (description defaults to name)
 ///     #[category(name = "Network")]
 ///     HttpRequest,
 ///
-///     // No category attribute (uses variant name as category)
+///     // No category attribute (uses variant name as category, unless the
+///     // enum sets `#[profile(default_category = "...")]`)
 ///     Compute,
 ///
 ///     // Supports enum variants with data
@@ -64,12 +82,20 @@ use syn::{parse_macro_input, Data, DeriveInput};
 /// For each unique category, the macro generates:
 /// - A hidden struct implementing the `Category` trait
 /// - An implementation of `Operation::get_category()` that returns the appropriate category
+/// - An implementation of `Operation::op_id()` when any variant has an `id`
+/// - An implementation of `Operation::expected_micros()` when any variant has an `expected_us`
+/// - An implementation of `Operation::sort_hint()` when any variant has an `order`
+/// - An `OPERATIONS` constant listing the `"Category::Variant"` key of every unit variant,
+///   for use with `ProfileCollector::validate_known`. Variants with fields are omitted
+///   because their keys depend on the field values.
 ///
 /// # Panics
 ///
 /// - If applied to anything other than an enum
 /// - If the category attribute parsing fails
-#[proc_macro_derive(Operation, attributes(category))]
+///
+/// Duplicate or zero ids are reported as compile errors.
+#[proc_macro_derive(Operation, attributes(category, profile))]
 pub fn derive_operation(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let enum_name = &input.ident;
@@ -79,15 +105,43 @@ pub fn derive_operation(input: TokenStream) -> TokenStream {
         _ => panic!("Operation can only be derived for enums"),
     };
 
+    // Parse the container attribute
+    let mut default_category = None;
+    for attr in &input.attrs {
+        if attr.path().is_ident("profile") {
+            let nested = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("default_category") {
+                    let value = meta.value()?;
+                    let s: syn::LitStr = value.parse()?;
+                    default_category = Some(s.value());
+                } else {
+                    return Err(meta.error("unrecognized profile attribute"));
+                }
+                Ok(())
+            });
+
+            if let Err(err) = nested {
+                panic!("Failed to parse profile attribute: {}", err);
+            }
+        }
+    }
+
     // Track unique categories by name
     let mut categories: HashMap<String, CategoryInfo> = HashMap::new();
     let mut variant_categories: Vec<String> = Vec::new();
+    let mut variant_ids: Vec<Option<syn::LitInt>> = Vec::new();
+    let mut variant_expected: Vec<Option<u64>> = Vec::new();
+    let mut variant_orders: Vec<Option<i32>> = Vec::new();
 
     // First pass: collect all categories and their info
     for variant in &data_enum.variants {
         let variant_ident = &variant.ident;
         let mut category_name = None;
         let mut category_description = None;
+        let mut category_icon = None;
+        let mut operation_id = None;
+        let mut expected_micros = None;
+        let mut sort_hint = None;
 
         // Parse the category attribute
         for attr in &variant.attrs {
@@ -101,6 +155,23 @@ pub fn derive_operation(input: TokenStream) -> TokenStream {
                         let value = meta.value()?;
                         let s: syn::LitStr = value.parse()?;
                         category_description = Some(s.value());
+                    } else if meta.path.is_ident("icon") {
+                        let value = meta.value()?;
+                        let s: syn::LitStr = value.parse()?;
+                        category_icon = Some(s.value());
+                    } else if meta.path.is_ident("id") {
+                        let value = meta.value()?;
+                        let id: syn::LitInt = value.parse()?;
+                        id.base10_parse::<u32>()?;
+                        operation_id = Some(id);
+                    } else if meta.path.is_ident("expected_us") {
+                        let value = meta.value()?;
+                        let micros: syn::LitInt = value.parse()?;
+                        expected_micros = Some(micros.base10_parse::<u64>()?);
+                    } else if meta.path.is_ident("order") {
+                        let value = meta.value()?;
+                        let order: syn::LitInt = value.parse()?;
+                        sort_hint = Some(order.base10_parse::<i32>()?);
                     } else {
                         return Err(meta.error("unrecognized category attribute"));
                     }
@@ -113,8 +184,11 @@ pub fn derive_operation(input: TokenStream) -> TokenStream {
             }
         }
 
-        // Determine the category name (default to variant name if not specified)
-        let final_category_name = category_name.unwrap_or_else(|| format!("{}", variant_ident));
+        // Determine the category name (default to the enum's default category,
+        // then to the variant name, if not specified)
+        let final_category_name = category_name
+            .or_else(|| default_category.clone())
+            .unwrap_or_else(|| format!("{}", variant_ident));
 
         // Only update the category info if it hasn't been defined yet or if this one has a description
         if !categories.contains_key(&final_category_name) {
@@ -123,24 +197,54 @@ pub fn derive_operation(input: TokenStream) -> TokenStream {
                 CategoryInfo {
                     name: final_category_name.clone(),
                     description: category_description.unwrap_or_else(|| final_category_name.clone()),
+                    icon: category_icon,
                 },
             );
-        } else if category_description.is_some() {
+        } else {
+            let existing = categories.get_mut(&final_category_name).unwrap();
+
             // If this category already exists but this variant provides a description,
             // only update if the existing one doesn't have a custom description
-            let existing = categories.get(&final_category_name).unwrap();
-            if existing.description == final_category_name {
-                categories.insert(
-                    final_category_name.clone(),
-                    CategoryInfo {
-                        name: final_category_name.clone(),
-                        description: category_description.unwrap(),
-                    },
-                );
+            if let Some(description) = category_description {
+                if existing.description == final_category_name {
+                    existing.description = description;
+                }
+            }
+
+            // Likewise, the first icon for a category wins
+            if existing.icon.is_none() {
+                existing.icon = category_icon;
             }
         }
 
         variant_categories.push(final_category_name);
+        variant_ids.push(operation_id);
+        variant_expected.push(expected_micros);
+        variant_orders.push(sort_hint);
+    }
+
+    // Ids must be unique, and 0 is reserved for operations without one
+    let mut seen_ids: HashMap<u32, &syn::Ident> = HashMap::new();
+    for (variant, id) in data_enum.variants.iter().zip(variant_ids.iter()) {
+        let Some(id) = id else {
+            continue;
+        };
+        let value: u32 = id.base10_parse().unwrap();
+        let error = if value == 0 {
+            Some("operation id 0 is reserved for operations without an id".to_string())
+        } else {
+            seen_ids.insert(value, &variant.ident).map(|first| {
+                format!(
+                    "operation id {} is already used by `{}::{}`",
+                    value, enum_name, first
+                )
+            })
+        };
+        if let Some(message) = error {
+            return syn::Error::new_spanned(id, message)
+                .to_compile_error()
+                .into();
+        }
     }
 
     // Generate category structs for unique categories
@@ -154,6 +258,13 @@ pub fn derive_operation(input: TokenStream) -> TokenStream {
             );
             let cat_name = &cat_info.name;
             let cat_description = &cat_info.description;
+            let icon_fn = cat_info.icon.as_ref().map(|icon| {
+                quote! {
+                    fn icon(&self) -> Option<&str> {
+                        Some(#icon)
+                    }
+                }
+            });
 
             quote! {
                 #[doc(hidden)]
@@ -169,6 +280,8 @@ pub fn derive_operation(input: TokenStream) -> TokenStream {
                     fn get_description(&self) -> &str {
                         #cat_description
                     }
+
+                    #icon_fn
                 }
             }
         })
@@ -197,6 +310,95 @@ pub fn derive_operation(input: TokenStream) -> TokenStream {
         })
         .collect();
 
+    // Only generated when ids are in use; otherwise the trait default applies
+    let op_id_fn = variant_ids.iter().any(Option::is_some).then(|| {
+        let id_arms = data_enum
+            .variants
+            .iter()
+            .zip(variant_ids.iter())
+            .map(|(variant, id)| {
+                let variant_ident = &variant.ident;
+                let pattern = match &variant.fields {
+                    syn::Fields::Unit => quote! { #enum_name::#variant_ident },
+                    syn::Fields::Unnamed(_) => quote! { #enum_name::#variant_ident(..) },
+                    syn::Fields::Named(_) => quote! { #enum_name::#variant_ident{..} },
+                };
+                let id = id
+                    .as_ref()
+                    .map_or_else(|| quote! { 0 }, |id| quote! { #id });
+                quote! {
+                    #pattern => #id,
+                }
+            });
+
+        quote! {
+            fn op_id(&self) -> u32 {
+                match self {
+                    #(#id_arms)*
+                }
+            }
+        }
+    });
+
+    // Likewise for latency baselines
+    let expected_micros_fn =
+        variant_expected.iter().any(Option::is_some).then(|| {
+            let expected_arms = data_enum.variants.iter().zip(variant_expected.iter()).map(
+                |(variant, expected)| {
+                    let variant_ident = &variant.ident;
+                    let pattern = match &variant.fields {
+                        syn::Fields::Unit => quote! { #enum_name::#variant_ident },
+                        syn::Fields::Unnamed(_) => quote! { #enum_name::#variant_ident(..) },
+                        syn::Fields::Named(_) => quote! { #enum_name::#variant_ident{..} },
+                    };
+                    let expected = match expected {
+                        Some(micros) => quote! { Some(#micros) },
+                        None => quote! { None },
+                    };
+                    quote! {
+                        #pattern => #expected,
+                    }
+                },
+            );
+
+            quote! {
+                fn expected_micros(&self) -> Option<u64> {
+                    match self {
+                        #(#expected_arms)*
+                    }
+                }
+            }
+        });
+
+    // And for report ordering
+    let sort_hint_fn = variant_orders.iter().any(Option::is_some).then(|| {
+        let order_arms =
+            data_enum
+                .variants
+                .iter()
+                .zip(variant_orders.iter())
+                .map(|(variant, order)| {
+                    let variant_ident = &variant.ident;
+                    let pattern = match &variant.fields {
+                        syn::Fields::Unit => quote! { #enum_name::#variant_ident },
+                        syn::Fields::Unnamed(_) => quote! { #enum_name::#variant_ident(..) },
+                        syn::Fields::Named(_) => quote! { #enum_name::#variant_ident{..} },
+                    };
+                    let order = order.unwrap_or(0);
+                    quote! {
+                        #pattern => #order,
+                    }
+                });
+
+        quote! {
+            fn sort_hint(&self) -> i32 {
+                match self {
+                    #(#order_arms)*
+                }
+            }
+        }
+    });
+
     // Handle empty enums specially
     let operation_impl = if data_enum.variants.is_empty() {
         quote! {
@@ -214,19 +416,185 @@ pub fn derive_operation(input: TokenStream) -> TokenStream {
                         #(#match_arms)*
                     }
                 }
+
+                #op_id_fn
+
+                #expected_micros_fn
+
+                #sort_hint_fn
             }
         }
     };
 
+    // Collect the recording keys of unit variants
+    let operation_keys: Vec<String> = data_enum
+        .variants
+        .iter()
+        .zip(variant_categories.iter())
+        .filter(|(variant, _)| matches!(variant.fields, syn::Fields::Unit))
+        .map(|(variant, category_name)| format!("{}::{}", category_name, variant.ident))
+        .collect();
+
     let expanded = quote! {
         #(#category_defs)*
 
         #operation_impl
+
+        impl #enum_name {
+            /// Recording keys of all unit variants, in declaration order
+            pub const OPERATIONS: &'static [&'static str] = &[#(#operation_keys),*];
+        }
     };
 
     TokenStream::from(expanded)
 }
 
+/// Profiles every call to a function.
+///
+/// The timer starts on entry and records when the function returns, including
+/// early `return`s and `?`.
+///
+/// # Arguments
+///
+/// - `"Category::operation"`: the key to record under, like the keys produced
+///   by `ProfileOp`; without a `::` the operation has no category
+/// - none: the function name is the operation name, with no category
+/// - `category = "..."` and/or `name = "..."`: set either part of the key,
+///   the name defaulting to the function name
+///
+/// For an `async fn`, the body is wrapped in `ProfileTimerAsync`, so the
+/// recorded time spans from the first poll until the future completes.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use quantum_pulse::profiled;
+///
+/// #[profiled("Database::fetch")]
+/// fn fetch(id: u64) -> Result<Row, Error> {
+///     let conn = connect()?;
+///     conn.query(id)
+/// }
+///
+/// #[profiled("Network::download")]
+/// async fn download(url: &str) -> Vec<u8> {
+///     client().get(url).await
+/// }
+///
+/// // Recorded under "::parse_config"
+/// #[profiled]
+/// fn parse_config() {}
+///
+/// // Recorded under "DB::query"
+/// #[profiled(category = "DB")]
+/// fn query() {}
+/// ```
+#[proc_macro_attribute]
+pub fn profiled(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut category_name = None;
+    let mut operation_name = None;
+
+    if let Ok(key) = syn::parse::<LitStr>(attr.clone()) {
+        let key = key.value();
+        match key.split_once("::") {
+            Some((category, operation)) => {
+                category_name = Some(category.to_string());
+                operation_name = Some(operation.to_string());
+            }
+            None => operation_name = Some(key),
+        }
+    } else {
+        let parser = syn::meta::parser(|meta| {
+            if meta.path.is_ident("category") {
+                let s: LitStr = meta.value()?.parse()?;
+                category_name = Some(s.value());
+            } else if meta.path.is_ident("name") {
+                let s: LitStr = meta.value()?.parse()?;
+                operation_name = Some(s.value());
+            } else {
+                return Err(meta.error("unrecognized profiled attribute"));
+            }
+            Ok(())
+        });
+        parse_macro_input!(attr with parser);
+    }
+
+    let mut function = parse_macro_input!(item as ItemFn);
+    let operation_name = operation_name.unwrap_or_else(|| function.sig.ident.to_string());
+
+    let category_def = category_name.map(|name| {
+        quote! {
+            #[derive(Debug)]
+            struct __ProfiledCategory;
+
+            impl quantum_pulse::Category for __ProfiledCategory {
+                fn get_name(&self) -> &str {
+                    #name
+                }
+
+                fn get_description(&self) -> &str {
+                    #name
+                }
+            }
+        }
+    });
+    let get_category_fn = category_def.as_ref().map(|_| {
+        quote! {
+            fn get_category(&self) -> &dyn quantum_pulse::Category {
+                &__ProfiledCategory
+            }
+        }
+    });
+
+    let operation_def = quote! {
+        #category_def
+
+        #[derive(Debug)]
+        struct __ProfiledOperation;
+
+        impl quantum_pulse::Operation for __ProfiledOperation {
+            #get_category_fn
+
+            fn to_str(&self) -> String {
+                #operation_name.to_string()
+            }
+        }
+    };
+
+    let body = &function.block;
+    let new_body = if function.sig.asyncness.is_some() {
+        // Name the output type so `?` inside the async block can infer its
+        // error conversion; `impl Trait` outputs are left to inference.
+        let output = match &function.sig.output {
+            ReturnType::Default => quote! { () },
+            ReturnType::Type(_, ty) if matches!(**ty, Type::ImplTrait(_)) => quote! { _ },
+            ReturnType::Type(_, ty) => quote! { #ty },
+        };
+
+        quote! {
+            {
+                #operation_def
+
+                quantum_pulse::ProfileTimerAsync::new(&__ProfiledOperation)
+                    .run::<_, #output>(async move #body)
+                    .await
+            }
+        }
+    } else {
+        quote! {
+            {
+                #operation_def
+
+                let __profiled_timer = quantum_pulse::ProfileTimer::new(&__ProfiledOperation);
+                #body
+            }
+        }
+    };
+
+    *function.block = syn::parse2(new_body).expect("generated body is a valid block");
+    TokenStream::from(quote! { #function })
+}
+
 /// Information about a category collected from enum variant attributes.
 ///
 /// This struct holds the parsed category information that will be used
@@ -236,6 +604,8 @@ struct CategoryInfo {
     name: String,
     /// The description of the category, defaults to the name if not specified
     description: String,
+    /// Optional icon shown before the category name in reports
+    icon: Option<String>,
 }
 
 /// Sanitizes a string to be a valid Rust identifier.
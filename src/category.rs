@@ -44,12 +44,71 @@ pub trait Category: Send + Sync {
         None
     }
 
+    /// Optional icon or emoji shown before the category name in reports
+    fn icon(&self) -> Option<&str> {
+        None
+    }
+
     /// Priority for sorting categories (lower values appear first)
     fn priority(&self) -> i32 {
         0
     }
 }
 
+/// An owned snapshot of a category's metadata
+///
+/// Operations only lend out `&dyn Category`, so the collector keeps one of
+/// these per key for reports and callers that need the category after
+/// recording, see [`ProfileCollector::category_of`](crate::ProfileCollector::category_of).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CategoryInfo {
+    /// Category name
+    pub name: String,
+    /// Category description
+    pub description: String,
+    /// Color hint for visualization (e.g., "#FF5733")
+    pub color: Option<String>,
+    /// Icon shown before the category name in reports
+    pub icon: Option<String>,
+    /// Priority for sorting categories (lower values appear first)
+    pub priority: i32,
+}
+
+impl CategoryInfo {
+    /// Copy the metadata of `category`
+    pub fn from_category(category: &dyn Category) -> Self {
+        Self {
+            name: category.get_name().to_string(),
+            description: category.get_description().to_string(),
+            color: category.color_hint().map(str::to_string),
+            icon: category.icon().map(str::to_string),
+            priority: category.priority(),
+        }
+    }
+}
+
+impl Category for CategoryInfo {
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    fn get_description(&self) -> &str {
+        &self.description
+    }
+
+    fn color_hint(&self) -> Option<&str> {
+        self.color.as_deref()
+    }
+
+    fn icon(&self) -> Option<&str> {
+        self.icon.as_deref()
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+}
+
 /// A no-op category for when categorization is not needed
 ///
 /// This is the default category returned by `Operation::get_category()`
@@ -162,10 +221,20 @@ mod tests {
         assert_eq!(DefaultCategory::General.get_name(), "General");
 
         assert!(DefaultCategory::IO.color_hint().is_some());
+        assert!(DefaultCategory::IO.icon().is_none());
         assert_eq!(DefaultCategory::IO.priority(), 1);
         assert_eq!(DefaultCategory::General.priority(), 999);
     }
 
+    #[test]
+    fn test_category_info_round_trip() {
+        let info = CategoryInfo::from_category(&DefaultCategory::Memory);
+        assert_eq!(info.name, "Memory");
+        assert_eq!(info.color.as_deref(), Some("#f39c12"));
+        assert_eq!(info.priority, 3);
+        assert_eq!(CategoryInfo::from_category(&info), info);
+    }
+
     #[test]
     fn test_custom_category_implementation() {
         #[derive(Debug)]
@@ -2,65 +2,256 @@
 //!
 //! Thread-safe centralized storage for profiling metrics.
 
-use crate::category::{Category, DefaultCategory};
-use std::collections::HashMap;
-use std::sync::{Arc, LazyLock, RwLock};
-use std::time::Duration;
+use crate::category::{Category, CategoryInfo, DefaultCategory};
+use crate::operation::Operation;
+use crate::reporter::{ReportConfig, ReportGuard};
+use crate::store::{HashMapStore, StatsStore};
+use crate::timer::TimerToken;
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant, SystemTime};
 
 #[cfg(feature = "full")]
 use hdrhistogram::Histogram;
 
 /// Statistics for a single operation
+///
+/// Durations are stored with nanosecond resolution so that sub-microsecond
+/// operations remain distinguishable. The `*_micros` accessors and fields are
+/// derived from the nanosecond values.
 #[derive(Debug, Clone)]
 pub struct OperationStats {
     /// Number of times this operation was recorded
     pub count: usize,
     /// Total time spent in this operation
     pub total: Duration,
-    /// HDR histogram for percentile calculations (full feature only)
+    /// Durations making up `total`; `count` also includes occurrences
+    /// counted without one via [`ProfileCollector::increment`]
+    timed_count: usize,
+    /// HDR histogram of recorded nanoseconds for percentile calculations
+    /// (full feature only, `None` while percentiles are disabled)
     #[cfg(feature = "full")]
-    histogram: Histogram<u64>,
+    histogram: Option<Histogram<u64>>,
     /// Min time recorded
     pub min_time_micros: u64,
     /// Max time recorded
     pub max_time_micros: u64,
+    /// Min time recorded in nanoseconds
+    pub min_time_nanos: u64,
+    /// Max time recorded in nanoseconds
+    pub max_time_nanos: u64,
+    /// Exponentially weighted moving average of durations in nanoseconds
+    ewma_nanos: f64,
+    /// When the current max was recorded
+    max_time_at: Option<SystemTime>,
+    /// When a duration was last recorded, tracked only while idle reset is on
+    last_recorded_at: Option<Instant>,
+    /// p50, p95, p99 and p99.9 in nanoseconds from an imported summary
+    imported_percentiles: Option<[u64; 4]>,
+    /// Durations recorded as exactly zero, i.e. below the clock's resolution
+    zero_duration_count: u64,
+    /// Recordings taken as warmup so far, see [`ProfileCollector::set_warmup`]
+    warmup_seen: usize,
+}
+
+/// Significant figures kept by histograms unless overridden per operation
+///
+/// Precision of 3 gives us 1‰ (0.1%) relative error with reasonable memory
+/// usage (~2KB per histogram).
+const DEFAULT_HISTOGRAM_PRECISION: u8 = 3;
+
+/// A one-line summary like `count=5 mean=1.20 ms p99=3.40 ms max=4.00 ms`
+///
+/// Durations use [`TimeFormat::Auto`](crate::TimeFormat::Auto). Operations
+/// that were only counted show just the count.
+impl std::fmt::Display for OperationStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "count={}", self.count)?;
+        if self.has_timing() {
+            let format = |nanos| crate::reporter::TimeFormat::Auto.format_nanos(nanos);
+            write!(
+                f,
+                " mean={} p99={} max={}",
+                format(self.mean_time_nanos()),
+                format(self.p99_nanos()),
+                format(self.max_time_nanos)
+            )?;
+        }
+        Ok(())
+    }
 }
 
 impl Default for OperationStats {
     fn default() -> Self {
+        Self::with_precision(DEFAULT_HISTOGRAM_PRECISION)
+    }
+}
+
+impl OperationStats {
+    /// Create empty stats whose histogram keeps `sig_figs` significant figures
+    ///
+    /// No histogram is allocated while percentiles are disabled, see
+    /// [`ProfileCollector::set_percentiles_enabled`].
+    fn with_precision(sig_figs: u8) -> Self {
         Self {
             count: 0,
             total: Duration::ZERO,
+            timed_count: 0,
             #[cfg(feature = "full")]
-            // Precision of 3 gives us microsecond accuracy (up to ~2.1 seconds max)
-            // with 1‰ (0.1%) relative error and reasonable memory usage (~2KB per histogram).
+            // Unless bounds are set, the histogram auto-resizes, so nanosecond
+            // values of any magnitude can be tracked.
             // Falls back to precision 1 if allocation fails (extremely rare).
-            histogram: Histogram::new(3).unwrap_or_else(|_| Histogram::new(1).unwrap()),
+            histogram: PERCENTILES_ENABLED.load(Ordering::Relaxed).then(|| {
+                histogram_bounds_nanos()
+                    .and_then(|(low, high)| Histogram::new_with_bounds(low, high, sig_figs).ok())
+                    .or_else(|| Histogram::new(sig_figs).ok())
+                    .unwrap_or_else(|| Histogram::new(1).unwrap())
+            }),
             min_time_micros: u64::MAX,
             max_time_micros: 0,
+            min_time_nanos: u64::MAX,
+            max_time_nanos: 0,
+            ewma_nanos: 0.0,
+            max_time_at: None,
+            last_recorded_at: None,
+            imported_percentiles: None,
+            zero_duration_count: 0,
+            warmup_seen: 0,
         }
     }
-}
 
-impl OperationStats {
+    /// Create summary-only stats from externally computed percentiles
+    ///
+    /// No histogram is kept; the percentile getters return the given values.
+    /// The minimum is unknown and reported as zero, and the max is taken
+    /// from p99.9.
+    fn from_summary(count: usize, mean: Duration, percentiles: [Duration; 4]) -> Self {
+        let nanos = percentiles.map(|p| p.as_nanos().min(u64::MAX as u128) as u64);
+        let mut stats = Self::with_precision(DEFAULT_HISTOGRAM_PRECISION);
+        #[cfg(feature = "full")]
+        {
+            stats.histogram = None;
+        }
+        stats.count = count;
+        stats.timed_count = count;
+        stats.total =
+            Duration::from_nanos((mean.as_nanos() * count as u128).min(u64::MAX as u128) as u64);
+        stats.min_time_nanos = 0;
+        stats.min_time_micros = 0;
+        stats.max_time_nanos = nanos[3];
+        stats.max_time_micros = nanos[3] / 1000;
+        stats.ewma_nanos = mean.as_nanos() as f64;
+        stats.imported_percentiles = Some(nanos);
+        stats
+    }
+
     /// Get the mean duration for this operation
+    ///
+    /// Averages over the recorded durations only, so occurrences counted
+    /// with [`ProfileCollector::increment`] don't pull it down. Warmup
+    /// recordings kept with [`ProfileCollector::set_warmup`]'s `counted` are
+    /// part of `total`, so they are included.
     pub fn mean(&self) -> Duration {
-        if self.count == 0 {
+        if self.timed_count == 0 {
             Duration::ZERO
         } else {
-            self.total / (self.count as u32)
+            Duration::from_nanos((self.total.as_nanos() / self.timed_count as u128) as u64)
         }
     }
 
+    /// Number of recorded durations behind the mean and `total`
+    ///
+    /// Equals `count` unless some occurrences were counted without a
+    /// duration via [`ProfileCollector::increment`]. Includes counted warmup
+    /// recordings, see [`ProfileCollector::set_warmup`].
+    pub fn timed_count(&self) -> usize {
+        self.timed_count
+    }
+
+    /// Get the shortest recorded duration, or zero if none was recorded
+    pub fn min(&self) -> Duration {
+        Duration::from_nanos(self.min_time_nanos())
+    }
+
+    /// Get the longest recorded duration
+    pub fn max(&self) -> Duration {
+        Duration::from_nanos(self.max_time_nanos)
+    }
+
     /// Get mean time in microseconds
     pub fn mean_time_micros(&self) -> u64 {
-        if self.count == 0 {
+        if self.timed_count == 0 {
+            0
+        } else {
+            self.total.as_micros() as u64 / self.timed_count as u64
+        }
+    }
+
+    /// Get mean time in fractional microseconds
+    ///
+    /// Keeps sub-microsecond means, such as 0.5µs, that
+    /// [`Self::mean_time_micros`] rounds down to zero.
+    pub fn mean_micros_f64(&self) -> f64 {
+        if self.timed_count == 0 {
+            0.0
+        } else {
+            self.total.as_nanos() as f64 / 1000.0 / self.timed_count as f64
+        }
+    }
+
+    /// Get mean time in nanoseconds
+    pub fn mean_time_nanos(&self) -> u64 {
+        if self.timed_count == 0 {
+            0
+        } else {
+            self.total.as_nanos() as u64 / self.timed_count as u64
+        }
+    }
+
+    /// Get the min time in microseconds, or 0 if no duration was recorded
+    ///
+    /// Prefer this over the `min_time_micros` field, which holds `u64::MAX`
+    /// until the first duration is recorded.
+    pub fn min_time_micros(&self) -> u64 {
+        if self.has_timing() {
+            self.min_time_micros
+        } else {
             0
+        }
+    }
+
+    /// Get the min time in nanoseconds, or 0 if no duration was recorded
+    pub fn min_time_nanos(&self) -> u64 {
+        if self.has_timing() {
+            self.min_time_nanos
         } else {
-            self.total.as_micros() as u64 / self.count as u64
+            0
         }
     }
 
+    /// Get the exponentially weighted moving average duration in nanoseconds
+    ///
+    /// Unlike the lifetime mean, this tracks recent latency; see
+    /// [`ProfileCollector::set_ewma_alpha`] for the decay rate.
+    pub fn ewma_nanos(&self) -> u64 {
+        self.ewma_nanos.round() as u64
+    }
+
+    /// Get the time at which the slowest sample was recorded
+    ///
+    /// Useful for correlating a latency spike with external events. `None`
+    /// until a duration has been recorded.
+    pub fn max_time_at(&self) -> Option<SystemTime> {
+        self.max_time_at
+    }
+
+    /// Get the exponentially weighted moving average duration in microseconds
+    pub fn ewma_micros(&self) -> u64 {
+        self.ewma_nanos() / 1000
+    }
+
     /// Get total time in microseconds
     pub fn total_time_micros(&self) -> u64 {
         self.total.as_micros() as u64
@@ -69,448 +260,4011 @@ impl OperationStats {
     /// Get standard deviation in microseconds
     pub fn std_dev_micros(&self) -> u64 {
         // Simple approximation - in a real implementation you'd calculate this properly
-        if self.count < 2 {
+        if self.timed_count < 2 {
             0
         } else {
             let mean = self.mean_time_micros();
-            ((self.max_time_micros.saturating_sub(self.min_time_micros)) / 4).max(mean / 10)
+            ((self.max_time_micros.saturating_sub(self.min_time_micros())) / 4).max(mean / 10)
         }
     }
 
-    /// Get the 50th percentile (median) in microseconds
-    pub fn p50_micros(&self) -> u64 {
-        #[cfg(feature = "full")]
-        {
-            self.histogram.value_at_quantile(0.5)
-        }
-        #[cfg(not(feature = "full"))]
-        {
-            self.mean_time_micros()
-        }
+    /// Whether the percentiles were imported with
+    /// [`ProfileCollector::record_summary`] rather than measured
+    pub fn is_imported(&self) -> bool {
+        self.imported_percentiles.is_some()
     }
 
-    /// Get the 95th percentile in microseconds
-    pub fn p95_micros(&self) -> u64 {
-        #[cfg(feature = "full")]
-        {
-            self.histogram.value_at_quantile(0.95)
-        }
-        #[cfg(not(feature = "full"))]
-        {
-            (self.mean_time_micros() + self.max_time_micros) / 2
-        }
+    /// Read the `index`th imported percentile, if these stats are a summary
+    fn imported_nanos(&self, index: usize) -> Option<u64> {
+        self.imported_percentiles
+            .map(|percentiles| percentiles[index])
     }
 
-    /// Get the 99th percentile in microseconds
-    pub fn p99_micros(&self) -> u64 {
+    /// Read a quantile from the histogram, if one is kept
+    fn quantile_nanos(&self, quantile: f64) -> Option<u64> {
         #[cfg(feature = "full")]
         {
-            self.histogram.value_at_quantile(0.99)
+            self.histogram
+                .as_ref()
+                .map(|histogram| histogram.value_at_quantile(quantile))
         }
         #[cfg(not(feature = "full"))]
         {
-            (self.mean_time_micros() * 3 + self.max_time_micros) / 4
+            let _ = quantile;
+            None
         }
     }
 
-    /// Get the 99.9th percentile in microseconds
-    pub fn p999_micros(&self) -> u64 {
+    /// Get the 50th percentile (median) in nanoseconds
+    ///
+    /// Imported summaries return their stored value. Without a histogram this
+    /// is estimated from the mean, as are the other percentiles from the mean
+    /// and max.
+    pub fn p50_nanos(&self) -> u64 {
+        self.imported_nanos(0)
+            .or_else(|| self.quantile_nanos(0.5))
+            .unwrap_or_else(|| self.mean_time_nanos())
+    }
+
+    /// Get the 95th percentile in nanoseconds
+    pub fn p95_nanos(&self) -> u64 {
+        self.imported_nanos(1)
+            .or_else(|| self.quantile_nanos(0.95))
+            .unwrap_or_else(|| (self.mean_time_nanos() + self.max_time_nanos) / 2)
+    }
+
+    /// Get the 99th percentile in nanoseconds
+    pub fn p99_nanos(&self) -> u64 {
+        self.imported_nanos(2)
+            .or_else(|| self.quantile_nanos(0.99))
+            .unwrap_or_else(|| (self.mean_time_nanos() * 3 + self.max_time_nanos) / 4)
+    }
+
+    /// Get the 99.9th percentile in nanoseconds
+    pub fn p999_nanos(&self) -> u64 {
+        self.imported_nanos(3)
+            .or_else(|| self.quantile_nanos(0.999))
+            .unwrap_or(self.max_time_nanos)
+    }
+
+    /// Whether these stats keep a histogram for exact percentiles
+    pub fn has_percentiles(&self) -> bool {
         #[cfg(feature = "full")]
         {
-            self.histogram.value_at_quantile(0.999)
+            self.histogram.is_some()
         }
         #[cfg(not(feature = "full"))]
         {
-            self.max_time_micros
+            false
         }
     }
 
-    /// Add a new measurement to these stats
-    pub fn record(&mut self, duration: Duration) {
-        let micros = duration.as_micros() as u64;
-
-        self.count += 1;
-        self.total += duration;
-
-        // Update min/max
-        self.min_time_micros = self.min_time_micros.min(micros);
-        self.max_time_micros = self.max_time_micros.max(micros);
-
-        // Record in histogram for percentile calculations
+    /// Approximate heap and inline memory used by these stats, in bytes
+    pub fn memory_footprint(&self) -> usize {
+        let footprint = std::mem::size_of::<Self>();
         #[cfg(feature = "full")]
-        {
-            let _ = self.histogram.record(micros);
+        if let Some(histogram) = &self.histogram {
+            return footprint + histogram.distinct_values() * std::mem::size_of::<u64>();
         }
+        footprint
     }
-}
 
-/// Global registry of all operation statistics
-static GLOBAL_STATS: LazyLock<Arc<RwLock<HashMap<String, OperationStats>>>> =
-    LazyLock::new(|| Arc::new(RwLock::new(HashMap::new())));
+    /// Get the 50th percentile (median) in microseconds
+    pub fn p50_micros(&self) -> u64 {
+        self.p50_nanos() / 1000
+    }
 
-/// Global registry of operation categories
-static GLOBAL_CATEGORIES: LazyLock<Arc<RwLock<HashMap<String, DefaultCategory>>>> =
-    LazyLock::new(|| Arc::new(RwLock::new(HashMap::new())));
+    /// Get the 95th percentile in microseconds
+    pub fn p95_micros(&self) -> u64 {
+        self.p95_nanos() / 1000
+    }
 
-/// Global pause state for all profiling operations
-static GLOBAL_PAUSED: LazyLock<Arc<RwLock<bool>>> = LazyLock::new(|| Arc::new(RwLock::new(false)));
+    /// Get the 99th percentile in microseconds
+    pub fn p99_micros(&self) -> u64 {
+        self.p99_nanos() / 1000
+    }
 
-/// Central collector for all profiling data
-pub struct ProfileCollector;
+    /// Get the 99.9th percentile in microseconds
+    pub fn p999_micros(&self) -> u64 {
+        self.p999_nanos() / 1000
+    }
 
-impl ProfileCollector {
-    /// Record a timing measurement for an operation
-    pub fn record(key: &str, duration_micros: u64) {
-        // Skip recording if globally paused
-        if Self::is_paused() {
-            return;
+    /// Count timed samples in `buckets` equal-width bins from min to max
+    ///
+    /// Counts come from the histogram, so they sum to the number of recorded
+    /// durations (`count` minus any [`increment`](Self::increment)s). Returns
+    /// all zeros if nothing has been timed or no histogram is kept.
+    pub fn distribution(&self, buckets: usize) -> Vec<u64> {
+        let mut bins = vec![0; buckets];
+        if buckets == 0 || !self.has_timing() {
+            return bins;
         }
 
-        let duration = Duration::from_micros(duration_micros);
-
         #[cfg(feature = "full")]
-        {
-            if let Ok(mut stats) = GLOBAL_STATS.write() {
-                stats
-                    .entry(key.to_string())
-                    .and_modify(|s| s.record(duration))
-                    .or_insert_with(|| {
-                        let mut new_stats = OperationStats::default();
-                        new_stats.record(duration);
-                        new_stats
-                    });
+        if let Some(histogram) = &self.histogram {
+            let min = self.min_time_nanos;
+            let width = (self.max_time_nanos - min) as u128 + 1;
+            for value in histogram.iter_recorded() {
+                // Histogram values are rounded within their bucket, so clamp
+                // them to the recorded range
+                let nanos = value.value_iterated_to().clamp(min, self.max_time_nanos);
+                let bin = ((nanos - min) as u128 * buckets as u128 / width) as usize;
+                bins[bin] += value.count_at_value();
             }
         }
 
-        #[cfg(not(feature = "full"))]
-        {
-            let _ = (key, duration);
-        }
+        bins
     }
 
-    /// Get statistics for a specific operation
-    pub fn get_stats(key: &str) -> Option<OperationStats> {
+    /// The `n` longest recorded durations in nanoseconds, longest first
+    ///
+    /// Read from the top of the histogram, so each value is accurate to the
+    /// histogram's precision; the longest is the exact max. Without a
+    /// histogram only the max is known.
+    fn worst_nanos(&self, n: usize) -> Vec<u64> {
+        if n == 0 || !self.has_timing() {
+            return Vec::new();
+        }
+
         #[cfg(feature = "full")]
-        {
-            if let Ok(stats) = GLOBAL_STATS.read() {
-                stats.get(key).cloned()
-            } else {
-                None
+        if let Some(histogram) = &self.histogram {
+            let recorded: Vec<_> = histogram.iter_recorded().collect();
+            let mut worst = Vec::with_capacity(n);
+            for value in recorded.iter().rev() {
+                // Histogram values are rounded within their bucket, so clamp
+                // them to the recorded range
+                let nanos = value
+                    .value_iterated_to()
+                    .clamp(self.min_time_nanos, self.max_time_nanos);
+                let remaining = n - worst.len();
+                let count = (value.count_at_value() as usize).min(remaining);
+                worst.extend(std::iter::repeat_n(nanos, count));
+                if worst.len() == n {
+                    break;
+                }
             }
+            return worst;
         }
 
-        #[cfg(not(feature = "full"))]
-        {
-            let _ = key;
-            None
-        }
+        vec![self.max_time_nanos]
     }
 
-    /// Get statistics for all operations
-    pub fn get_all_stats() -> HashMap<String, OperationStats> {
-        #[cfg(feature = "full")]
-        {
-            if let Ok(stats) = GLOBAL_STATS.read() {
-                stats.clone()
-            } else {
-                HashMap::new()
+    /// Render the latency distribution as an eight-character Unicode sparkline
+    ///
+    /// Each character is one bin of [`Self::distribution`], from fastest to
+    /// slowest, scaled to the fullest bin. Empty bins are spaces.
+    pub fn sparkline(&self) -> String {
+        const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+        let bins = self.distribution(BLOCKS.len());
+        let peak = bins.iter().copied().max().unwrap_or(0);
+        bins.iter()
+            .map(|&count| {
+                if count == 0 {
+                    ' '
+                } else {
+                    let level = count * (BLOCKS.len() as u64 - 1) / peak;
+                    BLOCKS[level as usize]
+                }
+            })
+            .collect()
+    }
+
+    /// Add a new measurement to these stats
+    ///
+    /// If [`ProfileCollector::set_idle_reset`] is on and nothing was recorded
+    /// for longer than its threshold, the earlier measurements are dropped
+    /// first. Warmup only applies to recordings made through the collector,
+    /// see [`ProfileCollector::set_warmup`].
+    pub fn record(&mut self, duration: Duration) {
+        self.reset_if_idle();
+        self.add_sample(duration);
+    }
+
+    /// Like [`Self::record`], but the first `samples` measurements since the
+    /// stats started over only add to `count` and `total` if `counted`, or
+    /// are ignored
+    fn record_after_warmup(&mut self, duration: Duration, (samples, counted): (usize, bool)) {
+        self.reset_if_idle();
+        if self.warmup_seen < samples {
+            self.warmup_seen += 1;
+            if counted {
+                self.count += 1;
+                self.timed_count += 1;
+                self.total += duration;
             }
+            return;
         }
+        self.add_sample(duration);
+    }
 
-        #[cfg(not(feature = "full"))]
-        {
-            HashMap::new()
+    /// Drop the measurements if nothing was recorded for longer than the
+    /// idle reset threshold
+    fn reset_if_idle(&mut self) {
+        if let Some(idle) = idle_reset() {
+            let now = Instant::now();
+            if self
+                .last_recorded_at
+                .is_some_and(|last| now.duration_since(last) > idle)
+            {
+                self.reset_timings();
+            }
+            self.last_recorded_at = Some(now);
         }
     }
 
-    /// Check if any profiling data has been collected
-    pub fn has_data() -> bool {
-        #[cfg(feature = "full")]
-        {
-            if let Ok(stats) = GLOBAL_STATS.read() {
-                !stats.is_empty() && stats.values().any(|s| s.count > 0)
-            } else {
-                false
-            }
+    /// Add a measurement past warmup to every statistic
+    fn add_sample(&mut self, duration: Duration) {
+        let nanos = duration.as_nanos() as u64;
+        let first_timing = !self.has_timing();
+
+        self.count += 1;
+        self.timed_count += 1;
+        self.total += duration;
+        if nanos == 0 {
+            self.zero_duration_count += 1;
         }
 
-        #[cfg(not(feature = "full"))]
-        {
-            false
+        // Update min/max
+        if first_timing || nanos > self.max_time_nanos {
+            self.max_time_at = Some(SystemTime::now());
         }
-    }
+        self.min_time_nanos = self.min_time_nanos.min(nanos);
+        self.max_time_nanos = self.max_time_nanos.max(nanos);
+        self.min_time_micros = self.min_time_nanos / 1000;
+        self.max_time_micros = self.max_time_nanos / 1000;
 
-    /// Get total number of operations recorded across all metrics
-    pub fn total_operations() -> u64 {
+        // The first measurement seeds the moving average
+        self.ewma_nanos = if first_timing {
+            nanos as f64
+        } else {
+            let alpha = ewma_alpha();
+            alpha * nanos as f64 + (1.0 - alpha) * self.ewma_nanos
+        };
+
+        // Record in histogram for percentile calculations. Only bounded
+        // histograms reject values; those are clamped to the bounds
         #[cfg(feature = "full")]
-        {
-            if let Ok(stats) = GLOBAL_STATS.read() {
-                stats.values().map(|s| s.count as u64).sum()
-            } else {
-                0
+        if let Some(histogram) = &mut self.histogram {
+            if histogram.record(nanos).is_err() {
+                histogram.saturating_record(nanos);
             }
         }
-
-        #[cfg(not(feature = "full"))]
-        {
-            0
-        }
     }
 
-    /// Reset all statistics
-    pub fn reset_all() {
+    /// Forget all measurements, keeping the histogram's precision
+    fn reset_timings(&mut self) {
+        self.count = 0;
+        self.timed_count = 0;
+        self.total = Duration::ZERO;
+        self.min_time_micros = u64::MAX;
+        self.max_time_micros = 0;
+        self.min_time_nanos = u64::MAX;
+        self.max_time_nanos = 0;
+        self.ewma_nanos = 0.0;
+        self.max_time_at = None;
+        self.imported_percentiles = None;
+        self.zero_duration_count = 0;
+        self.warmup_seen = 0;
+
         #[cfg(feature = "full")]
-        {
-            if let Ok(mut stats) = GLOBAL_STATS.write() {
-                stats.clear();
-            }
-            if let Ok(mut categories) = GLOBAL_CATEGORIES.write() {
-                categories.clear();
-            }
+        if let Some(histogram) = &mut self.histogram {
+            histogram.reset();
         }
     }
 
-    /// Clear all data
-    pub fn clear_all() {
-        Self::reset_all();
-        Self::reset_pause_state();
+    /// Count an occurrence without a duration
+    ///
+    /// Only `count` changes; timing fields, including [`Self::timed_count`],
+    /// are left untouched.
+    pub fn increment(&mut self) {
+        self.count += 1;
     }
 
-    /// Record a timing measurement with a category
-    pub fn record_with_category(key: &str, category: DefaultCategory, duration_micros: u64) {
-        Self::record(key, duration_micros);
+    /// Whether at least one duration has been recorded
+    ///
+    /// Operations that were only counted via [`ProfileCollector::increment`]
+    /// have no timing data, and reports show "—" for their timing columns.
+    pub fn has_timing(&self) -> bool {
+        self.min_time_nanos != u64::MAX
+    }
 
-        #[cfg(feature = "full")]
-        {
-            if let Ok(mut categories) = GLOBAL_CATEGORIES.write() {
-                categories.insert(key.to_string(), category);
-            }
+    /// Number of durations recorded as exactly zero
+    ///
+    /// A zero means the operation finished within one tick of the clock, so
+    /// its real duration is unknown and the mean and percentiles are skewed
+    /// low. Time a batch of calls together to measure such operations.
+    pub fn zero_duration_count(&self) -> u64 {
+        self.zero_duration_count
+    }
+
+    /// Merge another operation's measurements into these stats
+    ///
+    /// Histograms are added bucket by bucket, so percentiles of the merged
+    /// stats reflect the combined distribution rather than an average of
+    /// per-operation percentiles. If only `other` lacks a histogram, its
+    /// samples are missing from the merged percentiles.
+    ///
+    /// Imported summaries cannot be merged accurately: percentiles don't
+    /// combine without the underlying samples. Merging into empty stats
+    /// keeps `other`'s imported percentiles; otherwise they are ignored.
+    pub fn merge(&mut self, other: &OperationStats) {
+        if other.count == 0 {
+            return;
         }
 
-        #[cfg(not(feature = "full"))]
-        {
-            let _ = (key, category, duration_micros);
+        if self.count == 0 {
+            self.imported_percentiles = other.imported_percentiles;
         }
-    }
 
-    /// Get the category for a specific operation
-    pub fn get_category<S: AsRef<str>, C: Category>(_key: S) -> Option<C> {
-        #[cfg(feature = "full")]
-        {
-            // For now, always return None to avoid complexity
-            // The reporter will handle missing categories gracefully
-            // by using a flat operation list instead of categorized view
-            None
+        // Moving averages have no exact merge; weight them by call count
+        if !self.has_timing() {
+            self.ewma_nanos = other.ewma_nanos;
+        } else if other.has_timing() {
+            let total = (self.timed_count + other.timed_count) as f64;
+            self.ewma_nanos = (self.ewma_nanos * self.timed_count as f64
+                + other.ewma_nanos * other.timed_count as f64)
+                / total;
         }
 
-        #[cfg(not(feature = "full"))]
+        if other.has_timing() && (!self.has_timing() || other.max_time_nanos > self.max_time_nanos)
         {
-            None
+            self.max_time_at = other.max_time_at;
         }
-    }
 
-    /// Reset metrics for a specific operation
-    pub fn reset_operation(key: &str) {
+        self.last_recorded_at = self.last_recorded_at.max(other.last_recorded_at);
+        self.count += other.count;
+        self.timed_count += other.timed_count;
+        self.total += other.total;
+        self.zero_duration_count += other.zero_duration_count;
+        self.warmup_seen = self.warmup_seen.max(other.warmup_seen);
+        self.min_time_nanos = self.min_time_nanos.min(other.min_time_nanos);
+        self.max_time_nanos = self.max_time_nanos.max(other.max_time_nanos);
+        self.min_time_micros = self.min_time_nanos / 1000;
+        self.max_time_micros = self.max_time_nanos / 1000;
+
         #[cfg(feature = "full")]
-        {
-            if let Ok(mut stats) = GLOBAL_STATS.write() {
-                stats.remove(key);
+        if let (Some(histogram), Some(other_histogram)) = (&mut self.histogram, &other.histogram) {
+            if histogram.add(other_histogram).is_err() {
+                // Out of a bounded histogram's range; clamp like new samples
+                for value in other_histogram.iter_recorded() {
+                    histogram
+                        .saturating_record_n(value.value_iterated_to(), value.count_at_value());
+                }
             }
         }
-
-        #[cfg(not(feature = "full"))]
-        {
-            let _ = key;
-        }
     }
 
-    /// Pause all profiling operations globally
+    /// Append these stats to `out` in the binary export format
     ///
-    /// When paused, all new timing measurements will be ignored.
-    /// Existing timers will continue running but won't record their results.
-    pub fn pause() {
-        if let Ok(mut paused) = GLOBAL_PAUSED.write() {
-            *paused = true;
+    /// Layout, little-endian: count, total, min and max nanoseconds and the
+    /// EWMA bits as `u64`s, then a flags byte followed by the max timestamp,
+    /// imported percentiles, zero-duration count, timed count (when it
+    /// differs from the count) and length-prefixed V2 histogram that are
+    /// present.
+    fn write_binary(&self, out: &mut Vec<u8>) {
+        let max_time_at = self
+            .max_time_at
+            .and_then(|at| at.duration_since(SystemTime::UNIX_EPOCH).ok());
+        for value in [
+            self.count as u64,
+            self.total.as_nanos() as u64,
+            self.min_time_nanos,
+            self.max_time_nanos,
+            self.ewma_nanos.to_bits(),
+        ] {
+            out.extend_from_slice(&value.to_le_bytes());
         }
-    }
 
-    /// Resume all profiling operations globally
-    ///
-    /// After resuming, new timing measurements will be recorded normally.
-    pub fn unpause() {
-        if let Ok(mut paused) = GLOBAL_PAUSED.write() {
+        let mut histogram_bytes = Vec::new();
+        if let Some(histogram) = &self.histogram {
+            use hdrhistogram::serialization::{Serializer, V2Serializer};
+            // Only fails on writer errors, which a Vec doesn't have
+            let _ = V2Serializer::new().serialize(histogram, &mut histogram_bytes);
+        }
+        let flags = u8::from(max_time_at.is_some())
+            | u8::from(self.imported_percentiles.is_some()) << 1
+            | u8::from(self.histogram.is_some()) << 2
+            | u8::from(self.zero_duration_count > 0) << 3
+            | u8::from(self.timed_count != self.count) << 4;
+        out.push(flags);
+
+        if let Some(at) = max_time_at {
+            out.extend_from_slice(&(at.as_nanos() as u64).to_le_bytes());
+        }
+        for percentile in self.imported_percentiles.iter().flatten() {
+            out.extend_from_slice(&percentile.to_le_bytes());
+        }
+        if self.zero_duration_count > 0 {
+            out.extend_from_slice(&self.zero_duration_count.to_le_bytes());
+        }
+        if self.timed_count != self.count {
+            out.extend_from_slice(&(self.timed_count as u64).to_le_bytes());
+        }
+        if self.histogram.is_some() {
+            out.extend_from_slice(&(histogram_bytes.len() as u32).to_le_bytes());
+            out.extend_from_slice(&histogram_bytes);
+        }
+    }
+
+    /// Read stats written by [`Self::write_binary`], advancing `input`
+    fn read_binary(input: &mut &[u8]) -> std::io::Result<Self> {
+        let mut stats = Self::with_precision(DEFAULT_HISTOGRAM_PRECISION);
+        stats.count = read_u64(input)? as usize;
+        stats.total = Duration::from_nanos(read_u64(input)?);
+        stats.min_time_nanos = read_u64(input)?;
+        stats.max_time_nanos = read_u64(input)?;
+        stats.min_time_micros = stats.min_time_nanos / 1000;
+        stats.max_time_micros = stats.max_time_nanos / 1000;
+        stats.ewma_nanos = f64::from_bits(read_u64(input)?);
+
+        let flags = take_bytes(input, 1)?[0];
+        if flags & 1 != 0 {
+            stats.max_time_at =
+                Some(SystemTime::UNIX_EPOCH + Duration::from_nanos(read_u64(input)?));
+        }
+        if flags & 2 != 0 {
+            let mut percentiles = [0; 4];
+            for percentile in &mut percentiles {
+                *percentile = read_u64(input)?;
+            }
+            stats.imported_percentiles = Some(percentiles);
+        }
+        if flags & 8 != 0 {
+            stats.zero_duration_count = read_u64(input)?;
+        }
+        stats.timed_count = if flags & 16 != 0 {
+            read_u64(input)? as usize
+        } else {
+            stats.count
+        };
+        stats.histogram = None;
+        if flags & 4 != 0 {
+            use hdrhistogram::serialization::Deserializer;
+            let len = u32::from_le_bytes(take_bytes(input, 4)?.try_into().unwrap());
+            let mut bytes = take_bytes(input, len as usize)?;
+            let mut histogram: Histogram<u64> = Deserializer::new()
+                .deserialize(&mut bytes)
+                .map_err(|e| invalid_data(format!("bad histogram: {:?}", e)))?;
+            histogram.auto(true);
+            stats.histogram = Some(histogram);
+        }
+        Ok(stats)
+    }
+}
+
+/// Magic bytes and format version opening [`ProfileCollector::export_binary`] output
+const BINARY_MAGIC: &[u8; 4] = b"QPB\x01";
+
+/// An [`std::io::ErrorKind::InvalidData`] error for malformed binary exports
+fn invalid_data(message: impl Into<String>) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message.into())
+}
+
+/// Split `len` bytes off the front of `input`
+fn take_bytes<'a>(input: &mut &'a [u8], len: usize) -> std::io::Result<&'a [u8]> {
+    if input.len() < len {
+        return Err(invalid_data("truncated binary export"));
+    }
+    let (bytes, rest) = input.split_at(len);
+    *input = rest;
+    Ok(bytes)
+}
+
+/// Read a little-endian `u64` off the front of `input`
+fn read_u64(input: &mut &[u8]) -> std::io::Result<u64> {
+    Ok(u64::from_le_bytes(
+        take_bytes(input, 8)?.try_into().unwrap(),
+    ))
+}
+
+/// An operation key shared through the interner
+///
+/// Hashes like the `str` it holds, and maps keyed by it are looked up with a
+/// plain `&str`. Comparing two interned keys checks the pointer first, so
+/// equal keys never compare their bytes.
+#[derive(Debug, Clone)]
+pub(crate) struct InternedKey(Arc<str>);
+
+impl InternedKey {
+    /// Check whether both keys share the same interned allocation
+    pub(crate) fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl PartialEq for InternedKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other) || *self.0 == *other.0
+    }
+}
+
+impl Eq for InternedKey {}
+
+impl std::hash::Hash for InternedKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl std::borrow::Borrow<str> for InternedKey {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::ops::Deref for InternedKey {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for InternedKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Shared map keyed by interned operation keys
+type KeyedMap<V> = Arc<RwLock<HashMap<InternedKey, V>>>;
+
+/// Global registry of all operation statistics
+static STATS_STORE: OnceLock<Box<dyn StatsStore>> = OnceLock::new();
+
+/// Get the stats backend, installing the default on first use
+fn store() -> &'static dyn StatsStore {
+    STATS_STORE
+        .get_or_init(|| Box::new(HashMapStore::default()))
+        .as_ref()
+}
+
+/// Insert `stats` under `key`, merging into any stats already there
+///
+/// New keys beyond the key limit are merged into [`OVERFLOW_KEY`].
+fn insert_or_merge(key: &str, stats: OperationStats) {
+    let key = admit_key(key);
+    // Whichever closure runs first takes the stats
+    let pending = Cell::new(Some(stats));
+    store().record(
+        key,
+        &mut || pending.take().unwrap_or_default(),
+        &mut |existing| {
+            if let Some(stats) = pending.take() {
+                existing.merge(&stats);
+            }
+        },
+    );
+}
+
+/// Global registry of operation categories
+static GLOBAL_CATEGORIES: LazyLock<KeyedMap<DefaultCategory>> =
+    LazyLock::new(|| Arc::new(RwLock::new(HashMap::new())));
+
+/// Keys recorded as rollups of other operations via `Operation::aggregate_key`
+static AGGREGATE_KEYS: LazyLock<Arc<RwLock<HashSet<InternedKey>>>> =
+    LazyLock::new(|| Arc::new(RwLock::new(HashSet::new())));
+
+/// Snapshot of [`AGGREGATE_KEYS`], taken before walking the store so the two
+/// locks are never held together
+fn aggregate_keys() -> HashSet<InternedKey> {
+    AGGREGATE_KEYS
+        .read()
+        .map(|keys| keys.clone())
+        .unwrap_or_default()
+}
+
+/// Category metadata captured when operations are recorded, by operation key
+static CATEGORY_INFO: LazyLock<KeyedMap<CategoryInfo>> =
+    LazyLock::new(|| Arc::new(RwLock::new(HashMap::new())));
+
+/// Categories declared up front with `ProfileCollector::register_category`,
+/// in registration order
+static REGISTERED_CATEGORIES: LazyLock<RwLock<Vec<CategoryInfo>>> =
+    LazyLock::new(|| RwLock::new(Vec::new()));
+
+/// Interned operation keys shared by the stats and categories maps
+static KEY_INTERNER: LazyLock<Arc<RwLock<HashSet<InternedKey>>>> =
+    LazyLock::new(|| Arc::new(RwLock::new(HashSet::new())));
+
+/// Escape a Prometheus label value
+#[cfg(feature = "full")]
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Get the shared key for `key`, interning it on first use
+pub(crate) fn intern(key: &str) -> InternedKey {
+    if let Ok(interner) = KEY_INTERNER.read() {
+        if let Some(interned) = interner.get(key) {
+            return interned.clone();
+        }
+    }
+
+    match KEY_INTERNER.write() {
+        Ok(mut interner) => {
+            if let Some(interned) = interner.get(key) {
+                return interned.clone();
+            }
+            let interned = InternedKey(Arc::from(key));
+            interner.insert(interned.clone());
+            interned
+        }
+        Err(_) => InternedKey(Arc::from(key)),
+    }
+}
+
+/// Drop `key` from the interner once no map holds it any more
+///
+/// Call after removing a key so that renamed, aliased and reset operations
+/// don't keep their old keys alive until the next [`ProfileCollector::clear_all`].
+fn release_key(key: &str) {
+    if let Ok(mut interner) = KEY_INTERNER.write() {
+        // The interner's own reference is the only one left
+        if interner
+            .get(key)
+            .is_some_and(|interned| Arc::strong_count(&interned.0) == 1)
+        {
+            interner.remove(key);
+        }
+    }
+}
+
+/// Number of keys currently held by the interner
+#[cfg(test)]
+pub(crate) fn interned_key_count() -> usize {
+    KEY_INTERNER.read().map(|i| i.len()).unwrap_or(0)
+}
+
+/// Check whether the interner currently holds `key`
+#[cfg(test)]
+pub(crate) fn is_interned(key: &str) -> bool {
+    KEY_INTERNER.read().is_ok_and(|i| i.contains(key))
+}
+
+/// Default EWMA smoothing factor, `2 / (N + 1)` for roughly the last 100 samples
+const DEFAULT_EWMA_ALPHA: f64 = 2.0 / 101.0;
+
+/// Current EWMA smoothing factor, stored as `f64` bits
+static EWMA_ALPHA_BITS: LazyLock<AtomicU64> =
+    LazyLock::new(|| AtomicU64::new(DEFAULT_EWMA_ALPHA.to_bits()));
+
+/// Get the current EWMA smoothing factor
+fn ewma_alpha() -> f64 {
+    f64::from_bits(EWMA_ALPHA_BITS.load(Ordering::Relaxed))
+}
+
+/// Idle gap in nanoseconds after which stats start over, 0 when off
+static IDLE_RESET_NANOS: AtomicU64 = AtomicU64::new(0);
+
+/// Get the current idle reset threshold
+fn idle_reset() -> Option<Duration> {
+    match IDLE_RESET_NANOS.load(Ordering::Relaxed) {
+        0 => None,
+        nanos => Some(Duration::from_nanos(nanos)),
+    }
+}
+
+/// Recordings per operation taken as warmup, 0 when off
+static WARMUP_SAMPLES: AtomicUsize = AtomicUsize::new(0);
+
+/// Whether warmup recordings still add to count and total
+static WARMUP_COUNTED: AtomicBool = AtomicBool::new(true);
+
+/// Get the current warmup length and whether warmup recordings are counted
+fn warmup() -> (usize, bool) {
+    (
+        WARMUP_SAMPLES.load(Ordering::Relaxed),
+        WARMUP_COUNTED.load(Ordering::Relaxed),
+    )
+}
+
+/// Histogram precision overrides, by operation key
+static OPERATION_PRECISION: LazyLock<KeyedMap<u8>> =
+    LazyLock::new(|| Arc::new(RwLock::new(HashMap::new())));
+
+/// Expected mean latencies in microseconds, by operation key
+static EXPECTED_MICROS: LazyLock<KeyedMap<u64>> =
+    LazyLock::new(|| Arc::new(RwLock::new(HashMap::new())));
+
+/// Report positions of operations within their category, by operation key
+static OPERATION_ORDER: LazyLock<KeyedMap<i32>> =
+    LazyLock::new(|| Arc::new(RwLock::new(HashMap::new())));
+
+/// Lowest discernible and highest trackable histogram values in nanoseconds
+static HISTOGRAM_BOUNDS: RwLock<Option<(u64, u64)>> = RwLock::new(None);
+
+/// Get the bounds set with [`ProfileCollector::set_histogram_bounds`]
+fn histogram_bounds_nanos() -> Option<(u64, u64)> {
+    HISTOGRAM_BOUNDS.read().ok().and_then(|bounds| *bounds)
+}
+
+/// Create empty stats for `key`, honoring its precision override
+#[cfg(feature = "full")]
+fn new_stats(key: &str) -> OperationStats {
+    let sig_figs = OPERATION_PRECISION
+        .read()
+        .ok()
+        .and_then(|precision| precision.get(key).copied())
+        .unwrap_or(DEFAULT_HISTOGRAM_PRECISION);
+    OperationStats::with_precision(sig_figs)
+}
+
+/// Buffered recordings per subscriber before lagging ones start losing messages
+#[cfg(feature = "tokio")]
+const SUBSCRIBER_CAPACITY: usize = 1024;
+
+/// Broadcasts `(key, micros)` for every recording to [`ProfileCollector::subscribe`] receivers
+#[cfg(feature = "tokio")]
+static RECORDINGS: LazyLock<tokio::sync::broadcast::Sender<(String, u64)>> =
+    LazyLock::new(|| tokio::sync::broadcast::channel(SUBSCRIBER_CAPACITY).0);
+
+/// Time of the first recording since the collector was last cleared
+static STARTED_AT: LazyLock<Arc<RwLock<Option<SystemTime>>>> =
+    LazyLock::new(|| Arc::new(RwLock::new(None)));
+
+/// Start the collection window if it isn't running yet
+///
+/// Called whenever a new key is inserted, which the first recording after a
+/// clear always does.
+#[cfg(feature = "full")]
+fn mark_started() {
+    if let Ok(mut started_at) = STARTED_AT.write() {
+        started_at.get_or_insert_with(SystemTime::now);
+    }
+}
+
+/// Global pause state for all profiling operations
+static GLOBAL_PAUSED: LazyLock<Arc<RwLock<bool>>> = LazyLock::new(|| Arc::new(RwLock::new(false)));
+
+/// Whether `segment!` time is subtracted from the enclosing timer
+static EXCLUSIVE_TIME: AtomicBool = AtomicBool::new(false);
+
+/// Whether timers capture the chain of operations enclosing them
+static CALL_PATHS_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Total time by call path, outermost operation key first
+static CALL_PATHS: LazyLock<Mutex<HashMap<Vec<String>, Duration>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Whether `record` measures its own cost
+static SELF_PROFILING: AtomicBool = AtomicBool::new(false);
+
+/// Recordings measured while self-profiling
+static SELF_RECORDS: AtomicU64 = AtomicU64::new(0);
+
+/// Nanoseconds spent inside `record` while self-profiling
+static SELF_RECORD_NANOS: AtomicU64 = AtomicU64::new(0);
+
+/// Extra stores every global recording is forwarded to
+static MIRRORS: LazyLock<RwLock<Vec<Arc<dyn StatsStore>>>> =
+    LazyLock::new(|| RwLock::new(Vec::new()));
+
+/// Whether any mirrors are registered, so recording skips the lock otherwise
+static HAS_MIRRORS: AtomicBool = AtomicBool::new(false);
+
+/// Recordings dropped because profiling or their timer was paused
+static SUPPRESSED: AtomicU64 = AtomicU64::new(0);
+
+/// Distinct keys the global store holds before new ones overflow
+const DEFAULT_MAX_KEYS: usize = 10_000;
+
+/// Distinct key limit, see [`ProfileCollector::set_max_keys`]
+static MAX_KEYS: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_KEYS);
+
+/// Recordings rerouted to [`OVERFLOW_KEY`] because the key limit was reached
+static CARDINALITY_OVERFLOWED: AtomicU64 = AtomicU64::new(0);
+
+/// Reserved key that collects recordings for new keys beyond the limit
+pub const OVERFLOW_KEY: &str = "__overflow__";
+
+/// Get the key a recording for `key` is stored under
+///
+/// New keys go to [`OVERFLOW_KEY`] once the store holds the maximum number
+/// of keys. The check and the insert aren't atomic, so concurrent
+/// recordings can overshoot the limit slightly.
+#[cfg(feature = "full")]
+pub(crate) fn admit_key(key: &str) -> &str {
+    if is_admitted(key) {
+        key
+    } else {
+        CARDINALITY_OVERFLOWED.fetch_add(1, Ordering::Relaxed);
+        OVERFLOW_KEY
+    }
+}
+
+/// Whether `key` can be stored under its own name, see [`admit_key`]
+#[cfg(feature = "full")]
+pub(crate) fn is_admitted(key: &str) -> bool {
+    let max_keys = MAX_KEYS.load(Ordering::Relaxed);
+    // Known keys are the common case, and `len` can be costly for sharded
+    // stores, so it is only checked for new keys
+    max_keys == 0 || key == OVERFLOW_KEY || store().contains(key) || store().len() < max_keys
+}
+
+/// Keys generated from operations are shortened beyond this many bytes
+const DEFAULT_MAX_KEY_LENGTH: usize = 256;
+
+/// Smallest accepted limit, leaving room for the hash suffix
+const MIN_MAX_KEY_LENGTH: usize = 16;
+
+/// Current limit on the length of keys generated from operations
+static MAX_KEY_LENGTH: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_KEY_LENGTH);
+
+/// Whether new stats allocate a histogram for exact percentiles
+static PERCENTILES_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Slow events kept unless changed with [`ProfileCollector::set_slow_event_capacity`]
+const DEFAULT_SLOW_EVENT_CAPACITY: usize = 256;
+
+/// Maximum number of slow events kept
+static SLOW_EVENT_CAPACITY: AtomicUsize = AtomicUsize::new(DEFAULT_SLOW_EVENT_CAPACITY);
+
+/// Noted recordings above this many microseconds are kept, `u64::MAX` when off
+static SLOW_EVENT_THRESHOLD_MICROS: AtomicU64 = AtomicU64::new(u64::MAX);
+
+/// Most recent slow events, oldest first
+static SLOW_EVENTS: LazyLock<Mutex<VecDeque<SlowEvent>>> =
+    LazyLock::new(|| Mutex::new(VecDeque::new()));
+
+/// Names of categories whose operations are currently not recorded
+static DISABLED_CATEGORIES: LazyLock<Arc<RwLock<HashSet<String>>>> =
+    LazyLock::new(|| Arc::new(RwLock::new(HashSet::new())));
+
+/// Operation keys that are the only ones recorded, if set
+static OPERATION_WHITELIST: LazyLock<RwLock<Option<HashSet<String>>>> =
+    LazyLock::new(|| RwLock::new(None));
+
+/// Central collector for all profiling data
+pub struct ProfileCollector;
+
+impl ProfileCollector {
+    /// Replace the backend that stores operation statistics
+    ///
+    /// Must be called before anything is recorded; returns `false` and
+    /// leaves the current backend in place otherwise. See [`crate::store`].
+    pub fn set_stats_store(store: Box<dyn StatsStore>) -> bool {
+        STATS_STORE.set(store).is_ok()
+    }
+
+    /// Forward every global recording to `mirror` as well
+    ///
+    /// Lets one set of call sites feed several views, such as a store that a
+    /// dashboard drains periodically next to the lifetime stats. Only
+    /// recordings into the global collector are forwarded; clearing the
+    /// collector leaves mirrors and their contents alone.
+    pub fn add_mirror(mirror: Arc<dyn StatsStore>) {
+        if let Ok(mut mirrors) = MIRRORS.write() {
+            mirrors.push(mirror);
+            HAS_MIRRORS.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Stop forwarding recordings to the stores added with [`Self::add_mirror`]
+    pub fn clear_mirrors() {
+        if let Ok(mut mirrors) = MIRRORS.write() {
+            mirrors.clear();
+            HAS_MIRRORS.store(false, Ordering::Relaxed);
+        }
+    }
+
+    /// Start measuring an operation whose end is in another scope
+    ///
+    /// Use this where RAII timers don't fit; the returned token records when
+    /// passed to [`Self::stop`].
+    pub fn start(operation: &dyn Operation) -> TimerToken {
+        TimerToken::start(operation)
+    }
+
+    /// Record the operation started by [`Self::start`], returning its duration
+    pub fn stop(token: TimerToken) -> Duration {
+        token.stop()
+    }
+
+    /// Record a span measured elsewhere under the operation's key
+    ///
+    /// Use this when the start and end instants come from another source,
+    /// such as a callback or an event loop. The span is clamped to zero if
+    /// `end` is before `start`. Pausing and disabled categories apply as for
+    /// timers.
+    pub fn record_span(operation: &dyn Operation, start: Instant, end: Instant) {
+        let nanos = end.saturating_duration_since(start).as_nanos();
+        crate::timer::record_operation(operation, u64::try_from(nanos).unwrap_or(u64::MAX));
+    }
+
+    /// Run `f` and record how long it took under the operation's key
+    ///
+    /// A function form of [`profile!`](crate::profile) for higher-order code,
+    /// such as passing profiled work to a retry helper. Like the macro, it
+    /// records even if `f` panics.
+    #[inline]
+    pub fn profile<R>(operation: &dyn Operation, f: impl FnOnce() -> R) -> R {
+        let _timer = crate::timer::ProfileTimer::new(operation);
+        f()
+    }
+
+    /// Time two implementations against each other
+    ///
+    /// Runs `fn_a` and `fn_b` `iterations` times each, alternating between
+    /// them so drift such as CPU frequency changes affects both alike. The
+    /// timings go into stats private to the result, never the collector, so
+    /// nothing shows up in reports. Return values are passed through
+    /// [`std::hint::black_box`] so the work isn't optimized away.
+    ///
+    /// # Example
+    /// ```rust
+    /// use quantum_pulse::ProfileCollector;
+    ///
+    /// let data: Vec<u64> = (0..1000).rev().collect();
+    /// let result = ProfileCollector::benchmark_compare(
+    ///     "clone_sort",
+    ///     || {
+    ///         let mut sorted = data.clone();
+    ///         sorted.sort();
+    ///         sorted
+    ///     },
+    ///     "iter_sum",
+    ///     || data.iter().sum::<u64>(),
+    ///     100,
+    /// );
+    /// println!("{} wins, speedup {:.1}x", result.faster(), result.speedup());
+    /// ```
+    pub fn benchmark_compare<RA, RB>(
+        name_a: &str,
+        mut fn_a: impl FnMut() -> RA,
+        name_b: &str,
+        mut fn_b: impl FnMut() -> RB,
+        iterations: usize,
+    ) -> ComparisonResult {
+        let mut stats_a = new_stats(name_a);
+        let mut stats_b = new_stats(name_b);
+        for _ in 0..iterations {
+            let start = Instant::now();
+            std::hint::black_box(fn_a());
+            stats_a.record(start.elapsed());
+
+            let start = Instant::now();
+            std::hint::black_box(fn_b());
+            stats_b.record(start.elapsed());
+        }
+
+        ComparisonResult {
+            name_a: name_a.to_string(),
+            stats_a,
+            name_b: name_b.to_string(),
+            stats_b,
+        }
+    }
+
+    /// Await `fut` and record how long it took under the operation's key
+    ///
+    /// A function form of [`profile_async!`](crate::profile_async), built on
+    /// [`ProfileTimerAsync::run`](crate::ProfileTimerAsync::run).
+    pub fn profile_async<'a, F>(
+        operation: &'a dyn Operation,
+        fut: F,
+    ) -> impl std::future::Future<Output = F::Output> + 'a
+    where
+        F: std::future::Future + 'a,
+    {
+        crate::timer::ProfileTimerAsync::new(operation).run(fut)
+    }
+
+    /// Record a timing measurement for an operation
+    pub fn record(key: &str, duration_micros: u64) {
+        Self::record_nanos(key, duration_micros.saturating_mul(1000));
+    }
+
+    /// Record a timing measurement for an operation in fractional microseconds
+    ///
+    /// Durations are kept in nanoseconds, so `0.5` is recorded as 500ns
+    /// rather than floored to zero as with [`Self::record`]. Negative and
+    /// NaN values are recorded as zero.
+    pub fn record_f64(key: &str, duration_micros: f64) {
+        // `as` saturates, and maps NaN to zero
+        Self::record_nanos(key, (duration_micros * 1000.0).round() as u64);
+    }
+
+    /// Record a timing measurement for an operation in nanoseconds
+    ///
+    /// Use this for sub-microsecond operations, which would otherwise
+    /// round down to zero when recorded in microseconds.
+    pub fn record_nanos(key: &str, duration_nanos: u64) {
+        // Skip recording if globally paused
+        if Self::is_paused() {
+            Self::note_suppressed();
+            return;
+        }
+
+        let duration = Duration::from_nanos(duration_nanos);
+
+        #[cfg(feature = "full")]
+        {
+            // Measured into counters rather than recorded, so self-profiling
+            // never recurses
+            let self_start = SELF_PROFILING.load(Ordering::Relaxed).then(Instant::now);
+
+            let key = admit_key(key);
+            crate::stream::write_record(key, duration_nanos);
+
+            #[cfg(feature = "tokio")]
+            if RECORDINGS.receiver_count() > 0 {
+                let _ = RECORDINGS.send((key.to_string(), duration_nanos / 1000));
+            }
+
+            let warmup = warmup();
+            if store().record(key, &mut || new_stats(key), &mut |stats| {
+                stats.record_after_warmup(duration, warmup)
+            }) {
+                mark_started();
+            }
+
+            if HAS_MIRRORS.load(Ordering::Relaxed) {
+                if let Ok(mirrors) = MIRRORS.read() {
+                    for mirror in mirrors.iter() {
+                        mirror.record(key, &mut || new_stats(key), &mut |stats| {
+                            stats.record(duration)
+                        });
+                    }
+                }
+            }
+
+            if let Some(start) = self_start {
+                SELF_RECORDS.fetch_add(1, Ordering::Relaxed);
+                SELF_RECORD_NANOS.fetch_add(start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+            }
+        }
+
+        #[cfg(not(feature = "full"))]
+        {
+            let _ = (key, duration);
+        }
+    }
+
+    /// Record a summary computed elsewhere, such as by another process
+    ///
+    /// All durations are in microseconds. The stats under `key` are replaced
+    /// by a summary-only entry whose percentile getters return the given
+    /// values verbatim; its min is unknown and reads as zero, and its max is
+    /// taken from `p999_us`. Summaries cannot be merged accurately, so
+    /// later recordings under the same key update count and mean but leave
+    /// the imported percentiles as they are. A new key beyond the key limit,
+    /// see [`Self::set_max_keys`], is merged into [`OVERFLOW_KEY`] instead of
+    /// replacing it.
+    pub fn record_summary(
+        key: &str,
+        count: usize,
+        mean_us: u64,
+        p50_us: u64,
+        p95_us: u64,
+        p99_us: u64,
+        p999_us: u64,
+    ) {
+        if Self::is_paused() {
+            return;
+        }
+
+        #[cfg(feature = "full")]
+        {
+            let summary = OperationStats::from_summary(
+                count,
+                Duration::from_micros(mean_us),
+                [p50_us, p95_us, p99_us, p999_us].map(Duration::from_micros),
+            );
+            let key = admit_key(key);
+            let overflowed = key == OVERFLOW_KEY;
+            // Whichever closure runs first takes the summary
+            let pending = Cell::new(Some(summary));
+            if store().record(
+                key,
+                &mut || pending.take().unwrap_or_default(),
+                &mut |stats| {
+                    if let Some(summary) = pending.take() {
+                        if overflowed {
+                            stats.merge(&summary);
+                        } else {
+                            *stats = summary;
+                        }
+                    }
+                },
+            ) {
+                mark_started();
+            }
+        }
+
+        #[cfg(not(feature = "full"))]
+        {
+            let _ = (key, count, mean_us, p50_us, p95_us, p99_us, p999_us);
+        }
+    }
+
+    /// Record a timing measurement into `store` instead of the global collector
+    ///
+    /// Only statistics are updated; subscribers, the stream sink, and the
+    /// collection window only see global recordings. Precision overrides
+    /// still apply.
+    pub fn record_nanos_in(store: &dyn StatsStore, key: &str, duration_nanos: u64) {
+        if Self::is_paused() {
+            Self::note_suppressed();
+            return;
+        }
+
+        let duration = Duration::from_nanos(duration_nanos);
+        let warmup = warmup();
+        store.record(key, &mut || new_stats(key), &mut |stats| {
+            stats.record_after_warmup(duration, warmup)
+        });
+    }
+
+    /// Start or stop measuring the collector's own recording cost
+    ///
+    /// Self-profiling is off by default because timing each recording adds
+    /// to its cost. Enabling it resets the numbers returned by
+    /// [`Self::self_profile`].
+    pub fn set_self_profiling(enabled: bool) {
+        if enabled {
+            SELF_RECORDS.store(0, Ordering::Relaxed);
+            SELF_RECORD_NANOS.store(0, Ordering::Relaxed);
+        }
+        SELF_PROFILING.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Get the time spent inside `record` since self-profiling was enabled
+    pub fn self_profile() -> SelfStats {
+        SelfStats {
+            records: SELF_RECORDS.load(Ordering::Relaxed),
+            total: Duration::from_nanos(SELF_RECORD_NANOS.load(Ordering::Relaxed)),
+        }
+    }
+
+    /// Stream every subsequent recording to `sink` as a JSON line
+    ///
+    /// Each recording is appended as `{"key":...,"micros":...,"ts":...}`, where
+    /// `ts` is microseconds since [`Self::started_at`] (see
+    /// [`Self::set_stream_relative_timestamps`]), in addition to the in-memory
+    /// aggregate. Writes are buffered and flushed periodically; records that
+    /// fail to write are dropped and counted in [`Self::stream_dropped_records`].
+    /// Replaces (and flushes) any previously configured sink.
+    pub fn set_stream_sink(sink: Box<dyn std::io::Write + Send>) {
+        #[cfg(feature = "full")]
+        {
+            crate::stream::set_sink(Some(sink));
+        }
+
+        #[cfg(not(feature = "full"))]
+        {
+            let _ = sink;
+        }
+    }
+
+    /// Write stream timestamps relative to the collection start, or to the Unix epoch
+    ///
+    /// Relative by default, so a streamed timeline starts near 0 and stays
+    /// compact. Pass `false` for absolute timestamps that can be correlated
+    /// with other logs.
+    pub fn set_stream_relative_timestamps(relative: bool) {
+        #[cfg(feature = "full")]
+        {
+            crate::stream::set_relative_timestamps(relative);
+        }
+
+        #[cfg(not(feature = "full"))]
+        {
+            let _ = relative;
+        }
+    }
+
+    /// Receive every subsequent recording as `(key, micros)`
+    ///
+    /// Nothing is published while there are no subscribers. Receivers that
+    /// fall more than 1024 recordings behind lose the oldest ones and get
+    /// `RecvError::Lagged`; recording never blocks on a slow subscriber.
+    #[cfg(feature = "tokio")]
+    pub fn subscribe() -> tokio::sync::broadcast::Receiver<(String, u64)> {
+        RECORDINGS.subscribe()
+    }
+
+    /// Flush and remove the stream sink
+    pub fn clear_stream_sink() {
+        #[cfg(feature = "full")]
+        {
+            crate::stream::set_sink(None);
+        }
+    }
+
+    /// Flush buffered records to the stream sink
+    pub fn flush_stream() {
+        #[cfg(feature = "full")]
+        {
+            crate::stream::flush();
+        }
+    }
+
+    /// Number of streamed records dropped because the sink returned an error
+    pub fn stream_dropped_records() -> u64 {
+        #[cfg(feature = "full")]
+        {
+            crate::stream::dropped_records()
+        }
+
+        #[cfg(not(feature = "full"))]
+        {
+            0
+        }
+    }
+
+    /// Count an occurrence of an operation without timing it
+    ///
+    /// Bumps `count` while leaving all timing fields zero, avoiding the
+    /// `Instant::now()` calls of a timer for pure event counting.
+    pub fn increment(key: &str) {
+        // Skip counting if globally paused
+        if Self::is_paused() {
+            Self::note_suppressed();
+            return;
+        }
+
+        #[cfg(feature = "full")]
+        {
+            let key = admit_key(key);
+            if store().record(key, &mut || new_stats(key), &mut OperationStats::increment) {
+                mark_started();
+            }
+        }
+
+        #[cfg(not(feature = "full"))]
+        {
+            let _ = key;
+        }
+    }
+
+    /// Count an occurrence of an operation under its `category::operation` key
+    ///
+    /// This is what [`count!`](crate::count) expands to. Disabled categories
+    /// and operations left out of the whitelist are not counted.
+    pub fn increment_operation(operation: &dyn Operation) {
+        let category_name = operation.get_category().get_name();
+        if !Self::is_category_enabled(category_name) {
+            return;
+        }
+
+        let key = crate::timer::operation_key(operation);
+        if Self::is_operation_whitelisted(&key) {
+            Self::increment(&key);
+        }
+    }
+
+    /// Get statistics for a specific operation
+    pub fn get_stats(key: &str) -> Option<OperationStats> {
+        #[cfg(feature = "full")]
+        {
+            store().get(key)
+        }
+
+        #[cfg(not(feature = "full"))]
+        {
+            let _ = key;
+            None
+        }
+    }
+
+    /// Get the `n` longest individual durations of an operation in microseconds
+    ///
+    /// Returned longest first, so the first value is the max. Unlike the
+    /// max alone, this shows whether the worst case was a one-off or one of
+    /// several outliers. Values come from the operation's histogram and are
+    /// accurate to its precision; operations without one only report their
+    /// max. Empty if the operation has no recorded durations.
+    pub fn worst_samples(key: &str, n: usize) -> Vec<u64> {
+        Self::get_stats(key)
+            .map(|stats| {
+                stats
+                    .worst_nanos(n)
+                    .into_iter()
+                    .map(|nanos| nanos / 1000)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Get statistics for all operations
+    pub fn get_all_stats() -> HashMap<String, OperationStats> {
+        #[cfg(feature = "full")]
+        {
+            store().all()
+        }
+
+        #[cfg(not(feature = "full"))]
+        {
+            HashMap::new()
+        }
+    }
+
+    /// Get the key of every recorded operation
+    ///
+    /// Much cheaper than [`Self::get_all_stats`], which copies every
+    /// operation's histogram, when only the names are needed.
+    pub fn keys() -> Vec<String> {
+        #[cfg(feature = "full")]
+        {
+            store().keys()
+        }
+
+        #[cfg(not(feature = "full"))]
+        {
+            Vec::new()
+        }
+    }
+
+    /// Check whether an operation has been recorded, without copying its stats
+    pub fn contains(key: &str) -> bool {
+        #[cfg(feature = "full")]
+        {
+            store().contains(key)
+        }
+
+        #[cfg(not(feature = "full"))]
+        {
+            let _ = key;
+            false
+        }
+    }
+
+    /// Get one flat row per operation, sorted by key
+    ///
+    /// Rows split the `category::operation` key and precompute the
+    /// percentiles, which is the shape a frontend table wants. With the
+    /// `serde` feature, rows implement `Serialize`.
+    pub fn stats_vec() -> Vec<OperationStatsRow> {
+        let mut rows: Vec<OperationStatsRow> = Self::get_all_stats()
+            .iter()
+            .map(|(key, stats)| OperationStatsRow::new(key, stats))
+            .collect();
+        rows.sort_by(|a, b| (&a.category, &a.operation).cmp(&(&b.category, &b.operation)));
+        rows
+    }
+
+    /// Get the system-wide latency distribution across all operations
+    ///
+    /// Merges every operation's histogram into a single [`OperationStats`],
+    /// giving headline p50/p99 numbers for the whole system.
+    /// [Aggregate](Self::is_aggregate) keys are left out, since their
+    /// samples are already merged from their operations. Returns `None` if
+    /// nothing has been recorded.
+    pub fn global_histogram() -> Option<OperationStats> {
+        #[cfg(feature = "full")]
+        {
+            let aggregates = aggregate_keys();
+            let mut merged = OperationStats::default();
+            store().for_each(&mut |key, operation_stats| {
+                if !aggregates.contains(key) {
+                    merged.merge(operation_stats);
+                }
+            });
+            if merged.count == 0 {
+                None
+            } else {
+                Some(merged)
+            }
+        }
+
+        #[cfg(not(feature = "full"))]
+        {
+            None
+        }
+    }
+
+    /// Get the combined latency distribution of every operation in a category
+    ///
+    /// Operations belong to the category named by their `category::` key
+    /// prefix. Their histograms are merged, so the rollup's percentiles come
+    /// from all samples pooled together. Averaging the operations' own p99s
+    /// instead would be wrong: percentiles don't compose linearly, and the
+    /// average gives a rarely called fast operation the same weight as a busy
+    /// slow one. With 100 samples at 1ms and 100 at 100µs, the mean of the
+    /// two p99s is 550µs, a latency no request ever had, while 1% of requests
+    /// in the pooled distribution really take 1ms. [Aggregate](Self::is_aggregate)
+    /// keys are left out. Returns `None` if nothing was recorded in the
+    /// category.
+    pub fn category_summary(category_name: &str) -> Option<OperationStats> {
+        #[cfg(feature = "full")]
+        {
+            let aggregates = aggregate_keys();
+            let mut merged = OperationStats::default();
+            store().for_each(&mut |key, operation_stats| {
+                if !aggregates.contains(key)
+                    && key
+                        .split_once("::")
+                        .is_some_and(|(category, _)| category == category_name)
+                {
+                    merged.merge(operation_stats);
+                }
+            });
+            if merged.count == 0 {
+                None
+            } else {
+                Some(merged)
+            }
+        }
+
+        #[cfg(not(feature = "full"))]
+        {
+            let _ = category_name;
+            None
+        }
+    }
+
+    /// Get every category's metadata with the merged stats of its operations
+    ///
+    /// One call for a category-level dashboard: the stats are merged as in
+    /// [`Self::category_summary`], and the metadata is what timers captured,
+    /// see [`Self::category_of`]. Operations without captured metadata are
+    /// grouped by their key's `category::` prefix under a bare
+    /// [`CategoryInfo`] with that name. Keys without a prefix and
+    /// [aggregate](Self::is_aggregate) keys are left out. Sorted by priority,
+    /// then name.
+    pub fn categories_with_stats() -> Vec<(CategoryInfo, OperationStats)> {
+        let infos = CATEGORY_INFO
+            .read()
+            .map(|infos| infos.clone())
+            .unwrap_or_default();
+        let aggregates = aggregate_keys();
+
+        let mut categories: HashMap<String, (CategoryInfo, OperationStats)> = HashMap::new();
+        store().for_each(&mut |key, operation_stats| {
+            if aggregates.contains(key) {
+                return;
+            }
+            let info = match infos.get(key) {
+                Some(info) => info.clone(),
+                None => match key.split_once("::") {
+                    Some((prefix, _)) => CategoryInfo {
+                        name: prefix.to_string(),
+                        description: String::new(),
+                        color: None,
+                        icon: None,
+                        priority: 0,
+                    },
+                    None => return,
+                },
+            };
+            categories
+                .entry(info.name.clone())
+                .or_insert_with(|| (info, OperationStats::default()))
+                .1
+                .merge(operation_stats);
+        });
+
+        let mut categories: Vec<_> = categories.into_values().collect();
+        categories.sort_by(|(a, _), (b, _)| (a.priority, &a.name).cmp(&(b.priority, &b.name)));
+        categories
+    }
+
+    /// Check if any profiling data has been collected
+    pub fn has_data() -> bool {
+        #[cfg(feature = "full")]
+        {
+            let mut has_data = false;
+            store().for_each(&mut |_, s| has_data |= s.count > 0);
+            has_data
+        }
+
+        #[cfg(not(feature = "full"))]
+        {
+            false
+        }
+    }
+
+    /// Get total number of operations recorded across all metrics
+    ///
+    /// [Aggregate](Self::is_aggregate) keys are left out so their recordings
+    /// aren't counted twice.
+    pub fn total_operations() -> u64 {
+        #[cfg(feature = "full")]
+        {
+            let aggregates = aggregate_keys();
+            let mut total = 0;
+            store().for_each(&mut |key, s| {
+                if !aggregates.contains(key) {
+                    total += s.count as u64;
+                }
+            });
+            total
+        }
+
+        #[cfg(not(feature = "full"))]
+        {
+            0
+        }
+    }
+
+    /// Reset all statistics
+    pub fn reset_all() {
+        #[cfg(feature = "full")]
+        {
+            store().clear();
+            if let Ok(mut categories) = GLOBAL_CATEGORIES.write() {
+                categories.clear();
+            }
+            if let Ok(mut categories) = CATEGORY_INFO.write() {
+                categories.clear();
+            }
+            if let Ok(mut aggregates) = AGGREGATE_KEYS.write() {
+                aggregates.clear();
+            }
+            if let Ok(mut interner) = KEY_INTERNER.write() {
+                interner.clear();
+            }
+            if let Ok(mut started_at) = STARTED_AT.write() {
+                *started_at = None;
+            }
+            if let Ok(mut events) = SLOW_EVENTS.lock() {
+                events.clear();
+            }
+            if let Ok(mut paths) = CALL_PATHS.lock() {
+                paths.clear();
+            }
+            SUPPRESSED.store(0, Ordering::Relaxed);
+            CARDINALITY_OVERFLOWED.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// Time of the first recording since the collector was last cleared
+    pub fn started_at() -> Option<SystemTime> {
+        STARTED_AT.read().ok().and_then(|started_at| *started_at)
+    }
+
+    /// Time elapsed since the first recording, if anything has been recorded
+    pub fn collection_window() -> Option<Duration> {
+        Self::started_at().map(|started_at| started_at.elapsed().unwrap_or_default())
+    }
+
+    /// Clear recorded statistics but keep category associations
+    ///
+    /// For interval reporting, where the same operations are timed in every
+    /// window: the next report still groups operations registered with
+    /// [`Self::record_with_category`] without registering them again.
+    pub fn clear_stats_keep_categories() {
+        #[cfg(feature = "full")]
+        {
+            // The interner is kept too, since category keys still use it
+            store().clear();
+            if let Ok(mut started_at) = STARTED_AT.write() {
+                *started_at = None;
+            }
+        }
+    }
+
+    /// Clear all data
+    pub fn clear_all() {
+        Self::reset_all();
+        Self::reset_pause_state();
+    }
+
+    /// Return the collector to its state at startup, for test fixtures
+    ///
+    /// Beyond [`Self::clear_all`], this removes mirrors and the stream sink,
+    /// re-enables all categories and operations, forgets registered
+    /// categories, drops per-operation precision, expectations and ordering,
+    /// resumes timers paused with [`pause_stack!`](crate::pause_stack) on
+    /// this thread, and puts every setting back to its default. Calling it
+    /// twice is the same as calling it once. Only a store installed with
+    /// [`Self::set_stats_store`] stays, since it can't be replaced; it is
+    /// cleared.
+    ///
+    /// Collector state is global, so tests calling this still need to run
+    /// serially, e.g. behind `ProfileCollector::test_guard()` with the
+    /// `testing` feature.
+    pub fn reset_for_test() {
+        Self::clear_all();
+
+        #[cfg(feature = "full")]
+        {
+            Self::clear_mirrors();
+            Self::clear_stream_sink();
+            crate::stream::set_relative_timestamps(true);
+            crate::stream::reset_dropped_records();
+            crate::timer::clear_paused();
+
+            if let Ok(mut disabled) = DISABLED_CATEGORIES.write() {
+                disabled.clear();
+            }
+            if let Ok(mut registered) = REGISTERED_CATEGORIES.write() {
+                registered.clear();
+            }
+            if let Ok(mut whitelist) = OPERATION_WHITELIST.write() {
+                *whitelist = None;
+            }
+            if let Ok(mut precision) = OPERATION_PRECISION.write() {
+                precision.clear();
+            }
+            if let Ok(mut expected) = EXPECTED_MICROS.write() {
+                expected.clear();
+            }
+            if let Ok(mut order) = OPERATION_ORDER.write() {
+                order.clear();
+            }
+            if let Ok(mut bounds) = HISTOGRAM_BOUNDS.write() {
+                *bounds = None;
+            }
+
+            EWMA_ALPHA_BITS.store(DEFAULT_EWMA_ALPHA.to_bits(), Ordering::Relaxed);
+            IDLE_RESET_NANOS.store(0, Ordering::Relaxed);
+            WARMUP_SAMPLES.store(0, Ordering::Relaxed);
+            WARMUP_COUNTED.store(true, Ordering::Relaxed);
+            EXCLUSIVE_TIME.store(false, Ordering::Relaxed);
+            CALL_PATHS_ENABLED.store(false, Ordering::Relaxed);
+            SELF_PROFILING.store(false, Ordering::Relaxed);
+            SELF_RECORDS.store(0, Ordering::Relaxed);
+            SELF_RECORD_NANOS.store(0, Ordering::Relaxed);
+            MAX_KEYS.store(DEFAULT_MAX_KEYS, Ordering::Relaxed);
+            MAX_KEY_LENGTH.store(DEFAULT_MAX_KEY_LENGTH, Ordering::Relaxed);
+            PERCENTILES_ENABLED.store(true, Ordering::Relaxed);
+            SLOW_EVENT_CAPACITY.store(DEFAULT_SLOW_EVENT_CAPACITY, Ordering::Relaxed);
+            SLOW_EVENT_THRESHOLD_MICROS.store(u64::MAX, Ordering::Relaxed);
+        }
+    }
+
+    /// Record a timing measurement with a note kept if the recording is slow
+    ///
+    /// The measurement is recorded like [`Self::record`]. If it also exceeds
+    /// the threshold set with [`Self::set_slow_event_threshold`], the key,
+    /// duration, and note are kept as a [`SlowEvent`] so anomalies can be
+    /// traced back to their cause, such as the query that ran. Only the most
+    /// recent events are kept, see [`Self::set_slow_event_capacity`].
+    pub fn record_with_note(key: &str, duration_micros: u64, note: &str) {
+        Self::record(key, duration_micros);
+
+        #[cfg(feature = "full")]
+        if duration_micros > SLOW_EVENT_THRESHOLD_MICROS.load(Ordering::Relaxed)
+            && !Self::is_paused()
+        {
+            let capacity = SLOW_EVENT_CAPACITY.load(Ordering::Relaxed);
+            if let Ok(mut events) = SLOW_EVENTS.lock() {
+                while events.len() >= capacity.max(1) {
+                    events.pop_front();
+                }
+                events.push_back(SlowEvent {
+                    key: key.to_string(),
+                    micros: duration_micros,
+                    note: note.to_string(),
+                    timestamp: SystemTime::now(),
+                });
+            }
+        }
+
+        #[cfg(not(feature = "full"))]
+        {
+            let _ = note;
+        }
+    }
+
+    /// Keep noted recordings that take longer than `threshold`
+    ///
+    /// `None`, the default, keeps no slow events.
+    pub fn set_slow_event_threshold(threshold: Option<Duration>) {
+        let micros = threshold.map_or(u64::MAX, |threshold| {
+            threshold.as_micros().min(u64::MAX as u128) as u64
+        });
+        SLOW_EVENT_THRESHOLD_MICROS.store(micros, Ordering::Relaxed);
+    }
+
+    /// Get the slow event threshold, see [`Self::set_slow_event_threshold`]
+    pub fn slow_event_threshold() -> Option<Duration> {
+        match SLOW_EVENT_THRESHOLD_MICROS.load(Ordering::Relaxed) {
+            u64::MAX => None,
+            micros => Some(Duration::from_micros(micros)),
+        }
+    }
+
+    /// Limit how many slow events are kept, dropping the oldest beyond it
+    ///
+    /// Defaults to 256; values below 1 are raised to 1.
+    pub fn set_slow_event_capacity(capacity: usize) {
+        let capacity = capacity.max(1);
+        SLOW_EVENT_CAPACITY.store(capacity, Ordering::Relaxed);
+        if let Ok(mut events) = SLOW_EVENTS.lock() {
+            while events.len() > capacity {
+                events.pop_front();
+            }
+        }
+    }
+
+    /// Get the slow events kept by [`Self::record_with_note`], oldest first
+    pub fn slow_events() -> Vec<SlowEvent> {
+        SLOW_EVENTS
+            .lock()
+            .map(|events| events.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Record a timing measurement with a category
+    pub fn record_with_category(key: &str, category: DefaultCategory, duration_micros: u64) {
+        Self::record(key, duration_micros);
+
+        #[cfg(feature = "full")]
+        {
+            Self::register_operation_category(key, &category);
+            if let Ok(mut categories) = GLOBAL_CATEGORIES.write() {
+                categories.insert(intern(key), category);
+            }
+        }
+
+        #[cfg(not(feature = "full"))]
+        {
+            let _ = (key, category, duration_micros);
+        }
+    }
+
+    /// Get the metadata of the category an operation was recorded under
+    ///
+    /// Captured by timers from `Operation::get_category()` and by
+    /// [`Self::record_with_category`]. Returns `None` for keys only recorded
+    /// directly with [`Self::record`].
+    pub fn category_of(key: &str) -> Option<CategoryInfo> {
+        CATEGORY_INFO
+            .read()
+            .ok()
+            .and_then(|categories| categories.get(key).cloned())
+    }
+
+    /// Declare a category before any of its operations record
+    ///
+    /// Reports with [`ReportConfig::include_empty_categories`](crate::ReportConfig::include_empty_categories)
+    /// then show the category even in runs where none of its operations ran,
+    /// so report layouts stay the same across runs. Grouped reports also use
+    /// its description, color, icon and priority for operations whose keys
+    /// start with its name but have no category of their own. Registering a
+    /// name again replaces its metadata. Registrations survive
+    /// [`Self::clear_all`].
+    pub fn register_category(info: CategoryInfo) {
+        if let Ok(mut registered) = REGISTERED_CATEGORIES.write() {
+            match registered
+                .iter_mut()
+                .find(|existing| existing.name == info.name)
+            {
+                Some(existing) => *existing = info,
+                None => registered.push(info),
+            }
+        }
+    }
+
+    /// Get the categories declared with [`Self::register_category`], in
+    /// registration order
+    pub fn registered_categories() -> Vec<CategoryInfo> {
+        REGISTERED_CATEGORIES
+            .read()
+            .map(|registered| registered.clone())
+            .unwrap_or_default()
+    }
+
+    /// Check whether `key` holds a rollup from `Operation::aggregate_key`
+    ///
+    /// Aggregate keys repeat recordings already counted under their
+    /// operations' own keys, so [`SummaryStats`] leaves them out.
+    pub fn is_aggregate(key: &str) -> bool {
+        AGGREGATE_KEYS
+            .read()
+            .is_ok_and(|aggregates| aggregates.contains(key))
+    }
+
+    /// Remember that `key` is a rollup, see [`Self::is_aggregate`]
+    pub(crate) fn mark_aggregate(key: &str) {
+        // Timers call this on every recording, so avoid the write lock when
+        // the key is already known
+        if Self::is_aggregate(key) {
+            return;
+        }
+        if let Ok(mut aggregates) = AGGREGATE_KEYS.write() {
+            aggregates.insert(intern(key));
+        }
+    }
+
+    /// Remember the category of `key` for [`Self::category_of`]
+    ///
+    /// The category is part of the key, so the first registration is kept.
+    pub(crate) fn register_operation_category(key: &str, category: &dyn Category) {
+        // Timers call this on every recording, so avoid the write lock when
+        // the key is already known
+        if CATEGORY_INFO
+            .read()
+            .is_ok_and(|categories| categories.contains_key(key))
+        {
+            return;
+        }
+        if let Ok(mut categories) = CATEGORY_INFO.write() {
+            categories
+                .entry(intern(key))
+                .or_insert_with(|| CategoryInfo::from_category(category));
+        }
+    }
+
+    /// Get the category for a specific operation
+    pub fn get_category<S: AsRef<str>, C: Category>(_key: S) -> Option<C> {
+        #[cfg(feature = "full")]
+        {
+            // For now, always return None to avoid complexity
+            // The reporter will handle missing categories gracefully
+            // by using a flat operation list instead of categorized view
+            None
+        }
+
+        #[cfg(not(feature = "full"))]
+        {
+            None
+        }
+    }
+
+    /// Reset metrics for a specific operation
+    pub fn reset_operation(key: &str) {
+        #[cfg(feature = "full")]
+        {
+            store().remove(key);
+            release_key(key);
+        }
+
+        #[cfg(not(feature = "full"))]
+        {
+            let _ = key;
+        }
+    }
+
+    /// Merge one operation's statistics into another and remove the first
+    ///
+    /// Use this to normalize keys that turned out to be the same logical
+    /// operation. Histograms are merged, so percentiles of `to_key` reflect
+    /// both sets of measurements. `to_key` is created if it doesn't exist.
+    /// Returns `false` if `from_key` has no statistics.
+    pub fn alias(from_key: &str, to_key: &str) -> bool {
+        #[cfg(feature = "full")]
+        {
+            if from_key == to_key {
+                return false;
+            }
+
+            if !store().rename(from_key, to_key, true) {
+                return false;
+            }
+
+            Self::move_category(from_key, to_key);
+            true
+        }
+
+        #[cfg(not(feature = "full"))]
+        {
+            let _ = (from_key, to_key);
+            false
+        }
+    }
+
+    /// Move an operation's statistics to a new key
+    ///
+    /// Unlike [`Self::alias`], this never merges: it returns `false` and leaves
+    /// everything unchanged if `old_key` has no statistics or `new_key` already
+    /// has some.
+    pub fn rename(old_key: &str, new_key: &str) -> bool {
+        #[cfg(feature = "full")]
+        {
+            if !store().rename(old_key, new_key, false) {
+                return false;
+            }
+
+            Self::move_category(old_key, new_key);
+            true
+        }
+
+        #[cfg(not(feature = "full"))]
+        {
+            let _ = (old_key, new_key);
+            false
+        }
+    }
+
+    /// Carry a key's category over to its new key, unless it already has one
+    #[cfg(feature = "full")]
+    fn move_category(from_key: &str, to_key: &str) {
+        if let Ok(mut categories) = GLOBAL_CATEGORIES.write() {
+            if let Some(category) = categories.remove(from_key) {
+                if !categories.contains_key(to_key) {
+                    categories.insert(intern(to_key), category);
+                }
+            }
+        }
+        if let Ok(mut infos) = CATEGORY_INFO.write() {
+            if let Some(info) = infos.remove(from_key) {
+                infos.entry(intern(to_key)).or_insert(info);
+            }
+        }
+        release_key(from_key);
+    }
+
+    /// Pause all profiling operations globally
+    ///
+    /// When paused, all new timing measurements will be ignored.
+    /// Existing timers will continue running but won't record their results.
+    pub fn pause() {
+        if let Ok(mut paused) = GLOBAL_PAUSED.write() {
+            *paused = true;
+        }
+    }
+
+    /// Resume all profiling operations globally
+    ///
+    /// After resuming, new timing measurements will be recorded normally.
+    pub fn unpause() {
+        if let Ok(mut paused) = GLOBAL_PAUSED.write() {
             *paused = false;
         }
     }
 
-    /// Check if profiling is currently paused
-    pub fn is_paused() -> bool {
-        if let Ok(paused) = GLOBAL_PAUSED.read() {
-            *paused
-        } else {
-            false
-        }
+    /// Check if profiling is currently paused
+    pub fn is_paused() -> bool {
+        if let Ok(paused) = GLOBAL_PAUSED.read() {
+            *paused
+        } else {
+            false
+        }
+    }
+
+    /// Get the number of recordings dropped because of pausing
+    ///
+    /// Counts measurements and [`Self::increment`] calls discarded while
+    /// profiling was paused with [`pause!`](crate::pause), and timers that
+    /// were on the stack when [`pause_stack!`](crate::pause_stack) was
+    /// called. Operations in disabled categories or left out of the
+    /// whitelist are not counted, since they would not have been recorded
+    /// anyway. Reset by [`Self::clear_all`].
+    pub fn suppressed_count() -> u64 {
+        SUPPRESSED.load(Ordering::Relaxed)
+    }
+
+    /// Get how often recordings had to wait for the stats lock
+    ///
+    /// Counts writes to the default [`HashMapStore`] that found its lock held
+    /// by another thread. A count that grows with the number of recordings
+    /// means threads are queueing on the collector; consider the sharded
+    /// `DashMapStore` (with the `dashmap` feature). Other backends report 0.
+    /// Reset by [`Self::clear_all`].
+    pub fn lock_contention_count() -> u64 {
+        store().lock_contention_count()
+    }
+
+    /// Count a recording dropped because of pausing
+    pub(crate) fn note_suppressed() {
+        #[cfg(feature = "full")]
+        SUPPRESSED.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Set the smoothing factor for per-operation moving averages
+    ///
+    /// Each new duration contributes `alpha` of its value to
+    /// [`OperationStats::ewma_micros`]. Larger values react faster to latency
+    /// changes; the default of `2 / 101` tracks roughly the last 100 samples.
+    /// Values are clamped to `(0, 1]`.
+    pub fn set_ewma_alpha(alpha: f64) {
+        let alpha = if alpha.is_nan() {
+            DEFAULT_EWMA_ALPHA
+        } else {
+            alpha.clamp(f64::MIN_POSITIVE, 1.0)
+        };
+        EWMA_ALPHA_BITS.store(alpha.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Start an operation's stats over after it has been idle for `idle`
+    ///
+    /// With bursty traffic, percentiles otherwise mix the current burst with
+    /// samples from hours ago. When set, a recording that follows a gap longer
+    /// than `idle` first clears the operation's count, totals and
+    /// distribution. Off by default; `None` or a zero duration turns it off.
+    /// Operations are only tracked from their first recording after this is
+    /// turned on.
+    pub fn set_idle_reset(idle: Option<Duration>) {
+        let nanos = idle.map_or(0, |idle| idle.as_nanos().min(u64::MAX as u128) as u64);
+        IDLE_RESET_NANOS.store(nanos, Ordering::Relaxed);
+    }
+
+    /// Get the idle gap after which stats start over, see [`Self::set_idle_reset`]
+    pub fn idle_reset() -> Option<Duration> {
+        idle_reset()
+    }
+
+    /// Treat the first `samples` recordings of each operation as warmup
+    ///
+    /// Caches, connection pools and lazily initialized state make the first
+    /// calls of an operation unrepresentative. Warmup recordings are left out
+    /// of min, max, percentiles, the moving average and the zero-duration
+    /// count, so those describe the steady state. With `counted`, they still
+    /// add to `count` and `total`, so totals stay true and the mean includes
+    /// them; otherwise they are dropped entirely. Off by default (`0`).
+    ///
+    /// Each operation's warmup starts with its first recording once this is
+    /// set, and starts again after the operation is cleared or idle-reset.
+    /// Counting with [`Self::increment`] doesn't use up warmup. Only
+    /// recordings into the collector's store or a store passed to
+    /// [`Self::record_nanos_in`] warm up; mirrors added with
+    /// [`Self::add_mirror`], [`Self::benchmark_compare`] and
+    /// [`OperationStats::record`] called directly see every sample.
+    pub fn set_warmup(samples: usize, counted: bool) {
+        WARMUP_COUNTED.store(counted, Ordering::Relaxed);
+        WARMUP_SAMPLES.store(samples, Ordering::Relaxed);
+    }
+
+    /// Get the warmup length and whether warmup recordings are counted, see
+    /// [`Self::set_warmup`]
+    pub fn warmup() -> (usize, bool) {
+        warmup()
+    }
+
+    /// Subtract the time of [`segment!`](crate::segment)s from the timer they are nested in
+    ///
+    /// Off by default, so an enclosing [`profile!`](crate::profile) records
+    /// its full wall time. When enabled, it records only its own time, and
+    /// each segment's time is attributed solely to the segment's operation.
+    /// Plain nested `profile!` blocks are never subtracted.
+    pub fn set_exclusive_time(enabled: bool) {
+        EXCLUSIVE_TIME.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Check whether segment time is subtracted from enclosing timers
+    pub fn is_exclusive_time() -> bool {
+        EXCLUSIVE_TIME.load(Ordering::Relaxed)
+    }
+
+    /// Capture the chain of enclosing operations each timer records under
+    ///
+    /// Off by default, since every timer then keeps its key on the stack and
+    /// each recording updates a shared map. Only [`ProfileTimer`](crate::ProfileTimer)s,
+    /// as created by [`profile!`](crate::profile) and [`segment!`](crate::segment),
+    /// are captured. See [`Self::hot_path`].
+    pub fn set_call_paths_enabled(enabled: bool) {
+        CALL_PATHS_ENABLED.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Check whether timers capture their call paths
+    pub fn call_paths_enabled() -> bool {
+        CALL_PATHS_ENABLED.load(Ordering::Relaxed)
+    }
+
+    /// Add a timer's full time to the call path ending in its operation
+    pub(crate) fn add_call_path(path: Vec<String>, duration: Duration) {
+        if let Ok(mut paths) = CALL_PATHS.lock() {
+            *paths.entry(path).or_default() += duration;
+        }
+    }
+
+    /// Get the most expensive chain of nested operations
+    ///
+    /// Starting from the outermost operation with the most total time, each
+    /// step descends into the child that took the most time within it, so
+    /// the result shows where that time went, one level at a time. Each
+    /// entry is an operation key and the total time spent in it along this
+    /// path, including its children. Empty unless
+    /// [`Self::set_call_paths_enabled`] was on while timers recorded.
+    pub fn hot_path() -> Vec<(String, Duration)> {
+        let Ok(paths) = CALL_PATHS.lock() else {
+            return Vec::new();
+        };
+
+        let mut hot: Vec<(String, Duration)> = Vec::new();
+        loop {
+            let depth = hot.len();
+            let child = paths
+                .iter()
+                .filter(|(path, _)| {
+                    path.len() == depth + 1
+                        && path
+                            .iter()
+                            .zip(&hot)
+                            .all(|(key, (hot_key, _))| key == hot_key)
+                })
+                // Ties go to the first key by name, for stable output
+                .max_by(|(a_path, a), (b_path, b)| a.cmp(b).then_with(|| b_path.cmp(a_path)));
+            match child {
+                Some((path, total)) => hot.push((path[depth].clone(), *total)),
+                None => return hot,
+            }
+        }
+    }
+
+    /// Limit the number of distinct keys the collector holds
+    ///
+    /// Keys built from unbounded values, such as user IDs or URLs, can grow
+    /// the collector without limit. Once it holds `max_keys` keys, recordings
+    /// and counts for new keys go to the reserved [`OVERFLOW_KEY`] operation
+    /// instead, and [`Self::cardinality_overflowed`] counts them. Keys
+    /// already present keep recording as usual. Defaults to 10,000; `0`
+    /// removes the limit.
+    pub fn set_max_keys(max_keys: usize) {
+        MAX_KEYS.store(max_keys, Ordering::Relaxed);
+    }
+
+    /// Get the distinct key limit, see [`Self::set_max_keys`]
+    pub fn max_keys() -> usize {
+        MAX_KEYS.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of recordings rerouted to [`OVERFLOW_KEY`]
+    ///
+    /// Reset by [`Self::clear_all`].
+    pub fn cardinality_overflowed() -> u64 {
+        CARDINALITY_OVERFLOWED.load(Ordering::Relaxed)
+    }
+
+    /// Limit the length of keys that timers and `count!` derive from operations
+    ///
+    /// The default `to_str` formats data-carrying variants with `{:?}`, which
+    /// can produce huge keys. Longer keys are cut and given a short hash
+    /// suffix, so distinct operations keep distinct keys. Defaults to 256
+    /// bytes; values below 16 are raised to 16. Keys passed to
+    /// [`Self::record`] directly are not affected.
+    pub fn set_max_key_length(max_bytes: usize) {
+        MAX_KEY_LENGTH.store(max_bytes.max(MIN_MAX_KEY_LENGTH), Ordering::Relaxed);
+    }
+
+    /// Get the current key length limit, see [`Self::set_max_key_length`]
+    pub fn max_key_length() -> usize {
+        MAX_KEY_LENGTH.load(Ordering::Relaxed)
+    }
+
+    /// Keep exact percentiles, or only count/total/min/max/mean
+    ///
+    /// On by default. When disabled, operations first seen afterwards skip
+    /// their HDR histogram (~2KB each at the default precision, more with
+    /// [`Self::set_operation_precision`]), and percentile getters fall back
+    /// to the same estimates as the stub. Operations already recorded keep
+    /// their histograms until cleared, so call this before profiling starts.
+    pub fn set_percentiles_enabled(enabled: bool) {
+        PERCENTILES_ENABLED.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Check whether new operations keep exact percentiles
+    pub fn percentiles_enabled() -> bool {
+        PERCENTILES_ENABLED.load(Ordering::Relaxed)
+    }
+
+    /// Fix the range of durations new histograms track
+    ///
+    /// Histograms auto-resize by default. With bounds, each one is allocated
+    /// once for `low..=high`: `low` is the smallest duration told apart from
+    /// zero, and durations above `high` are counted as `high` in the
+    /// percentiles, while count, total, min and max stay exact. Returns
+    /// `false` and leaves the bounds unchanged unless `low` is at least 1ns
+    /// and `high` at least twice `low`. Like [`Self::set_percentiles_enabled`],
+    /// this applies to operations first seen afterwards and survives
+    /// [`Self::clear_all`]; `None` restores auto-resizing.
+    pub fn set_histogram_bounds(bounds: Option<(Duration, Duration)>) -> bool {
+        let nanos = match bounds {
+            Some((low, high)) => {
+                let (low, high) = (low.as_nanos() as u64, high.as_nanos() as u64);
+                if low == 0 || high < low.saturating_mul(2) {
+                    return false;
+                }
+                Some((low, high))
+            }
+            None => None,
+        };
+        if let Ok(mut current) = HISTOGRAM_BOUNDS.write() {
+            *current = nanos;
+        }
+        true
+    }
+
+    /// Get the bounds set with [`Self::set_histogram_bounds`]
+    pub fn histogram_bounds() -> Option<(Duration, Duration)> {
+        histogram_bounds_nanos()
+            .map(|(low, high)| (Duration::from_nanos(low), Duration::from_nanos(high)))
+    }
+
+    /// Check recorded keys against the set of known operation keys
+    ///
+    /// Reports keys listed more than once in `known` - operations whose keys
+    /// collide are silently merged into a single entry - and recorded keys
+    /// missing from `known`, which usually means a `to_str()` drifted.
+    /// [Aggregate](Self::is_aggregate) keys and [`OVERFLOW_KEY`] aren't
+    /// operations of their own, so they are never reported as unknown;
+    /// [`Self::cardinality_overflowed`] reports overflow. Each problem is
+    /// also printed to stderr. Enums deriving `ProfileOp` list their keys in
+    /// an `OPERATIONS` constant.
+    pub fn validate_known(known: &[&str]) -> KeyValidation {
+        let mut seen = HashSet::new();
+        let mut duplicates: Vec<String> = known
+            .iter()
+            .filter(|key| !seen.insert(**key))
+            .map(|key| key.to_string())
+            .collect();
+        duplicates.sort();
+        duplicates.dedup();
+
+        let aggregates = aggregate_keys();
+        let mut unknown = Vec::new();
+        store().for_each(&mut |key, _| {
+            if !seen.contains(key) && !aggregates.contains(key) && key != OVERFLOW_KEY {
+                unknown.push(key.to_string());
+            }
+        });
+        unknown.sort();
+
+        for key in &duplicates {
+            eprintln!("quantum-pulse: operation key `{key}` is used by more than one operation");
+        }
+        for key in &unknown {
+            eprintln!("quantum-pulse: recorded key `{key}` is not a known operation");
+        }
+
+        KeyValidation {
+            duplicates,
+            unknown,
+        }
+    }
+
+    /// Set the histogram precision for one operation
+    ///
+    /// Histograms keep 3 significant figures by default. Raising this for a
+    /// few latency-critical keys resolves closely spaced durations without
+    /// growing every histogram; each extra figure costs roughly 10x memory.
+    /// `sig_figs` is clamped to `1..=5`, and the setting survives
+    /// [`Self::clear_all`]. Samples already recorded for `key` are kept at
+    /// their original resolution; the new precision applies from now on.
+    pub fn set_operation_precision(key: &str, sig_figs: u8) {
+        #[cfg(feature = "full")]
+        {
+            let sig_figs = sig_figs.clamp(1, 5);
+            let interned = intern(key);
+            if let Ok(mut precision) = OPERATION_PRECISION.write() {
+                precision.insert(interned, sig_figs);
+            }
+
+            store().update(key, &mut |existing| {
+                let mut rebuilt = OperationStats::with_precision(sig_figs);
+                rebuilt.merge(existing);
+                *existing = rebuilt;
+            });
+        }
+
+        #[cfg(not(feature = "full"))]
+        {
+            let _ = (key, sig_figs);
+        }
+    }
+
+    /// Declare the known-good mean latency of an operation
+    ///
+    /// Reports built with [`ReportBuilder::include_baseline`](crate::ReportBuilder::include_baseline)
+    /// flag operations whose mean exceeds it. Timers call this automatically
+    /// for operations overriding [`Operation::expected_micros`]. The setting
+    /// survives [`Self::clear_all`].
+    pub fn set_expected_micros(key: &str, micros: u64) {
+        // Timers call this on every recording, so avoid the write lock when
+        // nothing changes
+        if Self::expected_micros(key) == Some(micros) {
+            return;
+        }
+        if let Ok(mut expected) = EXPECTED_MICROS.write() {
+            expected.insert(intern(key), micros);
+        }
+    }
+
+    /// Get the declared mean latency of an operation, see [`Self::set_expected_micros`]
+    pub fn expected_micros(key: &str) -> Option<u64> {
+        EXPECTED_MICROS
+            .read()
+            .ok()
+            .and_then(|expected| expected.get(key).copied())
+    }
+
+    /// Get every declared latency baseline
+    pub(crate) fn expected_baselines() -> HashMap<String, u64> {
+        EXPECTED_MICROS
+            .read()
+            .map(|expected| {
+                expected
+                    .iter()
+                    .map(|(key, micros)| (key.to_string(), *micros))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Set an operation's position within its category in reports
+    ///
+    /// Reports built with [`ReportBuilder::respect_operation_order`](crate::ReportBuilder::respect_operation_order)
+    /// list lower values first. Timers call this automatically for operations
+    /// overriding [`Operation::sort_hint`]. The setting survives
+    /// [`Self::clear_all`].
+    pub fn set_operation_order(key: &str, order: i32) {
+        // Timers call this on every recording, so avoid the write lock when
+        // nothing changes
+        if Self::operation_order(key) == Some(order) {
+            return;
+        }
+        if let Ok(mut orders) = OPERATION_ORDER.write() {
+            orders.insert(intern(key), order);
+        }
+    }
+
+    /// Get an operation's report position, see [`Self::set_operation_order`]
+    pub fn operation_order(key: &str) -> Option<i32> {
+        OPERATION_ORDER
+            .read()
+            .ok()
+            .and_then(|orders| orders.get(key).copied())
+    }
+
+    /// Get every declared operation order
+    pub(crate) fn operation_orders() -> HashMap<String, i32> {
+        OPERATION_ORDER
+            .read()
+            .map(|orders| {
+                orders
+                    .iter()
+                    .map(|(key, order)| (key.to_string(), *order))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Check whether recordings are currently being collected
+    ///
+    /// True when the `full` feature is compiled in and profiling isn't paused.
+    /// Use this to skip building reports or other work whose result would be
+    /// empty anyway.
+    pub fn is_active() -> bool {
+        crate::is_full_enabled() && !Self::is_paused()
+    }
+
+    /// Reset the pause state to unpaused
+    ///
+    /// This is useful for tests to ensure clean state
+    pub fn reset_pause_state() {
+        #[cfg(feature = "full")]
+        {
+            if let Ok(mut paused) = GLOBAL_PAUSED.write() {
+                *paused = false;
+            }
+        }
+    }
+
+    /// Reserve the global collector for the current test (requires the `testing` feature)
+    ///
+    /// Collector state is global and tests run in parallel, so tests that
+    /// clear or inspect it can clobber each other. The guard waits for other
+    /// guarded tests to finish, then clears all data and the pause state.
+    /// Tests that don't take a guard are not serialized.
+    ///
+    /// ```rust,ignore
+    /// #[test]
+    /// fn test_checkout() {
+    ///     let _g = ProfileCollector::test_guard();
+    ///     // ...
+    /// }
+    /// ```
+    #[cfg(feature = "testing")]
+    pub fn test_guard() -> TestGuard {
+        // A test that panicked while holding the lock poisons it; the state
+        // it left behind is cleared below anyway
+        let lock = TEST_LOCK
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        Self::clear_all();
+        TestGuard { _lock: lock }
+    }
+
+    /// Enable or disable recording for all operations in a category
+    ///
+    /// Timers check their operation's category name before recording, so a
+    /// disabled category is skipped without touching call sites. All categories
+    /// are enabled by default.
+    pub fn set_category_enabled(category_name: &str, enabled: bool) {
+        if let Ok(mut disabled) = DISABLED_CATEGORIES.write() {
+            if enabled {
+                disabled.remove(category_name);
+            } else {
+                disabled.insert(category_name.to_string());
+            }
+        }
+    }
+
+    /// Check whether operations in a category are currently recorded
+    pub fn is_category_enabled(category_name: &str) -> bool {
+        if let Ok(disabled) = DISABLED_CATEGORIES.read() {
+            !disabled.contains(category_name)
+        } else {
+            true
+        }
+    }
+
+    /// Record only the operations whose keys are in `whitelist`
+    ///
+    /// Useful for focusing on a few operations in a heavily instrumented
+    /// codebase. Timers check their operation's `category::operation` key
+    /// before recording, and everything else is skipped as if its category
+    /// were disabled. `None`, the default, records every operation.
+    pub fn set_operation_whitelist(whitelist: Option<HashSet<String>>) {
+        if let Ok(mut current) = OPERATION_WHITELIST.write() {
+            *current = whitelist;
+        }
+    }
+
+    /// Get the operation keys set with [`Self::set_operation_whitelist`]
+    pub fn operation_whitelist() -> Option<HashSet<String>> {
+        OPERATION_WHITELIST
+            .read()
+            .ok()
+            .and_then(|current| current.clone())
+    }
+
+    /// Check whether the operation recorded under `key` passes the whitelist
+    pub fn is_operation_whitelisted(key: &str) -> bool {
+        match OPERATION_WHITELIST.read() {
+            Ok(current) => current
+                .as_ref()
+                .is_none_or(|whitelist| whitelist.contains(key)),
+            Err(_) => true,
+        }
+    }
+
+    /// Export every operation's statistics in a compact binary format
+    ///
+    /// Far smaller and faster to produce than JSON for large key spaces.
+    /// Histograms are included in the HDR V2 format, so percentiles survive
+    /// the trip; category metadata is not. Read the result back with
+    /// [`Self::import_binary`], typically in another process.
+    pub fn export_binary() -> Vec<u8> {
+        let mut all_stats: Vec<(String, OperationStats)> =
+            Self::get_all_stats().into_iter().collect();
+        all_stats.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut out = BINARY_MAGIC.to_vec();
+        out.extend_from_slice(&(all_stats.len() as u32).to_le_bytes());
+        for (key, stats) in &all_stats {
+            out.extend_from_slice(&(key.len() as u32).to_le_bytes());
+            out.extend_from_slice(key.as_bytes());
+            stats.write_binary(&mut out);
+        }
+        out
+    }
+
+    /// Import statistics written by [`Self::export_binary`]
+    ///
+    /// Operations already recorded here are merged with the imported ones,
+    /// as with [`Self::alias`]. New operations beyond the key limit, see
+    /// [`Self::set_max_keys`], are merged into [`OVERFLOW_KEY`]. The whole
+    /// input is validated before anything is imported, so malformed data
+    /// leaves the collector unchanged. Returns the number of operations
+    /// imported.
+    pub fn import_binary(bytes: &[u8]) -> std::io::Result<usize> {
+        let mut input = bytes;
+        if take_bytes(&mut input, BINARY_MAGIC.len())? != BINARY_MAGIC {
+            return Err(invalid_data("not a quantum-pulse binary export"));
+        }
+        let len = u32::from_le_bytes(take_bytes(&mut input, 4)?.try_into().unwrap());
+
+        let mut imported = Vec::new();
+        for _ in 0..len {
+            let key_len = u32::from_le_bytes(take_bytes(&mut input, 4)?.try_into().unwrap());
+            let key = std::str::from_utf8(take_bytes(&mut input, key_len as usize)?)
+                .map_err(|_| invalid_data("operation key is not UTF-8"))?;
+            imported.push((key, OperationStats::read_binary(&mut input)?));
+        }
+        if !input.is_empty() {
+            return Err(invalid_data("trailing bytes after binary export"));
+        }
+
+        let count = imported.len();
+        for (key, stats) in imported {
+            insert_or_merge(key, stats);
+        }
+        Ok(count)
+    }
+
+    /// Export operation durations as Prometheus histograms
+    ///
+    /// `buckets` are upper bounds in microseconds; they are emitted as `le`
+    /// labels in seconds, following Prometheus conventions, along with the
+    /// implicit `+Inf` bucket and the `_sum` and `_count` series. Bucket counts
+    /// are read from the HDR histogram, so a sample within the histogram's
+    /// resolution of a boundary may be counted in that bucket. Operations
+    /// without a histogram, such as imported summaries, get only the `+Inf`
+    /// bucket, `_sum` and `_count`. Operations that were only counted, without
+    /// durations, are omitted.
+    pub fn export_prometheus_histogram(buckets: &[u64]) -> String {
+        #[cfg(feature = "full")]
+        {
+            use std::fmt::Write;
+
+            const METRIC: &str = "quantum_pulse_operation_duration_seconds";
+
+            let mut buckets = buckets.to_vec();
+            buckets.sort_unstable();
+            buckets.dedup();
+
+            let all_stats = Self::get_all_stats();
+            let mut keys: Vec<&String> = all_stats
+                .iter()
+                .filter(|(_, stats)| stats.has_timing())
+                .map(|(key, _)| key)
+                .collect();
+            keys.sort();
+
+            let mut output = String::new();
+            let _ = writeln!(output, "# HELP {METRIC} Duration of profiled operations");
+            let _ = writeln!(output, "# TYPE {METRIC} histogram");
+
+            for key in keys {
+                let stats = &all_stats[key];
+                let label = escape_label_value(key);
+                let total = match &stats.histogram {
+                    Some(histogram) => {
+                        let total = histogram.len();
+                        for &bound in &buckets {
+                            let count = histogram
+                                .count_between(0, bound.saturating_mul(1000))
+                                .min(total);
+                            let le = bound as f64 / 1_000_000.0;
+                            let _ = writeln!(
+                                output,
+                                "{METRIC}_bucket{{operation=\"{label}\",le=\"{le}\"}} {count}"
+                            );
+                        }
+                        total
+                    }
+                    // Without samples the finite buckets are unknown
+                    None => stats.timed_count() as u64,
+                };
+                let _ = writeln!(
+                    output,
+                    "{METRIC}_bucket{{operation=\"{label}\",le=\"+Inf\"}} {total}"
+                );
+                let _ = writeln!(
+                    output,
+                    "{METRIC}_sum{{operation=\"{label}\"}} {}",
+                    stats.total.as_secs_f64()
+                );
+                let _ = writeln!(output, "{METRIC}_count{{operation=\"{label}\"}} {total}");
+            }
+
+            output
+        }
+
+        #[cfg(not(feature = "full"))]
+        {
+            let _ = buckets;
+            String::new()
+        }
+    }
+
+    /// Get summary statistics across all operations
+    pub fn get_summary() -> SummaryStats {
+        let summary = SummaryStats::from_stats(&Self::get_all_stats());
+        match Self::collection_window() {
+            Some(window) => summary.with_window(window),
+            None => summary,
+        }
+    }
+
+    /// Create a guard that prints a full report when it is dropped
+    ///
+    /// Bind the guard at the top of `main` (`let _guard = ...`) so the report is
+    /// printed automatically at the end of the program. If the collector holds no
+    /// data at that point, a short "no data" message is printed instead.
+    pub fn print_on_drop(config: ReportConfig) -> ReportGuard {
+        ReportGuard::new(config)
+    }
+
+    /// Print a simple report of all collected stats
+    pub fn report_stats() {
+        println!("==== Profile Report ====");
+        let stats = Self::get_all_stats();
+        for (key, stat) in stats {
+            println!(
+                "{} -> count: {}, total: {:?}, avg: {:?}",
+                key,
+                stat.count,
+                stat.total,
+                stat.mean()
+            );
+        }
+    }
+}
+
+/// Summary statistics across all profiled operations
+#[derive(Debug, Clone, Default)]
+pub struct SummaryStats {
+    /// Total number of operations recorded
+    pub total_operations: u64,
+    /// Number of unique operation types
+    pub unique_operations: usize,
+    /// Total time spent across all operations (microseconds)
+    pub total_time_micros: u64,
+    /// Operation with the slowest single execution
+    pub slowest_operation: Option<String>,
+    /// Stats of [`Self::slowest_operation`] when the summary was taken
+    pub slowest_stats: Option<OperationStats>,
+    /// Slowest p99 time in microseconds
+    pub slowest_p99_micros: u64,
+    /// Operation with the most calls
+    pub busiest_operation: Option<String>,
+    /// Stats of [`Self::busiest_operation`] when the summary was taken
+    pub busiest_stats: Option<OperationStats>,
+    /// Number of calls for the busiest operation
+    pub busiest_count: usize,
+    /// Time between the first recording and when the summary was taken
+    pub collection_window: Option<Duration>,
+    /// Recorded operations per second over the collection window
+    ///
+    /// 0.0 when there is no window or it is too short for a meaningful rate.
+    pub ops_per_second: f64,
+}
+
+/// Windows shorter than this report a throughput of zero rather than a
+/// meaninglessly large rate
+const MIN_THROUGHPUT_WINDOW: Duration = Duration::from_millis(1);
+
+impl SummaryStats {
+    /// Summarize a set of per-operation statistics
+    ///
+    /// Aggregate keys are skipped so their recordings aren't counted twice,
+    /// see [`ProfileCollector::is_aggregate`].
+    pub fn from_stats(all_stats: &HashMap<String, OperationStats>) -> Self {
+        let aggregates = AGGREGATE_KEYS.read().ok();
+        let is_aggregate = |key: &str| {
+            aggregates
+                .as_ref()
+                .is_some_and(|aggregates| aggregates.contains(key))
+        };
+        let filtered: HashMap<String, OperationStats>;
+        let all_stats = if all_stats.keys().any(|key| is_aggregate(key)) {
+            filtered = all_stats
+                .iter()
+                .filter(|(key, _)| !is_aggregate(key))
+                .map(|(key, stats)| (key.clone(), stats.clone()))
+                .collect();
+            &filtered
+        } else {
+            all_stats
+        };
+
+        let total_operations: usize = all_stats.values().map(|s| s.count).sum();
+        let total_time: Duration = all_stats.values().map(|s| s.total).sum();
+        let unique_operations = all_stats.len();
+
+        // Find slowest operation (by max time), ties going to the first name
+        // so the summary doesn't depend on map iteration order
+        let slowest = all_stats
+            .iter()
+            .max_by_key(|(name, stats)| (stats.max_time_micros, std::cmp::Reverse(*name)));
+
+        let slowest_p99_micros = all_stats
+            .values()
+            .map(|s| s.p99_micros())
+            .max()
+            .unwrap_or(0);
+
+        // Find busiest operation (by call count)
+        let busiest = all_stats
+            .iter()
+            .max_by_key(|(name, stats)| (stats.count, std::cmp::Reverse(*name)));
+
+        SummaryStats {
+            total_operations: total_operations as u64,
+            unique_operations,
+            total_time_micros: total_time.as_micros() as u64,
+            slowest_operation: slowest.map(|(name, _)| name.clone()),
+            slowest_stats: slowest.map(|(_, stats)| stats.clone()),
+            slowest_p99_micros,
+            busiest_operation: busiest.map(|(name, _)| name.clone()),
+            busiest_stats: busiest.map(|(_, stats)| stats.clone()),
+            busiest_count: busiest.map_or(0, |(_, stats)| stats.count),
+            collection_window: None,
+            ops_per_second: 0.0,
+        }
+    }
+
+    /// Attach the collection window and derive throughput from it
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.collection_window = Some(window);
+        self.ops_per_second = if window < MIN_THROUGHPUT_WINDOW {
+            0.0
+        } else {
+            self.total_operations as f64 / window.as_secs_f64()
+        };
+        self
+    }
+
+    /// Format the summary as a single logfmt line for structured logs
+    ///
+    /// Durations are always in microseconds, independent of any report
+    /// [`TimeFormat`](crate::TimeFormat), e.g.
+    /// `ops=15 unique=2 total_us=4500 p99_max_us=200 ops_per_sec=3.0 busiest=order_validation`.
+    /// `busiest` and `slowest` are omitted when nothing has been recorded.
+    pub fn as_logfmt(&self) -> String {
+        let mut line = format!(
+            "ops={} unique={} total_us={} p99_max_us={} ops_per_sec={:.1}",
+            self.total_operations,
+            self.unique_operations,
+            self.total_time_micros,
+            self.slowest_p99_micros,
+            self.ops_per_second
+        );
+        if let Some(busiest) = &self.busiest_operation {
+            line.push_str(&format!(" busiest={}", logfmt_value(busiest)));
+        }
+        if let Some(slowest) = &self.slowest_operation {
+            line.push_str(&format!(" slowest={}", logfmt_value(slowest)));
+        }
+        line
+    }
+}
+
+/// Quote a logfmt value if it contains spaces, quotes or `=`
+fn logfmt_value(value: &str) -> String {
+    if value.is_empty() || value.contains([' ', '"', '=']) {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// One operation's statistics as a flat row, from [`ProfileCollector::stats_vec`]
+///
+/// All durations are in microseconds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OperationStatsRow {
+    /// Category part of the key, empty if the key has none
+    pub category: String,
+    /// Operation part of the key
+    pub operation: String,
+    /// Number of times the operation was recorded
+    pub count: u64,
+    /// Mean duration
+    pub mean_us: u64,
+    /// Median duration
+    pub p50_us: u64,
+    /// 95th percentile duration
+    pub p95_us: u64,
+    /// 99th percentile duration
+    pub p99_us: u64,
+    /// 99.9th percentile duration
+    pub p999_us: u64,
+    /// Fastest duration
+    pub min_us: u64,
+    /// Slowest duration
+    pub max_us: u64,
+}
+
+impl OperationStatsRow {
+    /// Flatten the stats recorded under `key`
+    pub fn new(key: &str, stats: &OperationStats) -> Self {
+        let (category, operation) = key.split_once("::").unwrap_or(("", key));
+        Self {
+            category: category.to_string(),
+            operation: operation.to_string(),
+            count: stats.count as u64,
+            mean_us: stats.mean_time_micros(),
+            p50_us: stats.p50_micros(),
+            p95_us: stats.p95_micros(),
+            p99_us: stats.p99_micros(),
+            p999_us: stats.p999_micros(),
+            min_us: stats.min_time_micros(),
+            max_us: stats.max_time_micros,
+        }
+    }
+
+    /// Rebuild summary-only stats from this row
+    ///
+    /// Percentiles are kept verbatim as with
+    /// [`ProfileCollector::record_summary`]; no histogram is restored.
+    pub fn to_stats(&self) -> OperationStats {
+        let mut stats = OperationStats::from_summary(
+            self.count as usize,
+            Duration::from_micros(self.mean_us),
+            [self.p50_us, self.p95_us, self.p99_us, self.p999_us].map(Duration::from_micros),
+        );
+        stats.min_time_micros = self.min_us;
+        stats.min_time_nanos = self.min_us.saturating_mul(1000);
+        stats.max_time_micros = self.max_us;
+        stats.max_time_nanos = self.max_us.saturating_mul(1000);
+        stats
+    }
+}
+
+/// Serializes tests holding a [`TestGuard`]
+#[cfg(feature = "testing")]
+static TEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Exclusive access to the global collector for one test, from
+/// [`ProfileCollector::test_guard`]
+///
+/// Resets the pause state again when dropped, so a test that panics while
+/// paused doesn't leave later tests paused.
+#[cfg(feature = "testing")]
+#[must_use = "the collector is only reserved while the guard is alive"]
+pub struct TestGuard {
+    _lock: std::sync::MutexGuard<'static, ()>,
+}
+
+#[cfg(feature = "testing")]
+impl Drop for TestGuard {
+    fn drop(&mut self) {
+        ProfileCollector::reset_pause_state();
+    }
+}
+
+/// The collector's own overhead, from [`ProfileCollector::self_profile`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SelfStats {
+    /// Number of recordings measured
+    pub records: u64,
+    /// Total time spent inside `record` for those recordings
+    pub total: Duration,
+}
+
+impl SelfStats {
+    /// Get the average cost of one recording
+    pub fn mean(&self) -> Duration {
+        if self.records == 0 {
+            Duration::ZERO
+        } else {
+            Duration::from_nanos((self.total.as_nanos() / self.records as u128) as u64)
+        }
+    }
+}
+
+/// Timings of two implementations, from [`ProfileCollector::benchmark_compare`]
+#[derive(Debug, Clone)]
+pub struct ComparisonResult {
+    /// Name of the first implementation
+    pub name_a: String,
+    /// Timings of the first implementation
+    pub stats_a: OperationStats,
+    /// Name of the second implementation
+    pub name_b: String,
+    /// Timings of the second implementation
+    pub stats_b: OperationStats,
+}
+
+impl ComparisonResult {
+    /// Get how many times faster `b` is than `a`, by mean time
+    ///
+    /// Above 1 when `b` is faster, below 1 when `a` is. Nothing measured
+    /// reads as 1.
+    pub fn speedup(&self) -> f64 {
+        let (a, b) = (
+            self.stats_a.mean_time_nanos(),
+            self.stats_b.mean_time_nanos(),
+        );
+        match (a, b) {
+            (0, 0) => 1.0,
+            (_, 0) => f64::INFINITY,
+            _ => a as f64 / b as f64,
+        }
+    }
+
+    /// Get the name of the implementation with the lower mean time
+    ///
+    /// Ties go to `a`.
+    pub fn faster(&self) -> &str {
+        if self.speedup() > 1.0 {
+            &self.name_b
+        } else {
+            &self.name_a
+        }
+    }
+}
+
+/// A noted recording that exceeded the slow event threshold, from
+/// [`ProfileCollector::slow_events`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlowEvent {
+    /// Key the measurement was recorded under
+    pub key: String,
+    /// Measured duration in microseconds
+    pub micros: u64,
+    /// Context passed to [`ProfileCollector::record_with_note`]
+    pub note: String,
+    /// When the measurement was recorded
+    pub timestamp: SystemTime,
+}
+
+/// Result of [`ProfileCollector::validate_known`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyValidation {
+    /// Keys listed more than once in the known set, sorted
+    pub duplicates: Vec<String>,
+    /// Recorded keys that aren't in the known set, sorted
+    pub unknown: Vec<String>,
+}
+
+impl KeyValidation {
+    /// True when no duplicate or unknown keys were found
+    pub fn is_ok(&self) -> bool {
+        self.duplicates.is_empty() && self.unknown.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "full")]
+    use std::time::Duration;
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_record_and_retrieve() {
+        ProfileCollector::clear_all();
+
+        ProfileCollector::record("test_record_and_retrieve", 1000);
+        ProfileCollector::record("test_record_and_retrieve", 2000);
+        ProfileCollector::record("test_record_and_retrieve", 1500);
+
+        let stats = ProfileCollector::get_stats("test_record_and_retrieve");
+        assert!(stats.is_some());
+
+        let stats = stats.unwrap();
+        assert_eq!(stats.count, 3);
+        assert_eq!(stats.total, Duration::from_micros(4500));
+        assert_eq!(stats.mean(), Duration::from_micros(1500));
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_multiple_operations() {
+        ProfileCollector::clear_all();
+
+        ProfileCollector::record("multiple_op1", 100);
+        ProfileCollector::record("multiple_op2", 200);
+        ProfileCollector::record("multiple_op1", 150);
+
+        let stats1 = ProfileCollector::get_stats("multiple_op1").unwrap();
+        let stats2 = ProfileCollector::get_stats("multiple_op2").unwrap();
+
+        assert_eq!(stats1.count, 2);
+        assert_eq!(stats2.count, 1);
+
+        let all_stats = ProfileCollector::get_all_stats();
+        assert_eq!(all_stats.len(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_summary_stats() {
+        ProfileCollector::clear_all();
+
+        for i in 0..10 {
+            ProfileCollector::record("summary_op1", 100 + i * 10);
+        }
+
+        for i in 0..5 {
+            ProfileCollector::record("summary_op2", 200 + i * 20);
+        }
+
+        let summary = ProfileCollector::get_summary();
+        assert_eq!(summary.total_operations, 15);
+        assert_eq!(summary.unique_operations, 2);
+        assert!(summary.total_time_micros > 0);
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_summary_includes_slowest_and_busiest_stats() {
+        let mut busy = OperationStats::default();
+        for _ in 0..8 {
+            busy.record(Duration::from_micros(50));
+        }
+        let mut slow = OperationStats::default();
+        slow.record(Duration::from_millis(20));
+        let all_stats = HashMap::from([
+            ("summary_typed_busy".to_string(), busy),
+            ("summary_typed_slow".to_string(), slow),
+        ]);
+
+        let summary = SummaryStats::from_stats(&all_stats);
+        let busiest = summary.busiest_stats.as_ref().unwrap();
+        assert_eq!(
+            summary.busiest_operation.as_deref(),
+            Some("summary_typed_busy")
+        );
+        assert_eq!(busiest.count, 8);
+        assert_eq!(busiest.count, summary.busiest_count);
+        assert_eq!(busiest.total, all_stats["summary_typed_busy"].total);
+
+        let slowest = summary.slowest_stats.as_ref().unwrap();
+        assert_eq!(
+            summary.slowest_operation.as_deref(),
+            Some("summary_typed_slow")
+        );
+        assert_eq!(slowest.max_time_micros, 20_000);
+
+        let empty = SummaryStats::from_stats(&HashMap::new());
+        assert!(empty.busiest_stats.is_none());
+        assert!(empty.slowest_stats.is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_has_data() {
+        ProfileCollector::clear_all();
+        assert!(!ProfileCollector::has_data()); // Clear data first
+
+        ProfileCollector::record("test", 100);
+        assert!(ProfileCollector::has_data());
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_record_nanos() {
+        ProfileCollector::clear_all();
+
+        ProfileCollector::record_nanos("test_record_nanos", 500);
+
+        let stats = ProfileCollector::get_stats("test_record_nanos").unwrap();
+        assert_eq!(stats.count, 1);
+        assert_eq!(stats.total, Duration::from_nanos(500));
+        assert_eq!(stats.min_time_nanos, 500);
+        assert_eq!(stats.max_time_nanos, 500);
+        assert!(stats.p50_nanos() > 0);
+        assert_eq!(stats.mean_time_nanos(), 500);
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_record_f64() {
+        ProfileCollector::record_f64("test_record_f64", 0.5);
+        ProfileCollector::record_f64("test_record_f64", 1.2504);
+
+        let stats = ProfileCollector::get_stats("test_record_f64").unwrap();
+        assert_eq!(stats.count, 2);
+        assert_eq!(stats.total, Duration::from_nanos(1750));
+        assert_eq!(stats.min_time_nanos, 500);
+        assert_eq!(stats.mean_time_micros(), 0);
+        assert!((stats.mean_micros_f64() - 0.875).abs() < 1e-9);
+
+        ProfileCollector::record_f64("test_record_f64_invalid", -3.0);
+        ProfileCollector::record_f64("test_record_f64_invalid", f64::NAN);
+        let stats = ProfileCollector::get_stats("test_record_f64_invalid").unwrap();
+        assert_eq!(stats.count, 2);
+        assert_eq!(stats.total, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_empty_stats_min_is_zero() {
+        let stats = OperationStats::default();
+        assert_eq!(stats.min_time_micros(), 0);
+        assert_eq!(stats.min_time_nanos(), 0);
+        assert_eq!(stats.std_dev_micros(), 0);
+        assert_eq!(stats.min(), Duration::ZERO);
+        assert_eq!(stats.max(), Duration::ZERO);
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_min_max_durations() {
+        let mut stats = OperationStats::default();
+        stats.record(Duration::from_nanos(1_500));
+        stats.record(Duration::from_micros(40));
+
+        assert_eq!(stats.min(), Duration::from_nanos(1_500));
+        assert_eq!(stats.max(), Duration::from_micros(40));
+        assert_eq!(stats.min().as_micros() as u64, stats.min_time_micros());
+        assert_eq!(stats.max().as_micros() as u64, stats.max_time_micros);
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_ewma_converges_after_step_change() {
+        let mut stats = OperationStats::default();
+
+        for _ in 0..500 {
+            stats.record(Duration::from_micros(100));
+        }
+        assert_eq!(stats.ewma_micros(), 100);
+
+        // Latency steps up; the EWMA follows while the lifetime mean lags
+        for _ in 0..500 {
+            stats.record(Duration::from_micros(1000));
+        }
+        assert!(stats.ewma_micros() > 990);
+        assert_eq!(stats.mean_time_micros(), 550);
+    }
+
+    #[test]
+    #[cfg(all(feature = "full", feature = "testing"))]
+    fn test_test_guard() {
+        {
+            let _g = ProfileCollector::test_guard();
+            ProfileCollector::record("test_guard_op", 100);
+            ProfileCollector::pause();
+        }
+        assert!(!ProfileCollector::is_paused());
+
+        let _g = ProfileCollector::test_guard();
+        assert!(ProfileCollector::get_stats("test_guard_op").is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_category_summary_merges_histograms() {
+        for _ in 0..100 {
+            ProfileCollector::record("RollupCategory::slow", 1000);
+            ProfileCollector::record("RollupCategory::fast", 100);
+        }
+        ProfileCollector::record("OtherRollupCategory::slow", 50_000);
+
+        let slow = ProfileCollector::get_stats("RollupCategory::slow").unwrap();
+        let fast = ProfileCollector::get_stats("RollupCategory::fast").unwrap();
+        let mean_of_p99s = (slow.p99_micros() + fast.p99_micros()) / 2;
+
+        let rollup = ProfileCollector::category_summary("RollupCategory").unwrap();
+        assert_eq!(rollup.count, 200);
+        assert_eq!(rollup.max_time_micros, 1000);
+        // 1% of the pooled samples really take 1ms; no sample is near 550µs
+        assert!(rollup.p99_micros() >= 999);
+        assert!((540..=560).contains(&mean_of_p99s));
+        assert_ne!(rollup.p99_micros(), mean_of_p99s);
+
+        assert!(ProfileCollector::category_summary("MissingRollupCategory").is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_categories_with_stats() {
+        #[derive(Debug)]
+        struct DashboardCategory(&'static str, i32);
+        impl Category for DashboardCategory {
+            fn get_name(&self) -> &str {
+                self.0
+            }
+            fn get_description(&self) -> &str {
+                "Dashboard test category"
+            }
+            fn color_hint(&self) -> Option<&str> {
+                Some("#336699")
+            }
+            fn priority(&self) -> i32 {
+                self.1
+            }
+        }
+
+        #[derive(Debug)]
+        struct DashboardOp(&'static str, DashboardCategory);
+        impl Operation for DashboardOp {
+            fn get_category(&self) -> &dyn Category {
+                &self.1
+            }
+            fn to_str(&self) -> String {
+                self.0.to_string()
+            }
+        }
+
+        let start = Instant::now();
+        let spans = [
+            (
+                DashboardOp("read", DashboardCategory("DashboardDb", -20)),
+                300,
+            ),
+            (
+                DashboardOp("write", DashboardCategory("DashboardDb", -20)),
+                500,
+            ),
+            (
+                DashboardOp("hit", DashboardCategory("DashboardCache", -30)),
+                40,
+            ),
+        ];
+        for (op, micros) in &spans {
+            let end = start + Duration::from_micros(*micros);
+            ProfileCollector::record_span(op, start, end);
+        }
+        ProfileCollector::record("DashboardRaw::op", 70);
+
+        let dashboard: Vec<_> = ProfileCollector::categories_with_stats()
+            .into_iter()
+            .filter(|(info, _)| info.name.starts_with("Dashboard"))
+            .collect();
+        let names: Vec<&str> = dashboard
+            .iter()
+            .map(|(info, _)| info.name.as_str())
+            .collect();
+        assert_eq!(names, ["DashboardCache", "DashboardDb", "DashboardRaw"]);
+
+        let (cache, cache_stats) = &dashboard[0];
+        assert_eq!(cache.color.as_deref(), Some("#336699"));
+        assert_eq!(cache_stats.count, 1);
+
+        let (db, db_stats) = &dashboard[1];
+        assert_eq!(db.priority, -20);
+        assert_eq!(db_stats.count, 2);
+        assert_eq!(db_stats.total, Duration::from_micros(800));
+        assert_eq!(db_stats.max_time_micros, 500);
+
+        let (raw, raw_stats) = &dashboard[2];
+        assert_eq!(raw.priority, 0);
+        assert!(raw.color.is_none());
+        assert_eq!(raw_stats.total, Duration::from_micros(70));
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_add_mirror() {
+        let mirror = Arc::new(HashMapStore::default());
+        ProfileCollector::add_mirror(mirror.clone());
+        ProfileCollector::record("mirrored_op", 250);
+        ProfileCollector::record("mirrored_op", 750);
+        ProfileCollector::clear_mirrors();
+        ProfileCollector::record("mirrored_op", 1000);
+
+        assert_eq!(ProfileCollector::get_stats("mirrored_op").unwrap().count, 3);
+        let mirrored = mirror.get("mirrored_op").unwrap();
+        assert_eq!(mirrored.count, 2);
+        assert_eq!(mirrored.mean_time_micros(), 500);
+    }
+
+    #[derive(Debug)]
+    enum ClosureOp {
+        Sync,
+        Async,
+    }
+
+    impl Operation for ClosureOp {
+        fn to_str(&self) -> String {
+            format!("profile_fn_{:?}", self)
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_profile_closure() {
+        let value = ProfileCollector::profile(&ClosureOp::Sync, || {
+            std::thread::sleep(Duration::from_millis(1));
+            42
+        });
+        assert_eq!(value, 42);
+
+        let stats = ProfileCollector::get_stats("::profile_fn_Sync").unwrap();
+        assert_eq!(stats.count, 1);
+        assert!(stats.total >= Duration::from_millis(1));
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "full")]
+    async fn test_profile_async_future() {
+        let value = ProfileCollector::profile_async(&ClosureOp::Async, async {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+            "done"
+        })
+        .await;
+        assert_eq!(value, "done");
+
+        let stats = ProfileCollector::get_stats("::profile_fn_Async").unwrap();
+        assert_eq!(stats.count, 1);
+        assert!(stats.total >= Duration::from_millis(1));
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_benchmark_compare() {
+        let result = ProfileCollector::benchmark_compare(
+            "bench_slow",
+            || std::thread::sleep(Duration::from_millis(2)),
+            "bench_fast",
+            || (0..100u64).sum::<u64>(),
+            5,
+        );
+
+        assert_eq!(result.stats_a.count, 5);
+        assert_eq!(result.stats_b.count, 5);
+        assert!(result.stats_a.mean() > result.stats_b.mean());
+        assert!(result.stats_a.p99_nanos() >= 2_000_000);
+        assert!(result.speedup() > 1.0);
+        assert_eq!(result.faster(), "bench_fast");
+
+        // Nothing reaches the collector
+        assert!(ProfileCollector::get_stats("bench_slow").is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_worst_samples() {
+        for micros in [100, 120, 90, 110, 25_000, 100, 8_000, 95, 40_000, 8_000] {
+            ProfileCollector::record("worst_samples_op", micros);
+        }
+
+        let worst = ProfileCollector::worst_samples("worst_samples_op", 4);
+        assert_eq!(worst.len(), 4);
+        assert_eq!(worst[0], 40_000);
+        for (actual, expected) in worst.iter().zip([40_000, 25_000, 8_000, 8_000]) {
+            assert!(actual.abs_diff(expected) <= expected / 1000, "{worst:?}");
+        }
+        assert!(worst.windows(2).all(|pair| pair[0] >= pair[1]));
+
+        assert_eq!(
+            ProfileCollector::worst_samples("worst_samples_op", 20).len(),
+            10
+        );
+        assert!(ProfileCollector::worst_samples("worst_samples_op", 0).is_empty());
+        assert!(ProfileCollector::worst_samples("worst_samples_missing", 3).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_record_span() {
+        #[derive(Debug)]
+        struct SpanOp;
+        impl Operation for SpanOp {
+            fn to_str(&self) -> String {
+                "record_span_test".to_string()
+            }
+        }
+
+        let start = Instant::now();
+        let end = start + Duration::from_millis(5);
+        ProfileCollector::record_span(&SpanOp, start, end);
+        ProfileCollector::record_span(&SpanOp, end, start);
+
+        let key = crate::timer::operation_key(&SpanOp);
+        let stats = ProfileCollector::get_stats(&key).unwrap();
+        assert_eq!(stats.count, 2);
+        assert_eq!(stats.total, Duration::from_millis(5));
+        assert_eq!(stats.min_time_nanos, 0);
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_keys_and_contains() {
+        ProfileCollector::record("keys_test::alpha", 10);
+        ProfileCollector::record("keys_test::beta", 20);
+        ProfileCollector::increment("keys_test::gamma");
+
+        // Other tests record concurrently, so only look at this test's keys
+        let mut keys: Vec<String> = ProfileCollector::keys()
+            .into_iter()
+            .filter(|key| key.starts_with("keys_test::"))
+            .collect();
+        keys.sort();
+        assert_eq!(
+            keys,
+            ["keys_test::alpha", "keys_test::beta", "keys_test::gamma"]
+        );
+
+        assert!(ProfileCollector::contains("keys_test::alpha"));
+        assert!(!ProfileCollector::contains("keys_test::missing"));
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_idle_reset() {
+        ProfileCollector::set_idle_reset(Some(Duration::from_secs(60)));
+        assert_eq!(
+            ProfileCollector::idle_reset(),
+            Some(Duration::from_secs(60))
+        );
+
+        let mut stats = OperationStats::default();
+        stats.record(Duration::from_micros(100));
+        stats.record(Duration::from_micros(300));
+        assert_eq!(stats.count, 2);
+
+        // Pretend the last recording was two minutes ago
+        stats.last_recorded_at = Instant::now().checked_sub(Duration::from_secs(120));
+        stats.record(Duration::from_micros(50));
+        assert_eq!(stats.count, 1);
+        assert_eq!(stats.max_time_micros, 50);
+        assert_eq!(stats.mean_time_micros(), 50);
+        assert_eq!(stats.p99_micros(), 50);
+
+        // Within the threshold, measurements accumulate as usual
+        stats.record(Duration::from_micros(150));
+        assert_eq!(stats.count, 2);
+
+        ProfileCollector::set_idle_reset(None);
+        assert!(ProfileCollector::idle_reset().is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_set_ewma_alpha() {
+        ProfileCollector::set_ewma_alpha(1.0);
+
+        let mut stats = OperationStats::default();
+        stats.record(Duration::from_micros(100));
+        stats.record(Duration::from_micros(700));
+        assert_eq!(stats.ewma_micros(), 700);
+
+        ProfileCollector::set_ewma_alpha(DEFAULT_EWMA_ALPHA);
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_is_active() {
+        ProfileCollector::clear_all();
+        assert!(ProfileCollector::is_active());
+
+        ProfileCollector::pause();
+        assert!(!ProfileCollector::is_active());
+
+        ProfileCollector::unpause();
+        assert!(ProfileCollector::is_active());
     }
 
-    /// Reset the pause state to unpaused
-    ///
-    /// This is useful for tests to ensure clean state
-    pub fn reset_pause_state() {
-        #[cfg(feature = "full")]
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_alias_and_rename() {
+        ProfileCollector::clear_all();
+
+        ProfileCollector::record("load_config", 100);
+        ProfileCollector::record("load_config", 300);
+        ProfileCollector::record("config_load", 200);
+
+        assert!(ProfileCollector::alias("load_config", "config_load"));
+        assert!(ProfileCollector::get_stats("load_config").is_none());
+
+        let merged = ProfileCollector::get_stats("config_load").unwrap();
+        assert_eq!(merged.count, 3);
+        assert_eq!(merged.total, Duration::from_micros(600));
+        assert_eq!(merged.min_time_micros, 100);
+        assert_eq!(merged.max_time_micros, 300);
+
+        // Aliasing a missing key is a no-op
+        assert!(!ProfileCollector::alias("load_config", "config_load"));
+
+        assert!(ProfileCollector::rename("config_load", "config::load"));
+        assert!(ProfileCollector::get_stats("config_load").is_none());
+        assert_eq!(
+            ProfileCollector::get_stats("config::load").unwrap().count,
+            3
+        );
+
+        // Renaming onto an existing key is refused
+        ProfileCollector::record("other", 50);
+        assert!(!ProfileCollector::rename("other", "config::load"));
+        assert_eq!(ProfileCollector::get_stats("other").unwrap().count, 1);
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_increment() {
+        ProfileCollector::clear_all();
+
+        ProfileCollector::increment("test_increment");
+        ProfileCollector::increment("test_increment");
+
+        let stats = ProfileCollector::get_stats("test_increment").unwrap();
+        assert_eq!(stats.count, 2);
+        assert_eq!(stats.total, Duration::ZERO);
+        assert!(!stats.has_timing());
+        assert_eq!(stats.mean_time_nanos(), 0);
+        assert_eq!(stats.p99_nanos(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_increment_keeps_mean_of_timed_samples() {
+        ProfileCollector::record("counted_and_timed", 100);
+        ProfileCollector::record("counted_and_timed", 300);
+        ProfileCollector::increment("counted_and_timed");
+        ProfileCollector::increment("counted_and_timed");
+
+        let stats = ProfileCollector::get_stats("counted_and_timed").unwrap();
+        assert_eq!(stats.count, 4);
+        assert_eq!(stats.timed_count(), 2);
+        assert_eq!(stats.mean_time_micros(), 200);
+        assert_eq!(stats.mean(), Duration::from_micros(200));
+
+        let mut merged = OperationStats::default();
+        merged.merge(&stats);
+        assert_eq!(merged.timed_count(), 2);
+        assert_eq!(merged.mean_time_micros(), 200);
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_clear_stats_keep_categories() {
+        ProfileCollector::record_with_category("keep_category_op", DefaultCategory::Memory, 100);
+        assert!(ProfileCollector::get_stats("keep_category_op").is_some());
+
+        ProfileCollector::clear_stats_keep_categories();
+        assert!(ProfileCollector::get_stats("keep_category_op").is_none());
+
+        // Re-recording without a category still resolves the registered one
+        ProfileCollector::record("keep_category_op", 200);
+        assert_eq!(
+            ProfileCollector::get_stats("keep_category_op")
+                .unwrap()
+                .count,
+            1
+        );
+        let categories = GLOBAL_CATEGORIES.read().unwrap();
+        let category = categories.get("keep_category_op").unwrap();
+        assert_eq!(category.get_name(), DefaultCategory::Memory.get_name());
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_keys_are_interned() {
+        ProfileCollector::clear_all();
+        assert_eq!(interned_key_count(), 0);
+
+        ProfileCollector::record("interned_op", 100);
+        ProfileCollector::record("interned_op", 200);
+        ProfileCollector::record_with_category("interned_op", DefaultCategory::IO, 300);
+        assert_eq!(interned_key_count(), 1);
+
         {
-            if let Ok(mut paused) = GLOBAL_PAUSED.write() {
-                *paused = false;
-            }
+            let categories = GLOBAL_CATEGORIES.read().unwrap();
+            let (category_key, _) = categories.get_key_value("interned_op").unwrap();
+            assert!(category_key.ptr_eq(&intern("interned_op")));
         }
+
+        assert_eq!(ProfileCollector::get_stats("interned_op").unwrap().count, 3);
+
+        ProfileCollector::clear_all();
+        assert_eq!(interned_key_count(), 0);
     }
 
-    /// Get summary statistics across all operations
-    pub fn get_summary() -> SummaryStats {
-        let all_stats = Self::get_all_stats();
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_removed_keys_leave_the_interner() {
+        ProfileCollector::record_with_category("release_renamed", DefaultCategory::IO, 100);
+        assert!(ProfileCollector::rename(
+            "release_renamed",
+            "release_renamed_to"
+        ));
+        assert!(!is_interned("release_renamed"));
+        assert!(is_interned("release_renamed_to"));
 
-        let total_operations: usize = all_stats.values().map(|s| s.count).sum();
-        let total_time: Duration = all_stats.values().map(|s| s.total).sum();
-        let unique_operations = all_stats.len();
+        ProfileCollector::record("release_aliased", 100);
+        assert!(ProfileCollector::alias(
+            "release_aliased",
+            "release_renamed_to"
+        ));
+        assert!(!is_interned("release_aliased"));
 
-        // Find slowest operation (by max time)
-        let slowest = all_stats
-            .iter()
-            .max_by_key(|(_, stats)| stats.max_time_micros)
-            .map(|(name, _)| name.clone());
+        ProfileCollector::reset_operation("release_renamed_to");
+        assert!(is_interned("release_renamed_to"), "still holds a category");
+        ProfileCollector::record("release_reset", 100);
+        ProfileCollector::reset_operation("release_reset");
+        assert!(!is_interned("release_reset"));
+    }
 
-        let slowest_p99_micros = all_stats
-            .values()
-            .map(|s| s.p99_micros())
-            .max()
-            .unwrap_or(0);
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_global_histogram() {
+        ProfileCollector::clear_all();
+        assert!(ProfileCollector::global_histogram().is_none());
 
-        // Find busiest operation (by call count)
-        let busiest = all_stats
-            .iter()
-            .max_by_key(|(_, stats)| stats.count)
-            .map(|(name, stats)| (name.clone(), stats.count));
+        for _ in 0..50 {
+            ProfileCollector::record("global_fast", 100);
+        }
+        for _ in 0..50 {
+            ProfileCollector::record("global_slow", 1000);
+        }
 
-        let (busiest_operation, busiest_count) = busiest
-            .map(|(name, count)| (Some(name), count))
-            .unwrap_or((None, 0));
+        let fast = ProfileCollector::get_stats("global_fast").unwrap();
+        let slow = ProfileCollector::get_stats("global_slow").unwrap();
+        let global = ProfileCollector::global_histogram().unwrap();
 
-        SummaryStats {
-            total_operations: total_operations as u64,
-            unique_operations,
-            total_time_micros: total_time.as_micros() as u64,
-            slowest_operation: slowest,
-            slowest_p99_micros,
-            busiest_operation,
-            busiest_count,
+        assert_eq!(global.count, 100);
+        assert_eq!(global.total, fast.total + slow.total);
+        assert_eq!(global.min_time_micros, 100);
+        assert_eq!(global.max_time_micros, 1000);
+        assert!(global.p99_micros() >= fast.p99_micros());
+        assert!(global.p99_micros() <= slow.p99_micros());
+        assert!(global.p50_micros() < slow.p50_micros());
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_validate_known() {
+        ProfileCollector::clear_all();
+
+        #[derive(Debug)]
+        enum Op {
+            Read,
+            Write,
+        }
+
+        impl Operation for Op {
+            fn to_str(&self) -> String {
+                // Copy-paste slip: both variants share a key
+                match self {
+                    Op::Read => "io".to_string(),
+                    Op::Write => "io".to_string(),
+                }
+            }
         }
+
+        let keys: Vec<String> = [Op::Read, Op::Write]
+            .iter()
+            .map(|op| format!("::{}", op.to_str()))
+            .collect();
+        let known: Vec<&str> = keys.iter().map(String::as_str).collect();
+
+        ProfileCollector::record("::io", 10);
+        ProfileCollector::record("::legacy_io", 10);
+
+        let validation = ProfileCollector::validate_known(&known);
+        assert!(!validation.is_ok());
+        assert_eq!(validation.duplicates, vec!["::io".to_string()]);
+        assert_eq!(validation.unknown, vec!["::legacy_io".to_string()]);
+
+        ProfileCollector::clear_all();
+        ProfileCollector::record("::io", 10);
+        assert!(ProfileCollector::validate_known(&["::io"]).is_ok());
     }
 
-    /// Print a simple report of all collected stats
-    pub fn report_stats() {
-        println!("==== Profile Report ====");
-        let stats = Self::get_all_stats();
-        for (key, stat) in stats {
-            println!(
-                "{} -> count: {}, total: {:?}, avg: {:?}",
-                key,
-                stat.count,
-                stat.total,
-                stat.mean()
-            );
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_summary_throughput() {
+        let mut stats = HashMap::new();
+        let mut op = OperationStats::default();
+        for _ in 0..10 {
+            op.increment();
         }
+        stats.insert("op".to_string(), op);
+
+        let summary = SummaryStats::from_stats(&stats);
+        assert_eq!(summary.collection_window, None);
+        assert_eq!(summary.ops_per_second, 0.0);
+
+        let summary = summary.with_window(Duration::from_secs(2));
+        assert_eq!(summary.collection_window, Some(Duration::from_secs(2)));
+        assert_eq!(summary.ops_per_second, 5.0);
+
+        // Zero and near-zero windows don't produce infinite rates
+        let summary = SummaryStats::from_stats(&stats).with_window(Duration::ZERO);
+        assert_eq!(summary.ops_per_second, 0.0);
+        let summary = SummaryStats::from_stats(&stats).with_window(Duration::from_nanos(10));
+        assert_eq!(summary.ops_per_second, 0.0);
     }
-}
 
-/// Summary statistics across all profiled operations
-#[derive(Debug, Clone, Default)]
-pub struct SummaryStats {
-    /// Total number of operations recorded
-    pub total_operations: u64,
-    /// Number of unique operation types
-    pub unique_operations: usize,
-    /// Total time spent across all operations (microseconds)
-    pub total_time_micros: u64,
-    /// Operation with the slowest single execution
-    pub slowest_operation: Option<String>,
-    /// Slowest p99 time in microseconds
-    pub slowest_p99_micros: u64,
-    /// Operation with the most calls
-    pub busiest_operation: Option<String>,
-    /// Number of calls for the busiest operation
-    pub busiest_count: usize,
-}
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_stats_vec() {
+        ProfileCollector::clear_all();
+        ProfileCollector::record("Database::query", 100);
+        ProfileCollector::record("Database::query", 300);
+        ProfileCollector::record("Cache::get", 10);
+        ProfileCollector::increment("uncategorized_event");
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let rows = ProfileCollector::stats_vec();
+        assert_eq!(rows.len(), ProfileCollector::get_all_stats().len());
+
+        let query = rows
+            .iter()
+            .find(|row| row.category == "Database" && row.operation == "query")
+            .unwrap();
+        assert_eq!(query.count, 2);
+        assert_eq!(query.mean_us, 200);
+        assert_eq!(query.min_us, 100);
+        assert_eq!(query.max_us, 300);
+        assert!(query.p50_us >= 99 && query.p50_us <= 101);
+        assert!(query.p999_us >= 299);
+
+        let event = rows
+            .iter()
+            .find(|row| row.operation == "uncategorized_event")
+            .unwrap();
+        assert_eq!(event.category, "");
+        assert_eq!(event.count, 1);
+        assert_eq!(event.min_us, 0);
+    }
+
+    #[test]
+    #[cfg(all(feature = "full", feature = "serde"))]
+    fn test_stats_row_serializes() {
+        let mut stats = OperationStats::default();
+        stats.record(Duration::from_micros(50));
+        let row = OperationStatsRow::new("IO::read", &stats);
+
+        let json = serde_json::to_value(&row).unwrap();
+        assert_eq!(json["category"], "IO");
+        assert_eq!(json["operation"], "read");
+        assert_eq!(json["count"], 1);
+        assert_eq!(json["max_us"], 50);
+    }
 
+    #[test]
     #[cfg(feature = "full")]
-    use std::time::Duration;
+    fn test_stats_row_with_huge_values_saturates() {
+        let row = OperationStatsRow {
+            category: "IO".to_string(),
+            operation: "read".to_string(),
+            count: 1,
+            mean_us: u64::MAX,
+            p50_us: u64::MAX,
+            p95_us: u64::MAX,
+            p99_us: u64::MAX,
+            p999_us: u64::MAX,
+            min_us: u64::MAX,
+            max_us: u64::MAX,
+        };
+        let stats = row.to_stats();
+        assert_eq!(stats.min_time_nanos, u64::MAX);
+        assert_eq!(stats.max_time_nanos, u64::MAX);
+        assert_eq!(stats.p99_nanos(), u64::MAX);
+    }
 
     #[test]
     #[cfg(feature = "full")]
-    fn test_record_and_retrieve() {
+    fn test_summary_as_logfmt() {
+        let empty = SummaryStats::from_stats(&HashMap::new()).as_logfmt();
+        assert_eq!(
+            empty,
+            "ops=0 unique=0 total_us=0 p99_max_us=0 ops_per_sec=0.0"
+        );
+
+        let mut stats = HashMap::new();
+        let mut validation = OperationStats::default();
+        for _ in 0..3 {
+            validation.record(Duration::from_micros(100));
+        }
+        let mut lookup = OperationStats::default();
+        lookup.record(Duration::from_micros(200));
+        stats.insert("order_validation".to_string(), validation);
+        stats.insert("cache lookup".to_string(), lookup);
+
+        let line = SummaryStats::from_stats(&stats)
+            .with_window(Duration::from_secs(2))
+            .as_logfmt();
+        assert!(line.starts_with("ops=4 unique=2 total_us=500 p99_max_us=200 ops_per_sec=2.0"));
+        assert!(line.contains(" busiest=order_validation"));
+        // Values with spaces are quoted
+        assert!(line.ends_with(" slowest=\"cache lookup\""));
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_collection_window() {
         ProfileCollector::clear_all();
+        assert!(ProfileCollector::started_at().is_none());
+        assert_eq!(ProfileCollector::get_summary().ops_per_second, 0.0);
 
-        ProfileCollector::record("test_record_and_retrieve", 1000);
-        ProfileCollector::record("test_record_and_retrieve", 2000);
-        ProfileCollector::record("test_record_and_retrieve", 1500);
+        ProfileCollector::record("window_op", 100);
+        let started_at = ProfileCollector::started_at().unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+        ProfileCollector::record("window_op", 100);
+        ProfileCollector::increment("window_counter");
 
-        let stats = ProfileCollector::get_stats("test_record_and_retrieve");
-        assert!(stats.is_some());
+        // Later recordings don't move the start of the window
+        assert_eq!(ProfileCollector::started_at(), Some(started_at));
 
-        let stats = stats.unwrap();
-        assert_eq!(stats.count, 3);
-        assert_eq!(stats.total, Duration::from_micros(4500));
-        assert_eq!(stats.mean(), Duration::from_micros(1500));
+        let summary = ProfileCollector::get_summary();
+        assert!(summary.collection_window.unwrap() >= Duration::from_millis(20));
+        assert!(summary.ops_per_second > 0.0 && summary.ops_per_second <= 3.0 / 0.02);
+
+        ProfileCollector::clear_all();
+        assert!(ProfileCollector::started_at().is_none());
+        assert!(ProfileCollector::collection_window().is_none());
     }
 
     #[test]
     #[cfg(feature = "full")]
-    fn test_multiple_operations() {
+    fn test_set_operation_precision() {
         ProfileCollector::clear_all();
 
-        ProfileCollector::record("multiple_op1", 100);
-        ProfileCollector::record("multiple_op2", 200);
-        ProfileCollector::record("multiple_op1", 150);
+        // Already recorded samples survive a precision change
+        ProfileCollector::record_nanos("precise_op", 500);
+        ProfileCollector::set_operation_precision("precise_op", 5);
+        assert_eq!(ProfileCollector::get_stats("precise_op").unwrap().count, 1);
 
-        let stats1 = ProfileCollector::get_stats("multiple_op1").unwrap();
-        let stats2 = ProfileCollector::get_stats("multiple_op2").unwrap();
+        // The override outlives clearing the collector
+        ProfileCollector::clear_all();
 
-        assert_eq!(stats1.count, 2);
-        assert_eq!(stats2.count, 1);
+        // 100µs and 100.02µs share a bucket at the default 3 significant figures
+        for key in ["precise_op", "default_precision_op"] {
+            for _ in 0..50 {
+                ProfileCollector::record_nanos(key, 100_000);
+            }
+            for _ in 0..50 {
+                ProfileCollector::record_nanos(key, 100_020);
+            }
+        }
 
-        let all_stats = ProfileCollector::get_all_stats();
-        assert_eq!(all_stats.len(), 2);
+        let precise = ProfileCollector::get_stats("precise_op").unwrap();
+        assert_eq!(precise.p50_nanos(), 100_000);
+        assert_eq!(precise.p99_nanos(), 100_020);
+
+        let coarse = ProfileCollector::get_stats("default_precision_op").unwrap();
+        assert_eq!(coarse.p50_nanos(), coarse.p99_nanos());
+
+        // Out-of-range precision is clamped rather than rejected
+        ProfileCollector::set_operation_precision("clamped_op", 9);
+        ProfileCollector::record_nanos("clamped_op", 100);
+        assert_eq!(ProfileCollector::get_stats("clamped_op").unwrap().count, 1);
     }
 
     #[test]
     #[cfg(feature = "full")]
-    fn test_summary_stats() {
+    fn test_export_prometheus_histogram() {
         ProfileCollector::clear_all();
 
-        for i in 0..10 {
-            ProfileCollector::record("summary_op1", 100 + i * 10);
+        for micros in [50, 80, 150, 400, 900, 2500, 2500, 20_000] {
+            ProfileCollector::record("IO::read", micros);
         }
+        ProfileCollector::increment("IO::counted_only");
+        ProfileCollector::record_summary("IO::summary", 10, 500, 400, 900, 1200, 1500);
 
-        for i in 0..5 {
-            ProfileCollector::record("summary_op2", 200 + i * 20);
+        // Buckets may be given in any order
+        let output = ProfileCollector::export_prometheus_histogram(&[1000, 100, 10_000]);
+        assert!(output.contains("# TYPE quantum_pulse_operation_duration_seconds histogram"));
+        assert!(!output.contains("counted_only"));
+
+        let bucket_counts: Vec<u64> = output
+            .lines()
+            .filter(|line| line.contains("_bucket{operation=\"IO::read\""))
+            .map(|line| line.rsplit(' ').next().unwrap().parse().unwrap())
+            .collect();
+        assert_eq!(bucket_counts, vec![2, 5, 7, 8]);
+        assert!(bucket_counts.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        let count = ProfileCollector::get_stats("IO::read").unwrap().count;
+        assert!(output.contains("_bucket{operation=\"IO::read\",le=\"0.0001\"} 2"));
+        assert!(output.contains("_bucket{operation=\"IO::read\",le=\"+Inf\"} 8"));
+        assert!(output.contains(&format!(
+            "quantum_pulse_operation_duration_seconds_count{{operation=\"IO::read\"}} {count}"
+        )));
+        assert!(output.contains(
+            "quantum_pulse_operation_duration_seconds_sum{operation=\"IO::read\"} 0.02658"
+        ));
+
+        // Summaries have no samples to bucket, but still count and sum
+        assert!(!output.contains("_bucket{operation=\"IO::summary\",le=\"0.001\"}"));
+        assert!(output.contains("_bucket{operation=\"IO::summary\",le=\"+Inf\"} 10"));
+        assert!(output.contains("_count{operation=\"IO::summary\"} 10"));
+        assert!(output.contains("_sum{operation=\"IO::summary\"} 0.005"));
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_summary_total_does_not_truncate_large_counts() {
+        let percentiles = [Duration::from_micros(10); 4];
+        let stats =
+            OperationStats::from_summary(5_000_000_000, Duration::from_micros(10), percentiles);
+        assert_eq!(stats.total, Duration::from_secs(50_000));
+        assert_eq!(stats.mean(), Duration::from_micros(10));
+        assert_eq!(stats.mean_time_micros(), 10);
+
+        // Totals beyond what a u64 of nanoseconds holds saturate
+        let stats = OperationStats::from_summary(usize::MAX, Duration::from_secs(1), percentiles);
+        assert_eq!(stats.total, Duration::from_nanos(u64::MAX));
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_escape_label_value() {
+        assert_eq!(escape_label_value("IO::read"), "IO::read");
+        assert_eq!(escape_label_value("a\"b\\c\n"), "a\\\"b\\\\c\\n");
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_display() {
+        let mut stats = OperationStats::default();
+        stats.increment();
+        assert_eq!(stats.to_string(), "count=1");
+
+        let mut stats = OperationStats::default();
+        for micros in [1000, 1200, 1400, 3400] {
+            stats.record(Duration::from_micros(micros));
         }
+        let line = stats.to_string();
+        assert!(line.starts_with("count=4 mean=1.75 ms p99=3."), "{line}");
+        assert!(line.ends_with(" max=3.40 ms"), "{line}");
+    }
 
-        let summary = ProfileCollector::get_summary();
-        assert_eq!(summary.total_operations, 15);
-        assert_eq!(summary.unique_operations, 2);
-        assert!(summary.total_time_micros > 0);
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_distribution_and_sparkline() {
+        let mut stats = OperationStats::default();
+        assert_eq!(stats.distribution(4), vec![0; 4]);
+        assert_eq!(stats.sparkline(), "        ");
+
+        for _ in 0..10 {
+            stats.record(Duration::from_micros(100));
+        }
+        for _ in 0..3 {
+            stats.record(Duration::from_micros(500));
+        }
+        stats.record(Duration::from_micros(1000));
+        stats.increment();
+
+        let bins = stats.distribution(5);
+        assert_eq!(bins.len(), 5);
+        assert_eq!(bins.iter().sum::<u64>(), 14);
+        assert_eq!(bins[0], 10);
+        assert_eq!(bins[4], 1);
+
+        for buckets in [1, 3, 8, 64] {
+            assert_eq!(stats.distribution(buckets).iter().sum::<u64>(), 14);
+        }
+        assert!(stats.distribution(0).is_empty());
+
+        let sparkline = stats.sparkline();
+        assert_eq!(sparkline.chars().count(), 8);
+        assert!(sparkline.starts_with('█'));
+        assert!(sparkline.ends_with('▁'));
     }
 
     #[test]
     #[cfg(feature = "full")]
-    fn test_has_data() {
-        ProfileCollector::clear_all();
-        assert!(!ProfileCollector::has_data()); // Clear data first
+    fn test_max_time_at() {
+        let mut stats = OperationStats::default();
+        assert!(stats.max_time_at().is_none());
 
-        ProfileCollector::record("test", 100);
-        assert!(ProfileCollector::has_data());
+        stats.record(Duration::from_micros(10));
+        let first = stats.max_time_at().unwrap();
+
+        std::thread::sleep(Duration::from_millis(5));
+        stats.record(Duration::from_micros(5));
+        assert_eq!(stats.max_time_at(), Some(first));
+
+        stats.record(Duration::from_micros(50));
+        let spike = stats.max_time_at().unwrap();
+        assert!(spike > first);
+        let age = SystemTime::now().duration_since(spike).unwrap();
+        assert!(age < Duration::from_secs(1));
+
+        // Merging keeps the timestamp of the larger max
+        let mut other = OperationStats::default();
+        other.record(Duration::from_micros(20));
+        stats.merge(&other);
+        assert_eq!(stats.max_time_at(), Some(spike));
+        other.merge(&stats);
+        assert_eq!(other.max_time_at(), Some(spike));
+    }
+
+    #[test]
+    #[cfg(all(feature = "full", feature = "tokio"))]
+    fn test_subscribe() {
+        use tokio::sync::broadcast::error::TryRecvError;
+
+        let mut receiver = ProfileCollector::subscribe();
+
+        ProfileCollector::record("subscribed_op", 12);
+        ProfileCollector::record_nanos("subscribed_op", 3_500);
+
+        // Other tests may record concurrently
+        let mut received = Vec::new();
+        while let Ok((key, micros)) = receiver.try_recv() {
+            if key == "subscribed_op" {
+                received.push(micros);
+            }
+        }
+        assert_eq!(received, vec![12, 3]);
+
+        // A receiver that falls behind loses messages instead of blocking
+        for _ in 0..SUBSCRIBER_CAPACITY + 10 {
+            ProfileCollector::record("subscribed_flood", 1);
+        }
+        assert!(matches!(receiver.try_recv(), Err(TryRecvError::Lagged(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_self_profile() {
+        ProfileCollector::set_self_profiling(true);
+        for _ in 0..10 {
+            ProfileCollector::record("self_profiled_op", 100);
+        }
+        ProfileCollector::set_self_profiling(false);
+
+        // Other tests may record concurrently
+        let overhead = ProfileCollector::self_profile();
+        assert!(overhead.records >= 10);
+        assert!(overhead.total > Duration::ZERO);
+        assert!(overhead.mean() <= overhead.total);
+
+        // Stopped measurement no longer counts
+        ProfileCollector::record("self_profiled_op", 100);
+        assert_eq!(ProfileCollector::self_profile(), overhead);
+
+        assert_eq!(SelfStats::default().mean(), Duration::ZERO);
     }
 
     #[test]
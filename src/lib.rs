@@ -56,13 +56,18 @@
 pub mod category;
 #[cfg(feature = "full")]
 pub mod collector;
-// #[cfg(feature = "full")]
-// pub mod metrics;
+#[cfg(feature = "full")]
+pub mod metrics;
 #[cfg(feature = "full")]
 pub mod operation;
+pub mod quantile;
 #[cfg(feature = "full")]
 pub mod reporter;
 #[cfg(feature = "full")]
+pub mod store;
+#[cfg(feature = "full")]
+mod stream;
+#[cfg(feature = "full")]
 pub mod timer;
 
 // Stub implementation modules - zero-cost abstractions
@@ -76,11 +81,53 @@ pub mod category {
         fn color_hint(&self) -> Option<&str> {
             None
         }
+        fn icon(&self) -> Option<&str> {
+            None
+        }
         fn priority(&self) -> i32 {
             0
         }
     }
 
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct CategoryInfo {
+        pub name: String,
+        pub description: String,
+        pub color: Option<String>,
+        pub icon: Option<String>,
+        pub priority: i32,
+    }
+
+    impl CategoryInfo {
+        pub fn from_category(category: &dyn Category) -> Self {
+            Self {
+                name: category.get_name().to_string(),
+                description: category.get_description().to_string(),
+                color: category.color_hint().map(str::to_string),
+                icon: category.icon().map(str::to_string),
+                priority: category.priority(),
+            }
+        }
+    }
+
+    impl Category for CategoryInfo {
+        fn get_name(&self) -> &str {
+            &self.name
+        }
+        fn get_description(&self) -> &str {
+            &self.description
+        }
+        fn color_hint(&self) -> Option<&str> {
+            self.color.as_deref()
+        }
+        fn icon(&self) -> Option<&str> {
+            self.icon.as_deref()
+        }
+        fn priority(&self) -> i32 {
+            self.priority
+        }
+    }
+
     #[derive(Debug)]
     pub struct NoCategory;
 
@@ -107,6 +154,26 @@ pub mod operation {
         fn to_str(&self) -> String {
             format!("{:?}", self)
         }
+
+        fn normalized_key(&self) -> String {
+            self.to_str()
+        }
+
+        fn op_id(&self) -> u32 {
+            0
+        }
+
+        fn expected_micros(&self) -> Option<u64> {
+            None
+        }
+
+        fn sort_hint(&self) -> i32 {
+            0
+        }
+
+        fn aggregate_key(&self) -> Option<String> {
+            None
+        }
     }
 
     #[derive(Debug)]
@@ -130,348 +197,1671 @@ pub mod operation {
 #[cfg(not(feature = "full"))]
 pub mod collector {
     use std::collections::HashMap;
+    use std::sync::atomic::{AtomicBool, Ordering};
     use std::time::Duration;
 
-    #[derive(Debug, Clone, Default)]
-    pub struct OperationStats {
-        pub count: usize,
-        pub total: Duration,
-    }
-
-    impl OperationStats {
-        pub fn mean(&self) -> Duration {
-            if self.count == 0 {
-                Duration::ZERO
-            } else {
-                self.total / (self.count as u32)
-            }
-        }
-    }
+    /// Set once the empty-report warning has been printed
+    pub(crate) static STUB_WARNING_SHOWN: AtomicBool = AtomicBool::new(false);
 
-    pub struct ProfileCollector;
+    pub const OVERFLOW_KEY: &str = "__overflow__";
 
-    impl ProfileCollector {
-        pub fn record(_key: &str, _duration_micros: u64) {}
-        pub fn get_stats(_key: &str) -> Option<OperationStats> {
-            None
-        }
-        pub fn get_all_stats() -> HashMap<String, OperationStats> {
-            HashMap::new()
-        }
-        pub fn clear_all() {}
-        pub fn reset_all() {}
-        pub fn reset_operation(_key: &str) {}
-        pub fn has_data() -> bool {
-            false
-        }
-        pub fn total_operations() -> u64 {
-            1 // Return 1 in stub mode to make tests pass
-        }
-        pub fn get_summary() -> SummaryStats {
-            SummaryStats::default()
+    /// Tell users who forgot the `full` feature why their report is blank
+    ///
+    /// Printed at most once per process; set
+    /// `QUANTUM_PULSE_SILENCE_STUB_WARNING` to suppress it.
+    fn warn_stub_report() {
+        if std::env::var_os("QUANTUM_PULSE_SILENCE_STUB_WARNING").is_some() {
+            return;
         }
-        pub fn report_stats() {}
-
-        pub fn pause() {}
-
-        pub fn unpause() {}
-
-        pub fn is_paused() -> bool {
-            false
+        if !STUB_WARNING_SHOWN.swap(true, Ordering::Relaxed) {
+            eprintln!("quantum-pulse: 'full' feature not enabled; report is empty.");
         }
-
-        pub fn reset_pause_state() {}
-    }
-
-    #[derive(Debug, Default)]
-    pub struct SummaryStats {
-        pub total_operations: u64,
-        pub unique_operations: usize,
-        pub total_time_micros: u64,
-    }
-
-    #[derive(Debug, Clone, Copy)]
-    pub enum TimeFormat {
-        Microseconds,
-        Milliseconds,
-        Seconds,
-        Auto,
-    }
-
-    #[derive(Debug, Clone, Copy)]
-    pub enum SortMetric {
-        TotalTime,
-        MeanTime,
-        MaxTime,
-        CallCount,
     }
 
-    #[derive(Debug, Clone, Copy)]
-    pub struct Percentile {
-        pub p50: u64,
-        pub p95: u64,
-        pub p99: u64,
-        pub p999: u64,
-    }
+    /// Quantiles estimated by stub stats, matching the full-mode getters
+    const STUB_QUANTILES: [f64; 4] = [0.5, 0.95, 0.99, 0.999];
 
-    #[derive(Debug)]
-    pub struct ReportConfig {
-        pub include_percentiles: bool,
-        pub group_by_category: bool,
-        pub time_format: TimeFormat,
-        pub sort_by: SortMetric,
-        pub sort_by_time: bool,
-        pub min_samples: u64,
+    /// Stub stats keep only counts and P² percentile estimates
+    #[derive(Debug, Clone)]
+    pub struct OperationStats {
+        pub count: usize,
+        pub total: Duration,
+        quantiles: [crate::quantile::P2Quantile; 4],
     }
 
-    impl Default for ReportConfig {
+    impl Default for OperationStats {
         fn default() -> Self {
             Self {
-                include_percentiles: false,
-                group_by_category: false,
-                time_format: TimeFormat::Auto,
-                sort_by: SortMetric::TotalTime,
-                sort_by_time: false,
-                min_samples: 0,
+                count: 0,
+                total: Duration::ZERO,
+                quantiles: STUB_QUANTILES.map(crate::quantile::P2Quantile::new),
             }
         }
     }
 
-    pub struct ProfileReport {
-        pub stats: HashMap<String, OperationStats>,
-        pub config: ReportConfig,
+    impl std::fmt::Display for OperationStats {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "count={}", self.count)
+        }
     }
 
-    impl ProfileReport {
-        pub fn generate() -> Self {
-            Self {
-                stats: HashMap::new(),
-                config: ReportConfig::default(),
+    impl OperationStats {
+        pub fn record(&mut self, duration: Duration) {
+            self.count += 1;
+            self.total += duration;
+            for quantile in &mut self.quantiles {
+                quantile.observe(duration.as_nanos() as f64);
             }
         }
 
-        pub fn generate_with_config(_config: ReportConfig) -> Self {
-            Self {
-                stats: HashMap::new(),
-                config: ReportConfig::default(),
-            }
+        pub fn zero_duration_count(&self) -> u64 {
+            0
         }
 
-        pub fn quick_summary(&self) -> String {
-            String::new()
+        pub fn timed_count(&self) -> usize {
+            self.count
         }
 
-        pub fn summary_stats(&self) -> SummaryStats {
-            SummaryStats::default()
+        fn estimate_nanos(&self, index: usize) -> u64 {
+            self.quantiles[index].estimate().round() as u64
         }
 
-        pub fn to_string(&self) -> String {
-            String::new()
+        pub fn p50_nanos(&self) -> u64 {
+            self.estimate_nanos(0)
         }
 
-        pub fn top_operations_by(
-            &self,
-            _metric: SortMetric,
-            _limit: usize,
-        ) -> Vec<(String, OperationStats)> {
-            Vec::new()
+        pub fn p95_nanos(&self) -> u64 {
+            self.estimate_nanos(1)
         }
-    }
 
-    impl std::fmt::Debug for ProfileReport {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            write!(f, "")
+        pub fn p99_nanos(&self) -> u64 {
+            self.estimate_nanos(2)
         }
-    }
-
-    pub struct ReportBuilder {
-        _phantom: std::marker::PhantomData<()>,
-    }
 
-    impl Default for ReportBuilder {
-        fn default() -> Self {
-            Self::new()
+        pub fn p999_nanos(&self) -> u64 {
+            self.estimate_nanos(3)
         }
-    }
 
-    impl ReportBuilder {
-        pub fn new() -> Self {
-            Self {
-                _phantom: std::marker::PhantomData,
-            }
+        pub fn p50_micros(&self) -> u64 {
+            self.p50_nanos() / 1000
         }
 
-        pub fn group_by_category(self, _enabled: bool) -> Self {
-            self
-        }
-        pub fn include_percentiles(self, _enabled: bool) -> Self {
-            self
-        }
-        pub fn time_format(self, _format: TimeFormat) -> Self {
-            self
-        }
-        pub fn sort_by_time(self, _enabled: bool) -> Self {
-            self
-        }
-        pub fn build(self) -> ProfileReport {
-            ProfileReport::generate()
+        pub fn p95_micros(&self) -> u64 {
+            self.p95_nanos() / 1000
         }
-    }
-}
-
-#[cfg(not(feature = "full"))]
-pub mod timer {
-    use crate::operation::Operation;
 
-    pub struct ProfileTimer<'a> {
-        _operation: &'a dyn Operation,
-    }
-
-    impl<'a> ProfileTimer<'a> {
-        pub fn new(operation: &'a dyn Operation) -> Self {
-            Self {
-                _operation: operation,
-            }
+        pub fn p99_micros(&self) -> u64 {
+            self.p99_nanos() / 1000
         }
-    }
 
-    impl<'a> Drop for ProfileTimer<'a> {
-        fn drop(&mut self) {
-            // No-op in stub mode
+        pub fn p999_micros(&self) -> u64 {
+            self.p999_nanos() / 1000
         }
-    }
-
-    pub struct ProfileTimerAsync<'a> {
-        _operation: &'a dyn Operation,
-    }
 
-    impl<'a> ProfileTimerAsync<'a> {
-        pub fn new(operation: &'a dyn Operation) -> Self {
-            Self {
-                _operation: operation,
+        pub fn mean_micros_f64(&self) -> f64 {
+            if self.count == 0 {
+                0.0
+            } else {
+                self.total.as_nanos() as f64 / 1000.0 / self.count as f64
             }
         }
 
-        pub async fn run<F, R>(self, fut: F) -> R
-        where
-            F: std::future::Future<Output = R>,
-        {
-            // In stub mode, just execute the future
-            fut.await
+        pub fn mean(&self) -> Duration {
+            if self.count == 0 {
+                Duration::ZERO
+            } else {
+                self.total / (self.count as u32)
+            }
         }
-    }
-
-    pub struct PausableTimer<'a> {
-        _operation: &'a dyn Operation,
-    }
 
-    impl<'a> PausableTimer<'a> {
-        pub fn new(operation: &'a dyn Operation) -> Self {
-            Self {
-                _operation: operation,
-            }
+        pub fn min(&self) -> Duration {
+            Duration::ZERO
         }
 
-        pub fn new_paused(operation: &'a dyn Operation) -> Self {
-            Self {
-                _operation: operation,
-            }
+        pub fn max(&self) -> Duration {
+            Duration::ZERO
         }
 
-        pub fn pause(&mut self) {}
+        pub fn merge(&mut self, _other: &OperationStats) {}
 
-        pub fn resume(&mut self) {}
+        pub fn increment(&mut self) {}
 
-        pub fn total_elapsed(&self) -> std::time::Duration {
-            std::time::Duration::ZERO
+        pub fn min_time_micros(&self) -> u64 {
+            0
         }
 
-        pub fn total_elapsed_micros(&self) -> u64 {
+        pub fn min_time_nanos(&self) -> u64 {
             0
         }
 
-        pub fn total_elapsed_millis(&self) -> u64 {
+        pub fn ewma_nanos(&self) -> u64 {
             0
         }
 
-        pub fn is_running(&self) -> bool {
+        pub fn ewma_micros(&self) -> u64 {
+            0
+        }
+
+        pub fn has_percentiles(&self) -> bool {
             false
         }
 
-        pub fn operation(&self) -> &dyn Operation {
-            self._operation
+        pub fn is_imported(&self) -> bool {
+            false
+        }
+
+        pub fn memory_footprint(&self) -> usize {
+            std::mem::size_of::<Self>()
+        }
+
+        pub fn has_timing(&self) -> bool {
+            false
+        }
+
+        pub fn max_time_at(&self) -> Option<std::time::SystemTime> {
+            None
+        }
+
+        pub fn distribution(&self, buckets: usize) -> Vec<u64> {
+            vec![0; buckets]
+        }
+
+        pub fn sparkline(&self) -> String {
+            String::new()
+        }
+    }
+
+    pub struct ProfileCollector;
+
+    impl ProfileCollector {
+        pub fn record(_key: &str, _duration_micros: u64) {}
+        pub fn record_nanos(_key: &str, _duration_nanos: u64) {}
+        pub fn record_f64(_key: &str, _duration_micros: f64) {}
+        pub fn record_summary(
+            _key: &str,
+            _count: usize,
+            _mean_us: u64,
+            _p50_us: u64,
+            _p95_us: u64,
+            _p99_us: u64,
+            _p999_us: u64,
+        ) {
+        }
+        pub fn increment(_key: &str) {}
+        pub fn set_stream_sink(_sink: Box<dyn std::io::Write + Send>) {}
+        pub fn clear_stream_sink() {}
+        pub fn set_stream_relative_timestamps(_relative: bool) {}
+        #[cfg(feature = "tokio")]
+        pub fn subscribe() -> tokio::sync::broadcast::Receiver<(String, u64)> {
+            tokio::sync::broadcast::channel(1).1
+        }
+        pub fn flush_stream() {}
+        pub fn stream_dropped_records() -> u64 {
+            0
+        }
+        pub fn increment_operation(_operation: &dyn crate::Operation) {}
+        pub fn get_stats(_key: &str) -> Option<OperationStats> {
+            None
+        }
+        pub fn get_all_stats() -> HashMap<String, OperationStats> {
+            HashMap::new()
+        }
+        pub fn keys() -> Vec<String> {
+            Vec::new()
+        }
+        pub fn contains(_key: &str) -> bool {
+            false
+        }
+        pub fn global_histogram() -> Option<OperationStats> {
+            None
+        }
+        pub fn category_summary(_category_name: &str) -> Option<OperationStats> {
+            None
+        }
+        pub fn clear_all() {}
+        pub fn clear_stats_keep_categories() {}
+        pub fn categories_with_stats() -> Vec<(crate::category::CategoryInfo, OperationStats)> {
+            Vec::new()
+        }
+        pub fn category_of(_key: &str) -> Option<crate::category::CategoryInfo> {
+            None
+        }
+        pub fn set_warmup(_samples: usize, _counted: bool) {}
+        pub fn warmup() -> (usize, bool) {
+            (0, true)
+        }
+        pub fn set_idle_reset(_idle: Option<Duration>) {}
+        pub fn idle_reset() -> Option<Duration> {
+            None
+        }
+        pub fn set_exclusive_time(_enabled: bool) {}
+        pub fn is_exclusive_time() -> bool {
+            false
+        }
+        pub fn set_max_key_length(_max_bytes: usize) {}
+        pub fn set_percentiles_enabled(_enabled: bool) {}
+        pub fn percentiles_enabled() -> bool {
+            false
+        }
+        pub fn max_key_length() -> usize {
+            256
+        }
+        pub fn set_max_keys(_max_keys: usize) {}
+        pub fn max_keys() -> usize {
+            10_000
+        }
+        pub fn cardinality_overflowed() -> u64 {
+            0
+        }
+        pub fn reset_all() {}
+        pub fn reset_for_test() {}
+        pub fn lock_contention_count() -> u64 {
+            0
+        }
+        pub fn register_category(_info: crate::category::CategoryInfo) {}
+        pub fn registered_categories() -> Vec<crate::category::CategoryInfo> {
+            Vec::new()
+        }
+        pub fn reset_operation(_key: &str) {}
+        pub fn alias(_from_key: &str, _to_key: &str) -> bool {
+            false
+        }
+        pub fn rename(_old_key: &str, _new_key: &str) -> bool {
+            false
+        }
+        pub fn has_data() -> bool {
+            false
+        }
+        /// Always 1 in stub mode, a historical workaround for tests that
+        /// expect recorded data. Don't use it to detect whether profiling is
+        /// compiled in; use [`ProfileCollector::is_active`] instead.
+        pub fn total_operations() -> u64 {
+            1 // Return 1 in stub mode to make tests pass
+        }
+        pub fn is_active() -> bool {
+            false
+        }
+        pub fn started_at() -> Option<std::time::SystemTime> {
+            None
+        }
+        pub fn collection_window() -> Option<Duration> {
+            None
+        }
+        pub fn validate_known(_known: &[&str]) -> KeyValidation {
+            KeyValidation::default()
+        }
+        pub fn set_ewma_alpha(_alpha: f64) {}
+        pub fn set_self_profiling(_enabled: bool) {}
+        pub fn self_profile() -> SelfStats {
+            SelfStats::default()
+        }
+        pub fn record_with_note(_key: &str, _duration_micros: u64, _note: &str) {}
+        pub fn set_slow_event_threshold(_threshold: Option<Duration>) {}
+        pub fn slow_event_threshold() -> Option<Duration> {
+            None
+        }
+        pub fn set_slow_event_capacity(_capacity: usize) {}
+        pub fn slow_events() -> Vec<SlowEvent> {
+            Vec::new()
+        }
+        #[inline]
+        pub fn profile<R>(
+            _operation: &dyn crate::operation::Operation,
+            f: impl FnOnce() -> R,
+        ) -> R {
+            f()
+        }
+        pub fn benchmark_compare<RA, RB>(
+            name_a: &str,
+            mut fn_a: impl FnMut() -> RA,
+            name_b: &str,
+            mut fn_b: impl FnMut() -> RB,
+            iterations: usize,
+        ) -> ComparisonResult {
+            for _ in 0..iterations {
+                std::hint::black_box(fn_a());
+                std::hint::black_box(fn_b());
+            }
+            ComparisonResult {
+                name_a: name_a.to_string(),
+                stats_a: OperationStats::default(),
+                name_b: name_b.to_string(),
+                stats_b: OperationStats::default(),
+            }
+        }
+        pub fn profile_async<'a, F>(
+            _operation: &'a dyn crate::operation::Operation,
+            fut: F,
+        ) -> impl std::future::Future<Output = F::Output> + 'a
+        where
+            F: std::future::Future + 'a,
+        {
+            fut
+        }
+        pub fn set_histogram_bounds(bounds: Option<(Duration, Duration)>) -> bool {
+            bounds.is_none_or(|(low, high)| !low.is_zero() && high >= low * 2)
+        }
+        pub fn histogram_bounds() -> Option<(Duration, Duration)> {
+            None
+        }
+        pub fn set_call_paths_enabled(_enabled: bool) {}
+        pub fn call_paths_enabled() -> bool {
+            false
+        }
+        pub fn hot_path() -> Vec<(String, Duration)> {
+            Vec::new()
+        }
+        pub fn worst_samples(_key: &str, _n: usize) -> Vec<u64> {
+            Vec::new()
+        }
+        pub fn export_binary() -> Vec<u8> {
+            Vec::new()
+        }
+        pub fn import_binary(_bytes: &[u8]) -> std::io::Result<usize> {
+            Ok(0)
+        }
+        pub fn record_span(
+            _operation: &dyn crate::operation::Operation,
+            _start: std::time::Instant,
+            _end: std::time::Instant,
+        ) {
+        }
+        pub fn start(operation: &dyn crate::operation::Operation) -> crate::timer::TimerToken {
+            crate::timer::TimerToken::start(operation)
+        }
+        pub fn stop(_token: crate::timer::TimerToken) -> Duration {
+            Duration::ZERO
+        }
+        pub fn record_nanos_in(
+            _store: &dyn crate::store::StatsStore,
+            _key: &str,
+            _duration_nanos: u64,
+        ) {
+        }
+        pub fn stats_vec() -> Vec<OperationStatsRow> {
+            Vec::new()
+        }
+        pub fn set_stats_store(_store: Box<dyn crate::store::StatsStore>) -> bool {
+            false
+        }
+        pub fn add_mirror(_mirror: std::sync::Arc<dyn crate::store::StatsStore>) {}
+        pub fn clear_mirrors() {}
+        pub fn export_prometheus_histogram(_buckets: &[u64]) -> String {
+            String::new()
+        }
+        pub fn set_operation_precision(_key: &str, _sig_figs: u8) {}
+        pub fn set_expected_micros(_key: &str, _micros: u64) {}
+        pub fn set_operation_order(_key: &str, _order: i32) {}
+        pub fn operation_order(_key: &str) -> Option<i32> {
+            None
+        }
+        pub fn expected_micros(_key: &str) -> Option<u64> {
+            None
+        }
+        pub fn get_summary() -> SummaryStats {
+            SummaryStats::default()
+        }
+        pub fn report_stats() {}
+
+        pub fn pause() {}
+
+        pub fn unpause() {}
+
+        pub fn is_paused() -> bool {
+            false
+        }
+
+        pub fn is_aggregate(_key: &str) -> bool {
+            false
+        }
+
+        pub fn suppressed_count() -> u64 {
+            0
+        }
+
+        pub fn reset_pause_state() {}
+
+        #[cfg(feature = "testing")]
+        pub fn test_guard() -> TestGuard {
+            let lock = TEST_LOCK
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            TestGuard { _lock: lock }
+        }
+
+        pub fn set_category_enabled(_category_name: &str, _enabled: bool) {}
+
+        pub fn is_category_enabled(_category_name: &str) -> bool {
+            true
+        }
+
+        pub fn set_operation_whitelist(_whitelist: Option<std::collections::HashSet<String>>) {}
+
+        pub fn operation_whitelist() -> Option<std::collections::HashSet<String>> {
+            None
+        }
+
+        pub fn is_operation_whitelisted(_key: &str) -> bool {
+            true
+        }
+
+        pub fn print_on_drop(config: ReportConfig) -> ReportGuard {
+            ReportGuard::new(config)
+        }
+    }
+
+    #[derive(Debug, Default)]
+    pub struct SummaryStats {
+        pub total_operations: u64,
+        pub unique_operations: usize,
+        pub total_time_micros: u64,
+        pub collection_window: Option<Duration>,
+        pub ops_per_second: f64,
+    }
+
+    impl SummaryStats {
+        pub fn from_stats(_all_stats: &HashMap<String, OperationStats>) -> Self {
+            Self::default()
+        }
+
+        pub fn with_window(self, _window: Duration) -> Self {
+            self
+        }
+
+        pub fn as_logfmt(&self) -> String {
+            format!(
+                "ops={} unique={} total_us={} p99_max_us=0 ops_per_sec={:.1}",
+                self.total_operations,
+                self.unique_operations,
+                self.total_time_micros,
+                self.ops_per_second
+            )
+        }
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    pub struct BaselineComparison {
+        pub regressed: Vec<String>,
+        pub new: Vec<String>,
+    }
+
+    impl BaselineComparison {
+        pub fn is_ok(&self) -> bool {
+            true
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct OperationStatsRow {
+        pub category: String,
+        pub operation: String,
+        pub count: u64,
+        pub mean_us: u64,
+        pub p50_us: u64,
+        pub p95_us: u64,
+        pub p99_us: u64,
+        pub p999_us: u64,
+        pub min_us: u64,
+        pub max_us: u64,
+    }
+
+    impl OperationStatsRow {
+        pub fn new(key: &str, stats: &OperationStats) -> Self {
+            let (category, operation) = key.split_once("::").unwrap_or(("", key));
+            Self {
+                category: category.to_string(),
+                operation: operation.to_string(),
+                count: stats.count as u64,
+                mean_us: 0,
+                p50_us: 0,
+                p95_us: 0,
+                p99_us: 0,
+                p999_us: 0,
+                min_us: 0,
+                max_us: 0,
+            }
+        }
+
+        pub fn to_stats(&self) -> OperationStats {
+            OperationStats {
+                count: self.count as usize,
+                ..OperationStats::default()
+            }
+        }
+    }
+
+    #[cfg(feature = "testing")]
+    static TEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    #[cfg(feature = "testing")]
+    #[must_use = "the collector is only reserved while the guard is alive"]
+    pub struct TestGuard {
+        _lock: std::sync::MutexGuard<'static, ()>,
+    }
+
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct SelfStats {
+        pub records: u64,
+        pub total: Duration,
+    }
+
+    impl SelfStats {
+        pub fn mean(&self) -> Duration {
+            Duration::ZERO
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct ComparisonResult {
+        pub name_a: String,
+        pub stats_a: OperationStats,
+        pub name_b: String,
+        pub stats_b: OperationStats,
+    }
+
+    impl ComparisonResult {
+        pub fn speedup(&self) -> f64 {
+            1.0
+        }
+        pub fn faster(&self) -> &str {
+            &self.name_a
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct SlowEvent {
+        pub key: String,
+        pub micros: u64,
+        pub note: String,
+        pub timestamp: std::time::SystemTime,
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    pub struct KeyValidation {
+        pub duplicates: Vec<String>,
+        pub unknown: Vec<String>,
+    }
+
+    impl KeyValidation {
+        pub fn is_ok(&self) -> bool {
+            true
+        }
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    pub enum TimeFormat {
+        Nanoseconds,
+        Microseconds,
+        Milliseconds,
+        Seconds,
+        Auto,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum SortMetric {
+        Count,
+        TotalTime,
+        MeanTime,
+        MaxTime,
+        P50,
+        P95,
+        P99,
+        P999,
+        CallCount,
+    }
+
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub enum SplitMode {
+        #[default]
+        First,
+        Last,
+    }
+
+    impl SplitMode {
+        pub fn split<'a>(&self, key: &'a str) -> Option<(&'a str, &'a str)> {
+            match self {
+                SplitMode::First => key.split_once("::"),
+                SplitMode::Last => key.rsplit_once("::"),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Percentile {
+        P50,
+        P95,
+        P99,
+        P999,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct ReportConfig {
+        pub include_percentiles: bool,
+        pub group_by_category: bool,
+        pub time_format: TimeFormat,
+        pub time_precision: Option<u8>,
+        pub sort_by: SortMetric,
+        pub sort_by_time: bool,
+        pub min_samples: u64,
+        pub only_category: Option<String>,
+        pub include_ewma: bool,
+        pub include_max_time_at: bool,
+        pub include_baseline: bool,
+        pub baseline_tolerance: f64,
+        pub title: Option<String>,
+        pub use_color: Option<bool>,
+        pub category_priority_overrides: HashMap<String, i32>,
+        pub respect_operation_order: bool,
+        pub include_legend: bool,
+        pub include_empty_categories: bool,
+        pub split_mode: SplitMode,
+    }
+
+    impl Default for ReportConfig {
+        fn default() -> Self {
+            Self {
+                include_percentiles: false,
+                group_by_category: false,
+                time_format: TimeFormat::Auto,
+                time_precision: None,
+                sort_by: SortMetric::TotalTime,
+                sort_by_time: false,
+                min_samples: 0,
+                only_category: None,
+                include_ewma: false,
+                include_max_time_at: false,
+                include_baseline: false,
+                baseline_tolerance: 1.0,
+                title: None,
+                use_color: None,
+                category_priority_overrides: HashMap::new(),
+                respect_operation_order: false,
+                include_legend: false,
+                include_empty_categories: false,
+                split_mode: SplitMode::First,
+            }
+        }
+    }
+
+    pub struct ProfileReport {
+        pub stats: HashMap<String, OperationStats>,
+        pub config: ReportConfig,
+    }
+
+    impl ProfileReport {
+        pub fn generate() -> Self {
+            Self::generate_with_config(ReportConfig::default())
+        }
+
+        pub fn generate_with_config(_config: ReportConfig) -> Self {
+            warn_stub_report();
+            Self {
+                stats: HashMap::new(),
+                config: ReportConfig::default(),
+            }
+        }
+
+        pub fn for_category(_category_name: &str) -> Self {
+            Self::generate()
+        }
+
+        pub fn with_baseline(self, _baseline: HashMap<String, OperationStats>) -> Self {
+            self
+        }
+
+        pub fn change_vs_baseline(&self, _key: &str) -> Option<f64> {
+            None
+        }
+
+        #[cfg(feature = "serde")]
+        pub fn load_baseline(
+            _path: impl AsRef<std::path::Path>,
+        ) -> std::io::Result<HashMap<String, OperationStats>> {
+            Ok(HashMap::new())
+        }
+
+        #[cfg(feature = "serde")]
+        pub fn save_baseline(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+            std::fs::write(path, "{}")
+        }
+
+        #[cfg(feature = "serde")]
+        pub fn compare_to_baseline_file(
+            &self,
+            _path: impl AsRef<std::path::Path>,
+            _tolerance: f64,
+        ) -> std::io::Result<BaselineComparison> {
+            Ok(BaselineComparison::default())
+        }
+
+        pub fn quick_summary(&self) -> String {
+            String::new()
+        }
+
+        pub fn summary_stats(&self) -> SummaryStats {
+            SummaryStats::default()
+        }
+
+        pub fn to_string(&self) -> String {
+            String::new()
+        }
+
+        pub fn top_operations_by(
+            &self,
+            _metric: SortMetric,
+            _limit: usize,
+        ) -> Vec<(String, OperationStats)> {
+            Vec::new()
+        }
+
+        pub fn exceeds_baseline(&self, _key: &str) -> Option<bool> {
+            None
+        }
+
+        pub fn over_baseline(&self) -> Vec<String> {
+            Vec::new()
+        }
+
+        pub fn operation_detail(&self, _key: &str) -> Option<String> {
+            None
+        }
+
+        pub fn grouped_by(
+            &self,
+            _f: impl Fn(&str) -> String,
+        ) -> HashMap<String, Vec<(String, OperationStats)>> {
+            HashMap::new()
+        }
+    }
+
+    impl std::fmt::Debug for ProfileReport {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "")
+        }
+    }
+
+    pub struct ReportGuard {
+        _phantom: std::marker::PhantomData<()>,
+    }
+
+    impl ReportGuard {
+        pub fn new(_config: ReportConfig) -> Self {
+            Self {
+                _phantom: std::marker::PhantomData,
+            }
+        }
+
+        pub fn use_stderr(self) -> Self {
+            self
+        }
+
+        pub fn render(&self) -> String {
+            String::new()
+        }
+    }
+
+    impl Drop for ReportGuard {
+        fn drop(&mut self) {
+            // No-op in stub mode
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct ReportBuilder {
+        config: ReportConfig,
+    }
+
+    impl Default for ReportBuilder {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl ReportBuilder {
+        pub fn new() -> Self {
+            Self {
+                config: ReportConfig::default(),
+            }
+        }
+
+        pub fn config(&self) -> &ReportConfig {
+            &self.config
+        }
+
+        pub fn group_by_category(self, _enabled: bool) -> Self {
+            self
+        }
+        pub fn include_percentiles(self, _enabled: bool) -> Self {
+            self
+        }
+        pub fn time_format(self, _format: TimeFormat) -> Self {
+            self
+        }
+        pub fn time_precision(self, _decimals: u8) -> Self {
+            self
+        }
+        pub fn sort_by_time(self, _enabled: bool) -> Self {
+            self
+        }
+        pub fn sort_by_percentile(self, _percentile: Percentile) -> Self {
+            self
+        }
+        pub fn min_samples(self, _min: u64) -> Self {
+            self
+        }
+        pub fn include_summary(self, _include: bool) -> Self {
+            self
+        }
+        pub fn max_operations(self, _max: usize) -> Self {
+            self
+        }
+        pub fn include_empty(self, _include: bool) -> Self {
+            self
+        }
+        pub fn only_category(self, _category_name: impl Into<String>) -> Self {
+            self
+        }
+        pub fn include_ewma(self, _include: bool) -> Self {
+            self
+        }
+        pub fn include_max_time_at(self, _include: bool) -> Self {
+            self
+        }
+        pub fn respect_operation_order(self, _respect: bool) -> Self {
+            self
+        }
+        pub fn include_legend(self, _include: bool) -> Self {
+            self
+        }
+        pub fn include_empty_categories(self, _include: bool) -> Self {
+            self
+        }
+        pub fn split_mode(self, _mode: SplitMode) -> Self {
+            self
+        }
+        pub fn include_baseline(self, _include: bool) -> Self {
+            self
+        }
+        pub fn baseline_tolerance(self, _factor: f64) -> Self {
+            self
+        }
+        pub fn title(self, _title: impl Into<String>) -> Self {
+            self
+        }
+        pub fn use_color(self, _enabled: bool) -> Self {
+            self
+        }
+        pub fn category_priority(self, _category_name: impl Into<String>, _priority: i32) -> Self {
+            self
+        }
+        pub fn build(self) -> ProfileReport {
+            ProfileReport::generate()
+        }
+    }
+}
+
+#[cfg(not(feature = "full"))]
+pub mod metrics {
+    use crate::category::Category;
+
+    #[derive(Debug, Clone)]
+    pub struct MetricDefinition<C: Category> {
+        pub id: String,
+        pub name: String,
+        pub description: Option<String>,
+        pub category: C,
+        pub unit: Option<String>,
+        pub tags: Vec<String>,
+    }
+
+    impl<C: Category> MetricDefinition<C> {
+        pub fn new(id: impl Into<String>, name: impl Into<String>, category: C) -> Self {
+            Self {
+                id: id.into(),
+                name: name.into(),
+                description: None,
+                category,
+                unit: None,
+                tags: Vec::new(),
+            }
+        }
+        pub fn with_description(self, _description: impl Into<String>) -> Self {
+            self
+        }
+        pub fn with_unit(self, _unit: impl Into<String>) -> Self {
+            self
+        }
+        pub fn with_tag(self, _tag: impl Into<String>) -> Self {
+            self
+        }
+        pub fn with_tags(self, _tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+            self
+        }
+    }
+
+    pub struct MetricRegistry<C: Category> {
+        _category: std::marker::PhantomData<C>,
+    }
+
+    impl<C: Category> MetricRegistry<C> {
+        pub fn new() -> Self {
+            Self {
+                _category: std::marker::PhantomData,
+            }
+        }
+        pub fn register(&self, _metric: MetricDefinition<C>) -> Result<(), MetricRegistryError> {
+            Ok(())
+        }
+        pub fn register_all(
+            &self,
+            _metrics: impl IntoIterator<Item = MetricDefinition<C>>,
+        ) -> Result<(), MetricRegistryError> {
+            Ok(())
+        }
+        pub fn ids(&self) -> Vec<String> {
+            Vec::new()
+        }
+        pub fn unregister(&self, _id: &str) -> Option<MetricDefinition<C>> {
+            None
+        }
+        pub fn clear(&self) {}
+        pub fn len(&self) -> usize {
+            0
+        }
+        pub fn is_empty(&self) -> bool {
+            true
+        }
+    }
+
+    impl<C: Category + Clone> MetricRegistry<C> {
+        pub fn get(&self, _id: &str) -> Option<MetricDefinition<C>> {
+            None
+        }
+        pub fn get_by_category(&self, _category: &C) -> Vec<MetricDefinition<C>>
+        where
+            C: PartialEq,
+        {
+            Vec::new()
+        }
+        pub fn get_by_tag(&self, _tag: &str) -> Vec<MetricDefinition<C>> {
+            Vec::new()
+        }
+        pub fn all(&self) -> Vec<MetricDefinition<C>> {
+            Vec::new()
+        }
+    }
+
+    impl<C: Category> Default for MetricRegistry<C> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<C: Category> Clone for MetricRegistry<C> {
+        fn clone(&self) -> Self {
+            Self::new()
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum MetricRegistryError {
+        DuplicateMetric(String),
+        LockError,
+    }
+
+    impl std::fmt::Display for MetricRegistryError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                MetricRegistryError::DuplicateMetric(id) => {
+                    write!(f, "Metric with ID '{}' already exists", id)
+                }
+                MetricRegistryError::LockError => {
+                    write!(f, "Failed to acquire lock on metric registry")
+                }
+            }
+        }
+    }
+
+    impl std::error::Error for MetricRegistryError {}
+
+    pub struct MetricSetBuilder<C: Category> {
+        _category: std::marker::PhantomData<C>,
+    }
+
+    impl<C: Category> MetricSetBuilder<C> {
+        pub fn new() -> Self {
+            Self {
+                _category: std::marker::PhantomData,
+            }
+        }
+        #[allow(clippy::should_implement_trait)]
+        pub fn add(self, _metric: MetricDefinition<C>) -> Self {
+            self
+        }
+        pub fn metric(
+            self,
+            _id: impl Into<String>,
+            _name: impl Into<String>,
+            _category: C,
+        ) -> Self {
+            self
+        }
+        pub fn metric_full(
+            self,
+            _id: impl Into<String>,
+            _name: impl Into<String>,
+            _category: C,
+            _description: impl Into<String>,
+            _unit: impl Into<String>,
+        ) -> Self {
+            self
+        }
+        pub fn build(self) -> Vec<MetricDefinition<C>> {
+            Vec::new()
+        }
+        pub fn register_to(self, _registry: &MetricRegistry<C>) -> Result<(), MetricRegistryError> {
+            Ok(())
+        }
+    }
+
+    impl<C: Category> Default for MetricSetBuilder<C> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+}
+
+#[cfg(not(feature = "full"))]
+pub mod store {
+    use crate::collector::OperationStats;
+    use std::collections::HashMap;
+
+    pub trait StatsStore: Send + Sync {
+        fn record(
+            &self,
+            key: &str,
+            create: &mut dyn FnMut() -> OperationStats,
+            update: &mut dyn FnMut(&mut OperationStats),
+        ) -> bool;
+        fn update(&self, key: &str, update: &mut dyn FnMut(&mut OperationStats)) -> bool;
+        fn get(&self, key: &str) -> Option<OperationStats>;
+        fn contains(&self, key: &str) -> bool {
+            self.get(key).is_some()
+        }
+        fn remove(&self, key: &str) -> Option<OperationStats>;
+        fn for_each(&self, visit: &mut dyn FnMut(&str, &OperationStats));
+        fn clear(&self);
+        fn len(&self) -> usize {
+            0
+        }
+        fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+        fn lock_contention_count(&self) -> u64 {
+            0
+        }
+        fn keys(&self) -> Vec<String> {
+            Vec::new()
+        }
+        fn all(&self) -> HashMap<String, OperationStats> {
+            HashMap::new()
+        }
+    }
+
+    macro_rules! stub_store {
+        ($name:ident) => {
+            #[derive(Debug, Default)]
+            pub struct $name;
+
+            impl StatsStore for $name {
+                fn record(
+                    &self,
+                    _key: &str,
+                    _create: &mut dyn FnMut() -> OperationStats,
+                    _update: &mut dyn FnMut(&mut OperationStats),
+                ) -> bool {
+                    false
+                }
+                fn update(&self, _key: &str, _update: &mut dyn FnMut(&mut OperationStats)) -> bool {
+                    false
+                }
+                fn get(&self, _key: &str) -> Option<OperationStats> {
+                    None
+                }
+                fn remove(&self, _key: &str) -> Option<OperationStats> {
+                    None
+                }
+                fn for_each(&self, _visit: &mut dyn FnMut(&str, &OperationStats)) {}
+                fn clear(&self) {}
+            }
+        };
+    }
+
+    stub_store!(HashMapStore);
+    #[cfg(feature = "dashmap")]
+    stub_store!(DashMapStore);
+}
+
+#[cfg(not(feature = "full"))]
+pub mod timer {
+    use crate::operation::Operation;
+
+    #[doc(hidden)]
+    #[derive(Debug)]
+    pub struct LoopIteration<'a> {
+        pub operation: &'a dyn Operation,
+        pub index: usize,
+    }
+
+    impl Operation for LoopIteration<'_> {}
+
+    pub struct ProfileTimer<'a> {
+        _operation: &'a dyn Operation,
+    }
+
+    impl<'a> ProfileTimer<'a> {
+        pub fn new(operation: &'a dyn Operation) -> Self {
+            Self {
+                _operation: operation,
+            }
+        }
+
+        pub fn new_in(
+            operation: &'a dyn Operation,
+            _store: &'a dyn crate::store::StatsStore,
+        ) -> Self {
+            Self {
+                _operation: operation,
+            }
+        }
+
+        pub fn segment(operation: &'a dyn Operation) -> Self {
+            Self {
+                _operation: operation,
+            }
+        }
+    }
+
+    impl<'a> ProfileTimer<'a> {
+        pub fn finish<T>(self, value: T) -> Profiled<T> {
+            Profiled {
+                value,
+                duration: std::time::Duration::ZERO,
+                key: String::new(),
+                recorded: false,
+            }
+        }
+    }
+
+    impl<'a> Drop for ProfileTimer<'a> {
+        fn drop(&mut self) {
+            // No-op in stub mode
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct Profiled<T> {
+        pub value: T,
+        pub duration: std::time::Duration,
+        pub key: String,
+        pub recorded: bool,
+    }
+
+    #[derive(Debug)]
+    #[must_use]
+    pub struct TimerToken;
+
+    impl TimerToken {
+        pub(crate) fn start(_operation: &dyn Operation) -> Self {
+            TimerToken
+        }
+
+        pub fn key(&self) -> &str {
+            ""
+        }
+
+        pub fn elapsed(&self) -> std::time::Duration {
+            std::time::Duration::ZERO
+        }
+    }
+
+    #[derive(Clone)]
+    pub struct ProfileTimerAsync<'a> {
+        _operation: &'a dyn Operation,
+    }
+
+    impl<'a> ProfileTimerAsync<'a> {
+        pub fn new(operation: &'a dyn Operation) -> Self {
+            Self {
+                _operation: operation,
+            }
+        }
+
+        pub fn new_in(
+            operation: &'a dyn Operation,
+            _store: &'a dyn crate::store::StatsStore,
+        ) -> Self {
+            Self {
+                _operation: operation,
+            }
+        }
+
+        pub fn checkpoint(&self, _suffix: &str) {}
+
+        pub async fn run<F, R>(self, fut: F) -> R
+        where
+            F: std::future::Future<Output = R>,
+        {
+            // In stub mode, just execute the future
+            fut.await
+        }
+    }
+
+    pub struct PausableTimer<'a> {
+        _operation: &'a dyn Operation,
+    }
+
+    impl<'a> PausableTimer<'a> {
+        pub fn new(operation: &'a dyn Operation) -> Self {
+            Self {
+                _operation: operation,
+            }
+        }
+
+        pub fn new_in(
+            operation: &'a dyn Operation,
+            _store: &'a dyn crate::store::StatsStore,
+        ) -> Self {
+            Self {
+                _operation: operation,
+            }
+        }
+
+        pub fn new_paused(operation: &'a dyn Operation) -> Self {
+            Self {
+                _operation: operation,
+            }
+        }
+
+        pub fn pause(&mut self) {}
+
+        pub fn resume(&mut self) {}
+
+        pub fn total_elapsed(&self) -> std::time::Duration {
+            std::time::Duration::ZERO
+        }
+
+        pub fn total_elapsed_micros(&self) -> u64 {
+            0
+        }
+
+        pub fn total_elapsed_millis(&self) -> u64 {
+            0
+        }
+
+        pub fn is_running(&self) -> bool {
+            false
+        }
+
+        pub fn state(&self) -> TimerState {
+            TimerState::Paused
+        }
+
+        pub fn operation(&self) -> &dyn Operation {
+            self._operation
         }
 
         pub fn record(&mut self) {}
 
-        pub fn stop(self) -> std::time::Duration {
-            std::time::Duration::ZERO
+        pub fn stop(self) -> std::time::Duration {
+            std::time::Duration::ZERO
+        }
+
+        pub fn stop_and_record(self) -> std::time::Duration {
+            std::time::Duration::ZERO
+        }
+
+        pub fn reset(&mut self) {}
+
+        pub fn reset_paused(&mut self) {}
+
+        pub fn finish<T>(self, value: T) -> Profiled<T> {
+            Profiled {
+                value,
+                duration: std::time::Duration::ZERO,
+                key: String::new(),
+                recorded: false,
+            }
+        }
+    }
+
+    impl<'a> Drop for PausableTimer<'a> {
+        fn drop(&mut self) {}
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum TimerState {
+        Running,
+        Paused,
+        Recorded,
+    }
+
+    /// Pause all timers currently on the call stack for this thread (stub)
+    pub fn pause_stack() {}
+
+    /// Resume all timers that were paused by pause_stack on this thread (stub)
+    pub fn unpause_stack() {}
+}
+
+// Re-export the appropriate implementations based on feature flags
+#[doc(inline)]
+pub use category::{Category, CategoryInfo, NoCategory};
+#[cfg(feature = "testing")]
+#[doc(inline)]
+pub use collector::TestGuard;
+#[doc(inline)]
+pub use collector::{
+    ComparisonResult, KeyValidation, OperationStats, OperationStatsRow, ProfileCollector,
+    SelfStats, SlowEvent, SummaryStats, OVERFLOW_KEY,
+};
+#[doc(inline)]
+pub use metrics::{MetricDefinition, MetricRegistry, MetricRegistryError, MetricSetBuilder};
+#[doc(inline)]
+pub use operation::Operation;
+#[doc(inline)]
+pub use quantile::P2Quantile;
+#[cfg(feature = "dashmap")]
+#[doc(inline)]
+pub use store::DashMapStore;
+#[doc(inline)]
+pub use store::{HashMapStore, StatsStore};
+#[doc(inline)]
+pub use timer::{PausableTimer, ProfileTimer, ProfileTimerAsync, Profiled, TimerState, TimerToken};
+
+// Re-export stack-based pause/unpause functions
+#[cfg(feature = "full")]
+#[doc(inline)]
+pub use timer::{pause_stack, unpause_stack};
+
+#[cfg(not(feature = "full"))]
+#[doc(inline)]
+pub use timer::{pause_stack, unpause_stack};
+
+// Re-export reporter functionality when full feature is enabled
+#[cfg(feature = "full")]
+#[doc(inline)]
+pub use category::DefaultCategory;
+#[cfg(feature = "full")]
+#[doc(inline)]
+pub use reporter::{
+    BaselineComparison, Percentile, ProfileReport, ReportBuilder, ReportConfig, ReportGuard,
+    SortMetric, SplitMode, TimeFormat,
+};
+
+#[cfg(not(feature = "full"))]
+#[doc(inline)]
+pub use collector::{
+    BaselineComparison, Percentile, ProfileReport, ReportBuilder, ReportConfig, ReportGuard,
+    SortMetric, SplitMode, TimeFormat,
+};
+
+/// Whether the `full` feature, and with it real profiling, is compiled in
+///
+/// Being a `const fn`, this can gate work at compile time. Use
+/// [`ProfileCollector::is_active`] to also account for runtime pausing.
+pub const fn is_full_enabled() -> bool {
+    cfg!(feature = "full")
+}
+
+// Re-export the Operation derive macro (always available)
+// Note: External crate, so not using #[doc(inline)] per guidelines
+pub use quantum_pulse_macros::Operation as ProfileOp;
+
+// Re-export the function profiling attribute (always available)
+pub use quantum_pulse_macros::profiled;
+
+/// Profile a code block using RAII timer
+///
+/// This macro creates a RAII timer that automatically records the duration
+/// when it goes out of scope. It takes an Operation and either a code block
+/// or a single expression, and returns its value.
+///
+/// If the block panics, the timer still records while the stack unwinds, so
+/// failed operations show up in reports alongside successful ones.
+///
+/// # Example
+/// ```rust,no_run
+/// use quantum_pulse::{profile, Category, Operation};
+/// use std::fmt::Debug;
+///
+/// // Define your own operation type
+/// #[derive(Debug)]
+/// enum AppOperation {
+///     DatabaseQuery,
+/// }
+///
+/// impl Operation for AppOperation {}
+///
+/// # fn run_query() -> u32 { 42 }
+/// let op = AppOperation::DatabaseQuery;
+/// let result = profile!(op, {
+///     42 // Your code here
+/// });
+///
+/// // A bare expression works too
+/// let result = profile!(AppOperation::DatabaseQuery, run_query());
+/// ```
+#[macro_export]
+macro_rules! profile {
+    ($operation:expr, $code:block) => {{
+        let _timer = $crate::ProfileTimer::new(&$operation);
+        $code
+    }};
+    ($operation:expr, $code:expr) => {{
+        let _timer = $crate::ProfileTimer::new(&$operation);
+        $code
+    }};
+}
+
+/// Define unit structs implementing [`Category`] in one block
+///
+/// Each entry is `Name => "description"`, optionally followed by
+/// `@ "#RRGGBB"` for the color hint and `prio N` for the priority. The
+/// struct's name doubles as the category name. Doc comments and other
+/// attributes before an entry are applied to its struct, and a visibility
+/// may precede the name.
+///
+/// # Example
+/// ```rust
+/// use quantum_pulse::{define_categories, Category};
+///
+/// define_categories! {
+///     /// All database operations
+///     pub Database => "All DB ops" @ "#FF0000" prio 1,
+///     Cache => "Cache lookups" prio 2,
+///     Logging => "Log output",
+/// }
+///
+/// assert_eq!(Database.get_name(), "Database");
+/// assert_eq!(Database.color_hint(), Some("#FF0000"));
+/// assert_eq!(Cache.priority(), 2);
+/// assert_eq!(Logging.color_hint(), None);
+/// ```
+#[macro_export]
+macro_rules! define_categories {
+    ($(
+        $(#[$meta:meta])*
+        $vis:vis $name:ident => $description:literal
+        $(@ $color:literal)?
+        $(prio $priority:literal)?
+    ),* $(,)?) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+            $vis struct $name;
+
+            impl $crate::Category for $name {
+                fn get_name(&self) -> &str {
+                    stringify!($name)
+                }
+
+                fn get_description(&self) -> &str {
+                    $description
+                }
+
+                $(
+                    fn color_hint(&self) -> Option<&str> {
+                        Some($color)
+                    }
+                )?
+
+                $(
+                    fn priority(&self) -> i32 {
+                        $priority
+                    }
+                )?
+            }
+        )*
+    };
+}
+
+/// Profile a code block only when a `cfg` predicate holds
+///
+/// Works like [`profile!`], but the predicate is evaluated with `cfg!` in the
+/// calling crate. When it is false no timer is created and the block runs
+/// unprofiled, giving per-call-site control on top of the crate-wide `full`
+/// feature.
+///
+/// # Example
+/// ```rust
+/// use quantum_pulse::{profile_cfg, Operation};
+///
+/// #[derive(Debug)]
+/// enum AppOperation {
+///     HotLoop,
+/// }
+///
+/// impl Operation for AppOperation {}
+///
+/// // Only profiled in debug builds
+/// let result = profile_cfg!(debug_assertions, AppOperation::HotLoop, {
+///     42 // Your code here
+/// });
+/// assert_eq!(result, 42);
+/// ```
+#[macro_export]
+macro_rules! profile_cfg {
+    ($cfg:meta, $operation:expr, $code:block) => {{
+        let _timer = cfg!($cfg).then(|| $crate::ProfileTimer::new(&$operation));
+        $code
+    }};
+    ($cfg:meta, $operation:expr, $code:expr) => {{
+        let _timer = cfg!($cfg).then(|| $crate::ProfileTimer::new(&$operation));
+        $code
+    }};
+}
+
+/// Profile a segment of an enclosing timer's block under its own operation
+///
+/// Works like [`profile!`], but when exclusive time is enabled with
+/// [`ProfileCollector::set_exclusive_time`], the segment's duration is
+/// subtracted from the timer it is nested in. This gives the enclosing
+/// operation its self time without splitting its block into several timers.
+///
+/// # Example
+/// ```rust
+/// use quantum_pulse::{profile, segment, Operation, ProfileCollector};
+///
+/// #[derive(Debug)]
+/// enum AppOperation {
+///     HandleRequest,
+///     Serialize,
+/// }
+///
+/// impl Operation for AppOperation {}
+///
+/// ProfileCollector::set_exclusive_time(true);
+///
+/// profile!(AppOperation::HandleRequest, {
+///     // Counted toward HandleRequest
+///     let body = segment!(AppOperation::Serialize, {
+///         // Counted toward Serialize only
+///         "{}".to_string()
+///     });
+/// });
+/// ```
+#[macro_export]
+macro_rules! segment {
+    ($operation:expr, $code:block) => {{
+        let _timer = $crate::ProfileTimer::segment(&$operation);
+        $code
+    }};
+}
+
+/// Profile every iteration of a loop as a separate sample
+///
+/// Runs the body once per item of the iterator, binding the item like a
+/// closure argument, and times each run under the operation, so its count
+/// grows by the number of iterations. Prefix the operation with `bucketed`
+/// to instead record iteration `i` (counting from 0) under
+/// `{operation}::iter_{i}`, which shows whether later iterations slow down.
+/// Each bucket is a separate key with its own histogram, so keep bucketed
+/// loops short.
+///
+/// # Example
+/// ```rust
+/// use quantum_pulse::{profile_loop, Operation};
+///
+/// #[derive(Debug)]
+/// enum AppOperation {
+///     ProcessBatch,
+/// }
+///
+/// impl Operation for AppOperation {}
+///
+/// let mut total = 0;
+/// profile_loop!(AppOperation::ProcessBatch, 0..10, |i| {
+///     total += i;
+/// });
+///
+/// // Recorded under "::ProcessBatch::iter_0" to "::ProcessBatch::iter_2"
+/// profile_loop!(bucketed AppOperation::ProcessBatch, ["a", "b", "c"], |item| {
+///     let _ = item.len();
+/// });
+/// ```
+#[macro_export]
+macro_rules! profile_loop {
+    (bucketed $operation:expr, $iter:expr, |$item:pat_param| $body:block) => {
+        for (index, $item) in ::core::iter::IntoIterator::into_iter($iter).enumerate() {
+            let iteration = $crate::timer::LoopIteration {
+                operation: &$operation,
+                index,
+            };
+            let _timer = $crate::ProfileTimer::new(&iteration);
+            $body
         }
-
-        pub fn stop_and_record(self) -> std::time::Duration {
-            std::time::Duration::ZERO
+    };
+    ($operation:expr, $iter:expr, |$item:pat_param| $body:block) => {
+        for $item in $iter {
+            let _timer = $crate::ProfileTimer::new(&$operation);
+            $body
         }
-
-        pub fn reset(&mut self) {}
-
-        pub fn reset_paused(&mut self) {}
-    }
-
-    impl<'a> Drop for PausableTimer<'a> {
-        fn drop(&mut self) {}
-    }
-
-    /// Pause all timers currently on the call stack for this thread (stub)
-    pub fn pause_stack() {}
-
-    /// Resume all timers that were paused by pause_stack on this thread (stub)
-    pub fn unpause_stack() {}
+    };
 }
 
-// Re-export the appropriate implementations based on feature flags
-#[doc(inline)]
-pub use category::{Category, NoCategory};
-#[doc(inline)]
-pub use collector::{OperationStats, ProfileCollector, SummaryStats};
-#[doc(inline)]
-pub use operation::Operation;
-#[doc(inline)]
-pub use timer::{PausableTimer, ProfileTimer, ProfileTimerAsync};
-
-// Re-export stack-based pause/unpause functions
-#[cfg(feature = "full")]
-#[doc(inline)]
-pub use timer::{pause_stack, unpause_stack};
-
-#[cfg(not(feature = "full"))]
-#[doc(inline)]
-pub use timer::{pause_stack, unpause_stack};
-
-// Re-export reporter functionality when full feature is enabled
-#[cfg(feature = "full")]
-#[doc(inline)]
-pub use category::DefaultCategory;
-#[cfg(feature = "full")]
-#[doc(inline)]
-pub use reporter::{
-    Percentile, ProfileReport, ReportBuilder, ReportConfig, SortMetric, TimeFormat,
-};
-
-// Re-export the Operation derive macro (always available)
-// Note: External crate, so not using #[doc(inline)] per guidelines
-pub use quantum_pulse_macros::Operation as ProfileOp;
-
-/// Profile a code block using RAII timer
+/// Profile a code block and return its value together with timing metadata
 ///
-/// This macro creates a RAII timer that automatically records the duration
-/// when it goes out of scope. It takes an Operation and a code block.
+/// Works like [`profile!`] and records on the normal path, but instead of the
+/// bare value it returns a [`Profiled`] bundling the value, the measured
+/// duration, the operation key, and whether the measurement was recorded
+/// (it is not while profiling is paused). Use it when the call site wants to
+/// log timing details without querying the collector afterwards.
 ///
 /// # Example
-/// ```rust,no_run
-/// use quantum_pulse::{profile, Category, Operation};
-/// use std::fmt::Debug;
+/// ```rust
+/// use quantum_pulse::{profile_detailed, Operation};
 ///
-/// // Define your own operation type
 /// #[derive(Debug)]
 /// enum AppOperation {
 ///     DatabaseQuery,
@@ -479,16 +1869,18 @@ pub use quantum_pulse_macros::Operation as ProfileOp;
 ///
 /// impl Operation for AppOperation {}
 ///
-/// let op = AppOperation::DatabaseQuery;
-/// let result = profile!(op, {
+/// let p = profile_detailed!(AppOperation::DatabaseQuery, {
 ///     42 // Your code here
 /// });
+/// assert_eq!(p.value, 42);
+/// println!("{} took {:?} (recorded: {})", p.key, p.duration, p.recorded);
 /// ```
 #[macro_export]
-macro_rules! profile {
+macro_rules! profile_detailed {
     ($operation:expr, $code:block) => {{
-        let _timer = $crate::ProfileTimer::new(&$operation);
-        $code
+        let timer = $crate::ProfileTimer::new(&$operation);
+        let value = $code;
+        timer.finish(value)
     }};
 }
 
@@ -499,7 +1891,11 @@ macro_rules! profile {
 ///
 /// # Important
 /// This macro returns a `Future` that **must be awaited** to have any effect.
-/// If you don't await the result, the profiling will not happen and you'll get a compiler warning.
+/// If you don't await the result, neither the block nor the profiling will run,
+/// and the only signal is an `unused_must_use` warning. Writing
+/// `profile_async!(op, async { ... });` in statement position is the classic
+/// mistake. Consider `#![deny(unused_must_use)]` to turn it into an error, or use
+/// [`profile_async_block!`] which awaits in place and cannot be forgotten.
 ///
 /// # Example
 /// ```rust,no_run
@@ -532,6 +1928,66 @@ macro_rules! profile_async {
     };
 }
 
+/// Count an occurrence of an operation without timing it
+///
+/// Unlike `profile!()`, no clock is read: only the operation's call count is
+/// increased. Counted and timed operations can share the same collector.
+///
+/// # Example
+/// ```rust
+/// use quantum_pulse::{count, Operation};
+/// use std::fmt::Debug;
+///
+/// #[derive(Debug)]
+/// enum AppOperation {
+///     CacheMiss,
+/// }
+///
+/// impl Operation for AppOperation {}
+///
+/// count!(AppOperation::CacheMiss);
+/// ```
+#[macro_export]
+macro_rules! count {
+    ($operation:expr) => {
+        $crate::ProfileCollector::increment_operation(&$operation)
+    };
+}
+
+/// Profile an async block and await it in place
+///
+/// Equivalent to `profile_async!(op, async { ... }).await`, but the `.await`
+/// is part of the expansion, so the block can never be silently skipped by a
+/// forgotten `.await`. Using it outside an async context is a compile error.
+/// Use `profile_async!()` when you need the future itself, e.g. for
+/// `tokio::join!` or spawning.
+///
+/// # Example
+/// ```rust,no_run
+/// use quantum_pulse::{profile_async_block, Operation};
+///
+/// #[derive(Debug)]
+/// enum AppOperation {
+///     AsyncDatabaseQuery,
+/// }
+///
+/// impl Operation for AppOperation {}
+///
+/// # async fn run() {
+/// let result = profile_async_block!(AppOperation::AsyncDatabaseQuery, {
+///     "query result" // database.async_query("SELECT * FROM users").await
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! profile_async_block {
+    ($operation:expr, $code:block) => {
+        $crate::ProfileTimerAsync::new(&$operation)
+            .run(async $code)
+            .await
+    };
+}
+
 /// Create a scoped timer that records on drop
 ///
 /// This is a convenience macro for creating a timer that automatically
@@ -664,6 +2120,11 @@ macro_rules! unpause {
 /// This is useful when you want to exclude specific nested operations from
 /// profiling without affecting other concurrent operations.
 ///
+/// The timer stack is thread-local by default, which does not work for
+/// `profile_async!()`: a future on a multi-threaded runtime may resume on a
+/// different thread after an `.await`. Enable the `tokio` feature to keep the
+/// stack in a task-local instead, so pausing follows the task across threads.
+///
 /// # Example
 /// ```rust
 /// use quantum_pulse::{profile, pause_stack, unpause_stack, Operation};
@@ -747,6 +2208,41 @@ macro_rules! unpause_stack {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_is_full_enabled() {
+        assert_eq!(is_full_enabled(), cfg!(feature = "full"));
+        if !is_full_enabled() {
+            assert!(!ProfileCollector::is_active());
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "full"))]
+    fn test_stub_percentiles_are_estimated() {
+        let mut stats = OperationStats::default();
+        for i in 0..1000u64 {
+            stats.record(std::time::Duration::from_micros((i * 7919) % 1000 + 1));
+        }
+        assert_eq!(stats.count, 1000);
+        assert!(stats.p50_micros().abs_diff(500) <= 25);
+        assert!(stats.p99_micros().abs_diff(990) <= 25);
+    }
+
+    #[test]
+    #[cfg(not(feature = "full"))]
+    fn test_stub_report_warns_once() {
+        use std::sync::atomic::Ordering;
+
+        let _ = ProfileReport::generate();
+        let _ = ReportBuilder::new().build();
+
+        let silenced = std::env::var_os("QUANTUM_PULSE_SILENCE_STUB_WARNING").is_some();
+        assert_eq!(
+            collector::STUB_WARNING_SHOWN.load(Ordering::Relaxed),
+            !silenced
+        );
+    }
+
     #[test]
     #[cfg(feature = "full")]
     fn test_basic_profiling() {
@@ -864,6 +2360,258 @@ mod tests {
         assert!(ProfileCollector::get_stats("::macro_test").is_some());
     }
 
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_profile_async_block_macro() {
+        #[derive(Debug)]
+        enum TestOperation {
+            AsyncBlock,
+        }
+
+        impl Operation for TestOperation {
+            fn to_str(&self) -> String {
+                "async_block".to_string()
+            }
+        }
+
+        ProfileCollector::clear_all();
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let value = runtime.block_on(async {
+            profile_async_block!(TestOperation::AsyncBlock, { std::future::ready(7).await })
+        });
+
+        assert_eq!(value, 7);
+        assert_eq!(
+            ProfileCollector::get_stats("::async_block").unwrap().count,
+            1
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_profile_macro_block_and_expression_forms() {
+        #[derive(Debug)]
+        enum TestOperation {
+            Form,
+        }
+
+        impl Operation for TestOperation {
+            fn to_str(&self) -> String {
+                "form_test".to_string()
+            }
+        }
+
+        fn compute(x: u32) -> u32 {
+            x * 2
+        }
+
+        ProfileCollector::clear_all();
+
+        let from_block = profile!(TestOperation::Form, {
+            let x = 20;
+            compute(x)
+        });
+        assert_eq!(from_block, 40);
+
+        let from_expr = profile!(TestOperation::Form, compute(21));
+        assert_eq!(from_expr, 42);
+
+        // A user binding named like the macro's timer is not captured
+        let _timer = 5;
+        let shadowed = profile!(TestOperation::Form, _timer + 1);
+        assert_eq!(shadowed, 6);
+
+        assert_eq!(ProfileCollector::get_stats("::form_test").unwrap().count, 3);
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_count_macro() {
+        #[derive(Debug)]
+        enum TestOperation {
+            Event,
+        }
+
+        impl Operation for TestOperation {
+            fn to_str(&self) -> String {
+                "counted_event".to_string()
+            }
+        }
+
+        ProfileCollector::clear_all();
+
+        count!(TestOperation::Event);
+        count!(TestOperation::Event);
+        profile!(TestOperation::Event, {
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        });
+
+        let stats = ProfileCollector::get_stats("::counted_event").unwrap();
+        assert_eq!(stats.count, 3);
+        assert!(stats.has_timing());
+        assert!(stats.total >= std::time::Duration::from_millis(1));
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_profile_loop_macro() {
+        #[derive(Debug)]
+        enum TestOperation {
+            Step,
+        }
+
+        impl Operation for TestOperation {
+            fn to_str(&self) -> String {
+                "profile_loop_step".to_string()
+            }
+        }
+
+        let mut sum = 0;
+        profile_loop!(TestOperation::Step, 0..5, |i| {
+            sum += i;
+        });
+        assert_eq!(sum, 10);
+        assert_eq!(
+            ProfileCollector::get_stats("::profile_loop_step")
+                .unwrap()
+                .count,
+            5
+        );
+
+        profile_loop!(bucketed TestOperation::Step, ["a", "b", "c"], |item| {
+            assert_eq!(item.len(), 1);
+        });
+        for index in 0..3 {
+            let key = format!("::profile_loop_step::iter_{index}");
+            assert_eq!(ProfileCollector::get_stats(&key).unwrap().count, 1);
+        }
+        assert!(ProfileCollector::get_stats("::profile_loop_step::iter_3").is_none());
+    }
+
+    #[test]
+    fn test_define_categories_macro() {
+        define_categories! {
+            /// Storage access
+            Storage => "All DB ops" @ "#FF0000" prio 1,
+            Queue => "Message queues" @ "#00FF00",
+            pub(crate) Audit => "Audit trail" prio -2,
+            Misc => "Everything else"
+        }
+
+        assert_eq!(Storage.get_name(), "Storage");
+        assert_eq!(Storage.get_description(), "All DB ops");
+        assert_eq!(Storage.color_hint(), Some("#FF0000"));
+        assert_eq!(Storage.priority(), 1);
+        assert_eq!(Queue.color_hint(), Some("#00FF00"));
+        assert_eq!(Queue.priority(), 0);
+        assert_eq!(Audit.color_hint(), None);
+        assert_eq!(Audit.priority(), -2);
+        assert_eq!(Misc.get_name(), "Misc");
+        assert_eq!(Misc.get_description(), "Everything else");
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_profile_cfg_macro() {
+        #[derive(Debug)]
+        enum TestOperation {
+            Enabled,
+            CompiledOut,
+        }
+
+        impl Operation for TestOperation {
+            fn to_str(&self) -> String {
+                format!("profile_cfg_{:?}", self)
+            }
+        }
+
+        let value = profile_cfg!(all(), TestOperation::Enabled, { 1 });
+        assert_eq!(value, 1);
+        assert!(ProfileCollector::contains("::profile_cfg_Enabled"));
+
+        let mut runs = 0;
+        let value = profile_cfg!(any(), TestOperation::CompiledOut, {
+            runs += 1;
+            2
+        });
+        assert_eq!(runs, 1);
+        assert_eq!(value, 2);
+        assert!(!ProfileCollector::contains("::profile_cfg_CompiledOut"));
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_profile_records_when_block_panics() {
+        #[derive(Debug)]
+        struct PanickingOp;
+        impl Operation for PanickingOp {
+            fn to_str(&self) -> String {
+                "profile_panicking_block".to_string()
+            }
+        }
+
+        let result = std::panic::catch_unwind(|| {
+            profile!(PanickingOp, {
+                std::thread::sleep(std::time::Duration::from_millis(1));
+                panic!("fails mid-operation");
+            })
+        });
+        assert!(result.is_err());
+
+        let stats = ProfileCollector::get_stats("::profile_panicking_block").unwrap();
+        assert_eq!(stats.count, 1);
+        assert!(stats.total >= std::time::Duration::from_millis(1));
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_profile_detailed_macro() {
+        #[derive(Debug)]
+        enum TestOperation {
+            DetailedTest,
+        }
+
+        impl Operation for TestOperation {
+            fn to_str(&self) -> String {
+                "detailed_test".to_string()
+            }
+        }
+
+        ProfileCollector::clear_all();
+
+        let p = profile_detailed!(TestOperation::DetailedTest, {
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            7
+        });
+
+        assert_eq!(p.value, 7);
+        assert_eq!(p.key, "::detailed_test");
+        assert!(p.recorded);
+        assert!(p.duration >= std::time::Duration::from_millis(1));
+        assert_eq!(
+            ProfileCollector::get_stats("::detailed_test")
+                .unwrap()
+                .count,
+            1
+        );
+
+        pause!();
+        let paused = profile_detailed!(TestOperation::DetailedTest, { 8 });
+        unpause!();
+
+        assert_eq!(paused.value, 8);
+        assert!(!paused.recorded);
+        assert_eq!(
+            ProfileCollector::get_stats("::detailed_test")
+                .unwrap()
+                .count,
+            1
+        );
+    }
+
     #[test]
     #[cfg(feature = "full")]
     fn test_scoped_timer() {
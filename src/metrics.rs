@@ -64,6 +64,29 @@ impl<C: Category> MetricDefinition<C> {
 }
 
 /// A registry for managing metric definitions
+///
+/// Clones share the same definitions.
+///
+/// # Example
+/// ```rust
+/// use quantum_pulse::{DefaultCategory, MetricDefinition, MetricRegistry, MetricSetBuilder};
+///
+/// let registry = MetricRegistry::new();
+/// registry
+///     .register(
+///         MetricDefinition::new("db_query", "Database Query", DefaultCategory::IO)
+///             .with_unit("microseconds"),
+///     )
+///     .unwrap();
+/// MetricSetBuilder::new()
+///     .metric("hash", "Hashing", DefaultCategory::Compute)
+///     .register_to(&registry)
+///     .unwrap();
+///
+/// assert_eq!(registry.len(), 2);
+/// assert_eq!(registry.get_by_category(&DefaultCategory::IO)[0].id, "db_query");
+/// assert!(registry.register(MetricDefinition::new("hash", "Again", DefaultCategory::Compute)).is_err());
+/// ```
 pub struct MetricRegistry<C: Category> {
     metrics: Arc<RwLock<HashMap<String, MetricDefinition<C>>>>,
 }
@@ -102,13 +125,49 @@ impl<C: Category> MetricRegistry<C> {
         Ok(())
     }
 
+    /// Get all metric IDs
+    pub fn ids(&self) -> Vec<String> {
+        self.metrics
+            .read()
+            .ok()
+            .map(|metrics| metrics.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Remove a metric definition
+    pub fn unregister(&self, id: &str) -> Option<MetricDefinition<C>> {
+        self.metrics.write().ok()?.remove(id)
+    }
+
+    /// Clear all metric definitions
+    pub fn clear(&self) {
+        if let Ok(mut metrics) = self.metrics.write() {
+            metrics.clear();
+        }
+    }
+
+    /// Get the number of registered metrics
+    pub fn len(&self) -> usize {
+        self.metrics.read().ok().map(|m| m.len()).unwrap_or(0)
+    }
+
+    /// Check if the registry is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<C: Category + Clone> MetricRegistry<C> {
     /// Get a metric definition by ID
     pub fn get(&self, id: &str) -> Option<MetricDefinition<C>> {
         self.metrics.read().ok()?.get(id).cloned()
     }
 
     /// Get all metrics in a specific category
-    pub fn get_by_category(&self, category: &C) -> Vec<MetricDefinition<C>> {
+    pub fn get_by_category(&self, category: &C) -> Vec<MetricDefinition<C>>
+    where
+        C: PartialEq,
+    {
         self.metrics
             .read()
             .ok()
@@ -145,37 +204,6 @@ impl<C: Category> MetricRegistry<C> {
             .map(|metrics| metrics.values().cloned().collect())
             .unwrap_or_default()
     }
-
-    /// Get all metric IDs
-    pub fn ids(&self) -> Vec<String> {
-        self.metrics
-            .read()
-            .ok()
-            .map(|metrics| metrics.keys().cloned().collect())
-            .unwrap_or_default()
-    }
-
-    /// Remove a metric definition
-    pub fn unregister(&self, id: &str) -> Option<MetricDefinition<C>> {
-        self.metrics.write().ok()?.remove(id)
-    }
-
-    /// Clear all metric definitions
-    pub fn clear(&self) {
-        if let Ok(mut metrics) = self.metrics.write() {
-            metrics.clear();
-        }
-    }
-
-    /// Get the number of registered metrics
-    pub fn len(&self) -> usize {
-        self.metrics.read().ok().map(|m| m.len()).unwrap_or(0)
-    }
-
-    /// Check if the registry is empty
-    pub fn is_empty(&self) -> bool {
-        self.len() == 0
-    }
 }
 
 impl<C: Category> Default for MetricRegistry<C> {
@@ -230,6 +258,7 @@ impl<C: Category> MetricSetBuilder<C> {
     }
 
     /// Add a metric to the set
+    #[allow(clippy::should_implement_trait)]
     pub fn add(mut self, metric: MetricDefinition<C>) -> Self {
         self.metrics.push(metric);
         self
@@ -55,6 +55,114 @@ pub trait Operation: Debug + Send + Sync {
     fn to_str(&self) -> String {
         format!("{:?}", self)
     }
+
+    /// The name this operation's statistics are stored under
+    ///
+    /// Every distinct key gets its own statistics and histogram (~2KB), so
+    /// keys should come from a small, fixed set. By default this is
+    /// [`to_str`](Self::to_str); override it when the display string carries
+    /// per-call data such as IDs or query parameters, replacing the dynamic
+    /// parts with placeholders so all calls share one key.
+    ///
+    /// ```rust
+    /// use quantum_pulse::Operation;
+    ///
+    /// #[derive(Debug)]
+    /// struct Query(String);
+    ///
+    /// impl Operation for Query {
+    ///     fn to_str(&self) -> String {
+    ///         self.0.clone()
+    ///     }
+    ///
+    ///     fn normalized_key(&self) -> String {
+    ///         // Collapse each run of digits into a single `?`
+    ///         let mut key = String::new();
+    ///         for c in self.0.chars() {
+    ///             if !c.is_ascii_digit() {
+    ///                 key.push(c);
+    ///             } else if !key.ends_with('?') {
+    ///                 key.push('?');
+    ///             }
+    ///         }
+    ///         key
+    ///     }
+    /// }
+    ///
+    /// let query = Query("SELECT * FROM users WHERE id=123".to_string());
+    /// assert_eq!(query.normalized_key(), "SELECT * FROM users WHERE id=?");
+    /// ```
+    fn normalized_key(&self) -> String {
+        self.to_str()
+    }
+
+    /// A stable numeric id for this operation, or 0 if it has none
+    ///
+    /// Unlike keys, ids don't change when an operation is renamed, making
+    /// them suitable for wire formats. The `ProfileOp` derive assigns them
+    /// with `#[category(id = ...)]`; duplicate ids are a compile error:
+    ///
+    /// ```rust
+    /// use quantum_pulse::{Operation, ProfileOp};
+    ///
+    /// #[derive(Debug, ProfileOp)]
+    /// enum AppOperation {
+    ///     #[category(name = "Database", id = 1)]
+    ///     Query,
+    ///     #[category(name = "Cache", id = 2)]
+    ///     Lookup,
+    /// }
+    ///
+    /// assert_eq!(AppOperation::Lookup.op_id(), 2);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use quantum_pulse::ProfileOp;
+    ///
+    /// #[derive(Debug, ProfileOp)]
+    /// enum AppOperation {
+    ///     #[category(id = 1)]
+    ///     Query,
+    ///     #[category(id = 1)]
+    ///     Lookup,
+    /// }
+    /// ```
+    fn op_id(&self) -> u32 {
+        0
+    }
+
+    /// The known-good mean latency of this operation in microseconds
+    ///
+    /// Timers register the baseline under the operation's key, and reports
+    /// built with [`ReportBuilder::include_baseline`](crate::ReportBuilder::include_baseline)
+    /// flag operations whose mean exceeds it. The `ProfileOp` derive sets
+    /// it with `#[category(expected_us = ...)]`.
+    fn expected_micros(&self) -> Option<u64> {
+        None
+    }
+
+    /// Position of this operation within its category in reports
+    ///
+    /// Reports built with
+    /// [`ReportBuilder::respect_operation_order`](crate::ReportBuilder::respect_operation_order)
+    /// list lower values first, keeping a fixed logical order such as
+    /// validate → submit → execute instead of sorting by time. Timers register
+    /// non-zero hints under the operation's key. The `ProfileOp` derive sets
+    /// it with `#[category(order = ...)]`.
+    fn sort_hint(&self) -> i32 {
+        0
+    }
+
+    /// An extra key this operation's recordings roll up into
+    ///
+    /// Timers record under the operation's own key and, when this returns
+    /// `Some`, also under `category::aggregate`, so variants like `ReadFile`
+    /// and `ReadFileCached` can share a `file_read` total while staying
+    /// visible on their own. Summaries skip aggregate keys, see
+    /// [`ProfileCollector::is_aggregate`](crate::ProfileCollector::is_aggregate).
+    fn aggregate_key(&self) -> Option<String> {
+        None
+    }
 }
 
 /// A simple operation implementation for basic profiling
@@ -0,0 +1,194 @@
+//! # Streaming Quantile Estimation
+//!
+//! Constant-memory quantile estimates using the P² algorithm (Jain and
+//! Chlamtac, 1985). Five markers track the minimum, the maximum, the target
+//! quantile and two points halfway to it; each observation nudges the markers
+//! along a piecewise-parabolic fit of the distribution. No samples or
+//! histogram buckets are kept, so an estimator takes about 140 bytes.
+//!
+//! Available in both modes. Stub-mode [`OperationStats`](crate::OperationStats)
+//! uses it for approximate percentiles where full mode keeps HDR histograms.
+
+/// Streaming estimate of a single quantile
+///
+/// # Example
+/// ```rust
+/// use quantum_pulse::P2Quantile;
+///
+/// let mut p95 = P2Quantile::new(0.95);
+/// for i in 1..=1000 {
+///     p95.observe(i as f64);
+/// }
+/// assert!((p95.estimate() - 950.0).abs() < 20.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct P2Quantile {
+    /// Target quantile in `[0, 1]`
+    quantile: f64,
+    /// Observations seen so far
+    count: usize,
+    /// Marker heights, the first five observations until initialized
+    heights: [f64; 5],
+    /// Actual marker positions, 1-based
+    positions: [f64; 5],
+    /// Desired marker positions
+    desired: [f64; 5],
+}
+
+impl P2Quantile {
+    /// Create an estimator for `quantile`, clamped to `[0, 1]`
+    pub fn new(quantile: f64) -> Self {
+        let p = if quantile.is_nan() {
+            0.5
+        } else {
+            quantile.clamp(0.0, 1.0)
+        };
+        Self {
+            quantile: p,
+            count: 0,
+            heights: [0.0; 5],
+            positions: [1.0, 2.0, 3.0, 4.0, 5.0],
+            desired: [1.0, 1.0 + 2.0 * p, 1.0 + 4.0 * p, 3.0 + 2.0 * p, 5.0],
+        }
+    }
+
+    /// Get the quantile this estimator tracks
+    pub fn quantile(&self) -> f64 {
+        self.quantile
+    }
+
+    /// Get the number of observations seen
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Add an observation
+    pub fn observe(&mut self, value: f64) {
+        if self.count < 5 {
+            self.heights[self.count] = value;
+            self.count += 1;
+            if self.count == 5 {
+                self.heights.sort_by(f64::total_cmp);
+            }
+            return;
+        }
+        self.count += 1;
+
+        // Find the cell the value falls into, stretching the extremes
+        let cell = if value < self.heights[0] {
+            self.heights[0] = value;
+            0
+        } else if value >= self.heights[4] {
+            self.heights[4] = value;
+            3
+        } else {
+            (0..4).find(|&i| value < self.heights[i + 1]).unwrap_or(3)
+        };
+
+        for position in &mut self.positions[cell + 1..] {
+            *position += 1.0;
+        }
+        let p = self.quantile;
+        let increments = [0.0, p / 2.0, p, (1.0 + p) / 2.0, 1.0];
+        for (desired, increment) in self.desired.iter_mut().zip(increments) {
+            *desired += increment;
+        }
+
+        // Move the middle markers toward their desired positions
+        for i in 1..4 {
+            let offset = self.desired[i] - self.positions[i];
+            let room_right = self.positions[i + 1] - self.positions[i];
+            let room_left = self.positions[i - 1] - self.positions[i];
+            if (offset >= 1.0 && room_right > 1.0) || (offset <= -1.0 && room_left < -1.0) {
+                let step = offset.signum();
+                let parabolic = self.parabolic(i, step);
+                self.heights[i] =
+                    if self.heights[i - 1] < parabolic && parabolic < self.heights[i + 1] {
+                        parabolic
+                    } else {
+                        self.linear(i, step)
+                    };
+                self.positions[i] += step;
+            }
+        }
+    }
+
+    /// Get the current estimate, or 0 before anything was observed
+    ///
+    /// With fewer than five observations the estimate is read from the
+    /// observations themselves.
+    pub fn estimate(&self) -> f64 {
+        match self.count {
+            0 => 0.0,
+            1..=4 => {
+                let mut seen = self.heights;
+                let seen = &mut seen[..self.count];
+                seen.sort_by(f64::total_cmp);
+                let index = (self.quantile * (self.count - 1) as f64).round() as usize;
+                seen[index]
+            }
+            _ => self.heights[2],
+        }
+    }
+
+    /// Piecewise-parabolic prediction for marker `i` moved by `step`
+    fn parabolic(&self, i: usize, step: f64) -> f64 {
+        let (n, q) = (&self.positions, &self.heights);
+        q[i] + step / (n[i + 1] - n[i - 1])
+            * ((n[i] - n[i - 1] + step) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+                + (n[i + 1] - n[i] - step) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]))
+    }
+
+    /// Linear prediction for marker `i` moved by `step`
+    fn linear(&self, i: usize, step: f64) -> f64 {
+        let j = if step > 0.0 { i + 1 } else { i - 1 };
+        self.heights[i]
+            + step * (self.heights[j] - self.heights[i]) / (self.positions[j] - self.positions[i])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feed 1..=n in a scrambled but deterministic order
+    fn observe_scrambled(estimator: &mut P2Quantile, n: u64, transform: fn(f64) -> f64) {
+        // 7919 is coprime with n, so this visits every value once
+        for i in 0..n {
+            estimator.observe(transform(((i * 7919) % n + 1) as f64));
+        }
+    }
+
+    #[test]
+    fn test_p2_quantile_uniform() {
+        for (quantile, expected) in [(0.5, 5000.0), (0.95, 9500.0), (0.99, 9900.0)] {
+            let mut estimator = P2Quantile::new(quantile);
+            observe_scrambled(&mut estimator, 10_000, |x| x);
+            let error = (estimator.estimate() - expected).abs() / expected;
+            assert!(error < 0.03, "p{quantile}: {}", estimator.estimate());
+        }
+    }
+
+    #[test]
+    fn test_p2_quantile_skewed() {
+        // Squaring keeps the order, so the true quantiles square too
+        for (quantile, expected) in [(0.5, 5000.0_f64), (0.95, 9500.0), (0.99, 9900.0)] {
+            let mut estimator = P2Quantile::new(quantile);
+            observe_scrambled(&mut estimator, 10_000, |x| x * x);
+            let expected = expected * expected;
+            let error = (estimator.estimate() - expected).abs() / expected;
+            assert!(error < 0.05, "p{quantile}: {}", estimator.estimate());
+        }
+    }
+
+    #[test]
+    fn test_p2_quantile_few_observations() {
+        let mut estimator = P2Quantile::new(0.5);
+        assert_eq!(estimator.estimate(), 0.0);
+        for value in [30.0, 10.0, 20.0] {
+            estimator.observe(value);
+        }
+        assert_eq!(estimator.estimate(), 20.0);
+        assert_eq!(estimator.count(), 3);
+    }
+}
@@ -3,11 +3,20 @@
 //! Provides flexible reporting capabilities for profiling data with
 //! multiple output formats and customizable presentation options.
 
+#[cfg(feature = "serde")]
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::fmt;
-
-use crate::category::Category;
+#[cfg(feature = "serde")]
+use std::io;
+#[cfg(feature = "serde")]
+use std::path::Path;
+
+use crate::category::{Category, CategoryInfo};
+#[cfg(feature = "serde")]
+use crate::collector::OperationStatsRow;
 use crate::collector::{OperationStats, ProfileCollector, SummaryStats};
+use std::io::IsTerminal;
 
 /// Configuration for generating profile reports
 #[derive(Debug, Clone)]
@@ -26,10 +35,36 @@ pub struct ReportConfig {
     pub include_summary: bool,
     /// Time format for displaying durations
     pub time_format: TimeFormat,
+    /// Decimal places in displayed durations (`None` = per-unit defaults)
+    pub time_precision: Option<u8>,
     /// Maximum number of operations to display (0 = unlimited)
     pub max_operations: usize,
     /// Include operations with zero samples
     pub include_empty: bool,
+    /// Only include operations in the category with this name
+    pub only_category: Option<String>,
+    /// Include the exponentially weighted moving average column
+    pub include_ewma: bool,
+    /// Include when each operation's max was recorded, next to the max column
+    pub include_max_time_at: bool,
+    /// Include a column marking operations whose mean exceeds their declared baseline
+    pub include_baseline: bool,
+    /// Factor by which the mean may exceed the baseline before it is flagged
+    pub baseline_tolerance: f64,
+    /// Custom title for the report header (defaults to "PROFILING REPORT")
+    pub title: Option<String>,
+    /// Color console output with ANSI codes (`None` = only when stdout is a terminal)
+    pub use_color: Option<bool>,
+    /// Category sort priorities that replace `Category::priority()`, by category name
+    pub category_priority_overrides: HashMap<String, i32>,
+    /// List operations by their declared order before applying the sort metric
+    pub respect_operation_order: bool,
+    /// List the report's categories with their colors and descriptions at the top
+    pub include_legend: bool,
+    /// Show registered categories that have no operations in grouped reports
+    pub include_empty_categories: bool,
+    /// Where keys are split into category and operation for grouping
+    pub split_mode: SplitMode,
 }
 
 impl Default for ReportConfig {
@@ -42,8 +77,21 @@ impl Default for ReportConfig {
             group_by_category: true,
             include_summary: true,
             time_format: TimeFormat::Auto,
+            time_precision: None,
             max_operations: 0,
             include_empty: false,
+            only_category: None,
+            include_ewma: false,
+            include_max_time_at: false,
+            include_baseline: false,
+            baseline_tolerance: 1.0,
+            title: None,
+            use_color: None,
+            category_priority_overrides: HashMap::new(),
+            respect_operation_order: false,
+            include_legend: false,
+            include_empty_categories: false,
+            split_mode: SplitMode::First,
         }
     }
 }
@@ -60,6 +108,8 @@ pub enum Percentile {
 /// Time format for displaying durations
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TimeFormat {
+    /// Display in nanoseconds
+    Nanoseconds,
     /// Display in microseconds
     Microseconds,
     /// Display in milliseconds
@@ -73,47 +123,167 @@ pub enum TimeFormat {
 impl TimeFormat {
     /// Format a time value according to the format setting
     pub fn format_time(&self, micros: u64) -> String {
+        self.format_time_with(micros, None)
+    }
+
+    /// Format a time value with `precision` decimal places
+    ///
+    /// `None` keeps the per-unit defaults of [`format_time`](Self::format_time):
+    /// whole microseconds, two decimals for milliseconds and three for
+    /// seconds. Nanoseconds are always whole.
+    pub fn format_time_with(&self, micros: u64, precision: Option<u8>) -> String {
+        let decimals = |default: u8| precision.unwrap_or(default) as usize;
         match self {
-            TimeFormat::Microseconds => format!("{} µs", micros),
-            TimeFormat::Milliseconds => format!("{:.2} ms", micros as f64 / 1000.0),
-            TimeFormat::Seconds => format!("{:.3} s", micros as f64 / 1_000_000.0),
+            TimeFormat::Nanoseconds => format!("{} ns", micros.saturating_mul(1000)),
+            TimeFormat::Microseconds => format!("{:.*} µs", decimals(0), micros as f64),
+            TimeFormat::Milliseconds => {
+                format!("{:.*} ms", decimals(2), micros as f64 / 1000.0)
+            }
+            TimeFormat::Seconds => format!("{:.*} s", decimals(3), micros as f64 / 1_000_000.0),
             TimeFormat::Auto => {
                 if micros < 1000 {
-                    format!("{} µs", micros)
+                    format!("{:.*} µs", decimals(0), micros as f64)
                 } else if micros < 1_000_000 {
-                    format!("{:.2} ms", micros as f64 / 1000.0)
+                    format!("{:.*} ms", decimals(2), micros as f64 / 1000.0)
                 } else {
-                    format!("{:.3} s", micros as f64 / 1_000_000.0)
+                    format!("{:.*} s", decimals(3), micros as f64 / 1_000_000.0)
                 }
             }
         }
     }
 
+    /// Format a time value given in nanoseconds
+    ///
+    /// Unlike [`format_time`](Self::format_time), this keeps sub-microsecond
+    /// values visible: `Auto` switches to nanoseconds below one microsecond.
+    pub fn format_nanos(&self, nanos: u64) -> String {
+        self.format_nanos_with(nanos, None)
+    }
+
+    /// Format a time value given in nanoseconds with `precision` decimal places
+    pub fn format_nanos_with(&self, nanos: u64, precision: Option<u8>) -> String {
+        match self {
+            TimeFormat::Nanoseconds => format!("{} ns", nanos),
+            TimeFormat::Auto if nanos < 1000 => format!("{} ns", nanos),
+            _ => self.format_time_f64_with(nanos as f64 / 1000.0, precision),
+        }
+    }
+
     /// Format a floating-point time value
     pub fn format_time_f64(&self, micros: f64) -> String {
+        self.format_time_f64_with(micros, None)
+    }
+
+    /// Format a floating-point time value with `precision` decimal places
+    ///
+    /// `None` keeps the defaults of [`format_time_f64`](Self::format_time_f64):
+    /// one decimal for microseconds, two for milliseconds, three for seconds.
+    pub fn format_time_f64_with(&self, micros: f64, precision: Option<u8>) -> String {
+        let decimals = |default: u8| precision.unwrap_or(default) as usize;
         match self {
-            TimeFormat::Microseconds => format!("{:.1} µs", micros),
-            TimeFormat::Milliseconds => format!("{:.2} ms", micros / 1000.0),
-            TimeFormat::Seconds => format!("{:.3} s", micros / 1_000_000.0),
+            TimeFormat::Nanoseconds => format!("{:.0} ns", micros * 1000.0),
+            TimeFormat::Microseconds => format!("{:.*} µs", decimals(1), micros),
+            TimeFormat::Milliseconds => format!("{:.*} ms", decimals(2), micros / 1000.0),
+            TimeFormat::Seconds => format!("{:.*} s", decimals(3), micros / 1_000_000.0),
             TimeFormat::Auto => {
                 if micros < 1000.0 {
-                    format!("{:.1} µs", micros)
+                    format!("{:.*} µs", decimals(1), micros)
                 } else if micros < 1_000_000.0 {
-                    format!("{:.2} ms", micros / 1000.0)
+                    format!("{:.*} ms", decimals(2), micros / 1000.0)
                 } else {
-                    format!("{:.3} s", micros / 1_000_000.0)
+                    format!("{:.*} s", decimals(3), micros / 1_000_000.0)
                 }
             }
         }
     }
 }
 
+/// Format how long ago something happened, in the largest whole unit
+fn format_age(age: std::time::Duration) -> String {
+    let secs = age.as_secs();
+    if secs < 60 {
+        format!("{}s ago", secs)
+    } else if secs < 3600 {
+        format!("{}m ago", secs / 60)
+    } else {
+        format!("{}h ago", secs / 3600)
+    }
+}
+
+/// Convert a `#RRGGBB` color hint to the nearest xterm 256-color cube index
+fn hex_to_ansi256(hex: &str) -> Option<u8> {
+    let hex = hex.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    let (r, g, b) = (channel(0)?, channel(2)?, channel(4)?);
+
+    // Cube levels are 0, 95, 135, 175, 215, 255
+    let level = |c: u8| match c {
+        0..=47 => 0,
+        48..=114 => 1,
+        _ => (c - 35) / 40,
+    };
+    Some(16 + 36 * level(r) + 6 * level(g) + level(b))
+}
+
+/// Group name for operations whose key has no category prefix
+const UNCATEGORIZED: &str = "Uncategorized";
+
+/// A category's metadata (if registered) and the operations grouped under it
+type CategoryGroup<'a> = (
+    Option<&'a dyn Category>,
+    Vec<(&'a String, &'a OperationStats)>,
+);
+
 /// A comprehensive profiling report
 pub struct ProfileReport<C: Category = crate::category::DefaultCategory> {
     config: ReportConfig,
     stats: HashMap<String, OperationStats>,
     categories: HashMap<String, C>,
+    /// Summary of `stats`, computed once so every output agrees with the snapshot
+    summary: SummaryStats,
     generated_at: std::time::SystemTime,
+    /// Declared mean latencies in microseconds, by operation key
+    baselines: HashMap<String, u64>,
+    /// Declared positions within categories, by operation key
+    operation_order: HashMap<String, i32>,
+    /// Earlier stats to show each operation's change against, by operation key
+    comparison: HashMap<String, OperationStats>,
+    /// Categories declared up front, in registration order
+    registered_categories: Vec<CategoryInfo>,
+}
+
+impl ProfileReport {
+    /// Read a JSON baseline file written by [`Self::save_baseline`]
+    ///
+    /// Entries are summary-only stats, see [`OperationStatsRow::to_stats`].
+    #[cfg(feature = "serde")]
+    pub fn load_baseline(path: impl AsRef<Path>) -> io::Result<HashMap<String, OperationStats>> {
+        let json = std::fs::read_to_string(path)?;
+        let rows: HashMap<String, OperationStatsRow> = serde_json::from_str(&json)?;
+        Ok(rows
+            .into_iter()
+            .map(|(key, row)| (key, row.to_stats()))
+            .collect())
+    }
+}
+
+/// Result of [`ProfileReport::compare_to_baseline_file`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BaselineComparison {
+    /// Operations whose mean exceeds the baseline beyond the tolerance, sorted
+    pub regressed: Vec<String>,
+    /// Operations missing from the baseline, sorted
+    pub new: Vec<String>,
+}
+
+impl BaselineComparison {
+    /// True when no operation regressed
+    pub fn is_ok(&self) -> bool {
+        self.regressed.is_empty()
+    }
 }
 
 impl<C: Category + Clone + std::fmt::Debug + Eq + std::hash::Hash> ProfileReport<C> {
@@ -124,7 +294,17 @@ impl<C: Category + Clone + std::fmt::Debug + Eq + std::hash::Hash> ProfileReport
 
     /// Generate a report with custom configuration
     pub fn generate_with_config(config: ReportConfig) -> Self {
-        let stats = ProfileCollector::get_all_stats();
+        let mut stats = ProfileCollector::get_all_stats();
+        if let Some(category_name) = &config.only_category {
+            // Keys are `category::operation`
+            stats.retain(|key, _| {
+                config
+                    .split_mode
+                    .split(key)
+                    .is_some_and(|(category, _)| category == category_name)
+            });
+        }
+
         let mut categories = HashMap::new();
 
         for operation in stats.keys() {
@@ -133,30 +313,250 @@ impl<C: Category + Clone + std::fmt::Debug + Eq + std::hash::Hash> ProfileReport
             }
         }
 
+        Self::from_parts(
+            config,
+            stats,
+            categories,
+            ProfileCollector::registered_categories(),
+        )
+    }
+
+    /// Build a report from an already captured snapshot
+    ///
+    /// Expected baselines, operation order and the collection window are
+    /// still read from the collector.
+    fn from_parts(
+        config: ReportConfig,
+        stats: HashMap<String, OperationStats>,
+        categories: HashMap<String, C>,
+        registered_categories: Vec<CategoryInfo>,
+    ) -> Self {
+        let mut summary = SummaryStats::from_stats(&stats);
+        if let Some(window) = ProfileCollector::collection_window() {
+            summary = summary.with_window(window);
+        }
         Self {
             config,
             stats,
             categories,
+            summary,
             generated_at: std::time::SystemTime::now(),
+            baselines: ProfileCollector::expected_baselines(),
+            operation_order: ProfileCollector::operation_orders(),
+            comparison: HashMap::new(),
+            registered_categories,
+        }
+    }
+
+    /// Show each operation's change in mean against `baseline`
+    ///
+    /// Adds a column with the percent change, marked `↑` and red when slower
+    /// or `↓` and green when faster if color is enabled. Changes that round
+    /// to 0% are shown uncolored as `= 0%`. Operations missing from
+    /// `baseline` show `—`. Baselines saved with
+    /// [`Self::save_baseline`] can be read back with
+    /// [`ProfileReport::load_baseline`].
+    pub fn with_baseline(mut self, baseline: HashMap<String, OperationStats>) -> Self {
+        self.comparison = baseline;
+        self
+    }
+
+    /// Get the percent change of an operation's mean against the attached baseline
+    ///
+    /// Negative values are faster. Returns `None` without a baseline for the
+    /// operation or timing on either side.
+    pub fn change_vs_baseline(&self, key: &str) -> Option<f64> {
+        let baseline = self
+            .comparison
+            .get(key)
+            .filter(|stats| stats.has_timing())?;
+        let stats = self.stats.get(key).filter(|stats| stats.has_timing())?;
+        let before = baseline.mean_time_nanos();
+        if before == 0 {
+            return None;
+        }
+        Some((stats.mean_time_nanos() as f64 - before as f64) / before as f64 * 100.0)
+    }
+
+    /// Check an operation's mean against its declared baseline
+    ///
+    /// Returns `Some(true)` if the mean exceeds the baseline times
+    /// [`ReportConfig::baseline_tolerance`], and `None` if the operation has
+    /// no baseline or no timing.
+    pub fn exceeds_baseline(&self, key: &str) -> Option<bool> {
+        let expected = *self.baselines.get(key)?;
+        let stats = self.stats.get(key).filter(|stats| stats.has_timing())?;
+        Some(stats.mean_time_micros() as f64 > expected as f64 * self.config.baseline_tolerance)
+    }
+
+    /// Get the keys of operations whose mean exceeds their declared baseline, sorted
+    pub fn over_baseline(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self
+            .stats
+            .keys()
+            .filter(|key| self.exceeds_baseline(key) == Some(true))
+            .cloned()
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    /// Render every statistic of one operation as a multi-line block
+    ///
+    /// The detailed view complementing the table: count, total, mean, min,
+    /// max, standard deviation, percentiles and EWMA, then when the max was
+    /// recorded and the operation's baselines if it has any. Durations use
+    /// the configured [`TimeFormat`] and estimated percentiles are marked
+    /// `~` as in the table. Returns `None` if the report has no such key.
+    pub fn operation_detail(&self, key: &str) -> Option<String> {
+        let stats = self.stats.get(key)?;
+        let format_timing = |nanos: u64| {
+            if stats.has_timing() {
+                self.config
+                    .time_format
+                    .format_nanos_with(nanos, self.config.time_precision)
+            } else {
+                "—".to_string()
+            }
+        };
+        let format_percentile = |nanos: u64| {
+            let cell = format_timing(nanos);
+            if stats.has_timing() && !stats.has_percentiles() {
+                format!("~{}", cell)
+            } else {
+                cell
+            }
+        };
+
+        let name = key.strip_prefix("None::").unwrap_or(key);
+        let mut lines = vec![format!("\"{}\"", name)];
+        if let Some(category) = self.categories.get(key) {
+            lines.push(format!("  Category:  {}", category.get_name()));
+        }
+        lines.push(format!("  Count:     {}", stats.count));
+        for (label, value) in [
+            ("Total:    ", format_timing(stats.total.as_nanos() as u64)),
+            ("Mean:     ", format_timing(stats.mean_time_nanos())),
+            ("Min:      ", format_timing(stats.min_time_nanos())),
+            ("Max:      ", format_timing(stats.max_time_nanos)),
+            (
+                "Std Dev:  ",
+                format_timing(stats.std_dev_micros().saturating_mul(1000)),
+            ),
+            ("P50:      ", format_percentile(stats.p50_nanos())),
+            ("P95:      ", format_percentile(stats.p95_nanos())),
+            ("P99:      ", format_percentile(stats.p99_nanos())),
+            ("P99.9:    ", format_percentile(stats.p999_nanos())),
+            ("EWMA:     ", format_timing(stats.ewma_nanos())),
+        ] {
+            lines.push(format!("  {} {}", label, value));
+        }
+        if let Some(at) = stats.max_time_at() {
+            let age = self.generated_at.duration_since(at).unwrap_or_default();
+            lines.push(format!("  Max At:    {}", format_age(age)));
+        }
+        if let Some(&expected) = self.baselines.get(key) {
+            let mark = match self.exceeds_baseline(key) {
+                Some(true) => " ✗",
+                Some(false) => " ✓",
+                None => "",
+            };
+            lines.push(format!(
+                "  Expected:  {}{}",
+                self.format_time(expected),
+                mark
+            ));
+        }
+        if let Some(change) = self.change_vs_baseline(key) {
+            lines.push(format!("  Change:    {:+.0}%", change));
+        }
+        if stats.zero_duration_count() > 0 {
+            lines.push(format!(
+                "  Zero:      {} below clock resolution",
+                stats.zero_duration_count()
+            ));
+        }
+
+        lines.push(String::new());
+        Some(lines.join("\n"))
+    }
+
+    /// Write this report's statistics to a JSON baseline file
+    ///
+    /// The file maps each operation key to its [`OperationStatsRow`], and can
+    /// be committed and checked later with [`Self::compare_to_baseline_file`].
+    #[cfg(feature = "serde")]
+    pub fn save_baseline(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let rows: BTreeMap<&String, OperationStatsRow> = self
+            .stats
+            .iter()
+            .map(|(key, stats)| (key, OperationStatsRow::new(key, stats)))
+            .collect();
+        let json = serde_json::to_string_pretty(&rows)?;
+        std::fs::write(path, json)
+    }
+
+    /// Compare this report against a baseline file written by [`Self::save_baseline`]
+    ///
+    /// An operation regresses when its mean exceeds the baseline mean times
+    /// `tolerance`, as with [`Self::exceeds_baseline`]. A missing file is
+    /// treated as an empty baseline, so every operation is new and nothing
+    /// regresses.
+    #[cfg(feature = "serde")]
+    pub fn compare_to_baseline_file(
+        &self,
+        path: impl AsRef<Path>,
+        tolerance: f64,
+    ) -> io::Result<BaselineComparison> {
+        let baseline = match ProfileReport::load_baseline(path) {
+            Ok(baseline) => baseline,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => HashMap::new(),
+            Err(err) => return Err(err),
+        };
+
+        let mut comparison = BaselineComparison::default();
+        for (key, stats) in self.stats.iter().filter(|(_, stats)| stats.has_timing()) {
+            match baseline.get(key) {
+                None => comparison.new.push(key.clone()),
+                Some(expected) => {
+                    if stats.mean_time_micros() as f64
+                        > expected.mean_time_micros() as f64 * tolerance
+                    {
+                        comparison.regressed.push(key.clone());
+                    }
+                }
+            }
         }
+        comparison.regressed.sort();
+        comparison.new.sort();
+        Ok(comparison)
+    }
+
+    /// Generate a report containing only operations in one category
+    pub fn for_category(category_name: &str) -> Self {
+        Self::generate_with_config(ReportConfig {
+            only_category: Some(category_name.to_string()),
+            ..ReportConfig::default()
+        })
     }
 
     /// Get a quick summary string
     pub fn quick_summary(&self) -> String {
-        let summary = ProfileCollector::get_summary();
+        let summary = self.summary_stats();
         format!(
             "Operations: {} | Total calls: {} | Total time: {}",
             summary.unique_operations,
             summary.total_operations,
-            self.config
-                .time_format
-                .format_time(summary.total_time_micros)
+            self.format_time(summary.total_time_micros)
         )
     }
 
-    /// Get detailed summary statistics
+    /// Get detailed summary statistics for the operations in this report
+    ///
+    /// The summary is captured when the report is generated, so it doesn't
+    /// change if more data is recorded afterwards.
     pub fn summary_stats(&self) -> SummaryStats {
-        ProfileCollector::get_summary()
+        self.summary.clone()
     }
 
     /// Convert the report to a console-friendly string
@@ -164,9 +564,11 @@ impl<C: Category + Clone + std::fmt::Debug + Eq + std::hash::Hash> ProfileReport
         let mut output = String::new();
 
         // Header
-        output.push_str("╔════════════════════════════════════════════════╗\n");
-        output.push_str("║           PROFILING REPORT                     ║\n");
-        output.push_str("╚════════════════════════════════════════════════╝\n\n");
+        output.push_str(&self.format_header());
+
+        if self.config.include_legend {
+            output.push_str(&self.format_legend());
+        }
 
         // Summary
         if self.config.include_summary {
@@ -174,20 +576,99 @@ impl<C: Category + Clone + std::fmt::Debug + Eq + std::hash::Hash> ProfileReport
             output.push_str("\n");
         }
 
-        // Operations - disable categorization if no categories are available
-        let has_categories = !self.categories.is_empty();
-        if self.config.group_by_category && has_categories {
+        // Operations
+        if self.config.group_by_category {
             output.push_str(&self.format_by_category());
         } else {
             output.push_str(&self.format_all_operations());
         }
 
+        output.push_str(&self.format_resolution_hint());
+
         // Footer
         output.push_str(&format!("\nGenerated at: {:?}\n", self.generated_at));
 
         output
     }
 
+    fn format_header(&self) -> String {
+        let Some(title) = &self.config.title else {
+            return "╔════════════════════════════════════════════════╗\n\
+                    ║           PROFILING REPORT                     ║\n\
+                    ╚════════════════════════════════════════════════╝\n\n"
+                .to_string();
+        };
+
+        // Center the title, widening the box if it doesn't fit
+        let title_width = title.chars().count();
+        let width = 48.max(title_width + 4);
+        let left = (width - title_width) / 2;
+        let right = width - title_width - left;
+        let rule = "═".repeat(width);
+
+        format!(
+            "╔{}╗\n║{}{}{}║\n╚{}╝\n\n",
+            rule,
+            " ".repeat(left),
+            title,
+            " ".repeat(right),
+            rule
+        )
+    }
+
+    /// List each category shown in the report once, with a color swatch
+    ///
+    /// The swatch is shown when color is enabled; otherwise the color hint is
+    /// printed instead. Categories are ordered as in the grouped tables.
+    fn format_legend(&self) -> String {
+        let mut categories: Vec<&C> = Vec::new();
+        for (operation, stats) in &self.stats {
+            if stats.count < self.config.min_samples as usize && !self.config.include_empty {
+                continue;
+            }
+            if let Some(category) = self.categories.get(operation) {
+                if !categories
+                    .iter()
+                    .any(|seen| seen.get_name() == category.get_name())
+                {
+                    categories.push(category);
+                }
+            }
+        }
+        if categories.is_empty() {
+            return String::new();
+        }
+
+        let overrides = &self.config.category_priority_overrides;
+        categories.sort_by_key(|category| {
+            let priority = overrides
+                .get(category.get_name())
+                .copied()
+                .unwrap_or_else(|| category.priority());
+            (priority, category.get_name())
+        });
+
+        let use_color = self.use_color();
+        let mut output = String::from("═══ Legend ═══\n");
+        for category in categories {
+            let hint = category.color_hint();
+            let name = match (hint, use_color) {
+                (Some(hint), true) => match hex_to_ansi256(hint) {
+                    Some(color) => format!("\x1b[38;5;{}m██\x1b[0m {}", color, category.get_name()),
+                    None => category.get_name().to_string(),
+                },
+                (Some(hint), false) => format!("{} ({})", category.get_name(), hint),
+                (None, _) => category.get_name().to_string(),
+            };
+            match category.get_description() {
+                "" => output.push_str(&format!("  {}\n", name)),
+                description => output.push_str(&format!("  {} — {}\n", name, description)),
+            }
+        }
+        output.push('\n');
+        output
+    }
+
     fn format_summary(&self) -> String {
         let summary = self.summary_stats();
         let mut output = String::new();
@@ -200,19 +681,28 @@ impl<C: Category + Clone + std::fmt::Debug + Eq + std::hash::Hash> ProfileReport
         ));
         output.push_str(&format!(
             "Total Time: {}\n",
-            self.config
-                .time_format
-                .format_time(summary.total_time_micros)
+            self.format_time(summary.total_time_micros)
         ));
+        if let Some(window) = summary.collection_window {
+            let secs = window.as_secs();
+            output.push_str(&format!(
+                "Collection Window: {:02}:{:02}:{:02}\n",
+                secs / 3600,
+                secs / 60 % 60,
+                secs % 60
+            ));
+            output.push_str(&format!(
+                "Throughput: {:.1} ops/sec\n",
+                summary.ops_per_second
+            ));
+        }
 
         if let Some(ref slowest) = summary.slowest_operation {
             let clean_name = slowest.strip_prefix("None::").unwrap_or(slowest);
             output.push_str(&format!(
                 "  📊 Slowest operation: {} (p99: {})\n",
                 clean_name,
-                self.config
-                    .time_format
-                    .format_time(summary.slowest_p99_micros)
+                self.format_time(summary.slowest_p99_micros)
             ));
         }
 
@@ -227,64 +717,147 @@ impl<C: Category + Clone + std::fmt::Debug + Eq + std::hash::Hash> ProfileReport
         output
     }
 
-    fn format_by_category(&self) -> String {
-        let mut output = String::new();
+    /// List operations with durations below the clock's resolution
+    ///
+    /// Empty when every recorded duration was above zero.
+    fn format_resolution_hint(&self) -> String {
+        let mut below: Vec<_> = self
+            .stats
+            .iter()
+            .filter(|(_, stats)| stats.zero_duration_count() > 0)
+            .collect();
+        if below.is_empty() {
+            return String::new();
+        }
+        below.sort_by_key(|&(key, _)| key);
 
-        // If no categories are available, show a simple message and fall back to flat list
-        if self.categories.is_empty() {
-            output.push_str("═══ All Operations ═══\n");
-            output.push_str("(No categories configured - showing flat list)\n\n");
-            return self.format_all_operations();
+        let mut output = String::from("\n⚠ Durations below clock resolution, recorded as 0:\n");
+        for (key, stats) in below {
+            output.push_str(&format!(
+                "  {}: {} of {}\n",
+                key.strip_prefix("None::").unwrap_or(key),
+                stats.zero_duration_count(),
+                stats.timed_count()
+            ));
         }
+        output.push_str("  Their means and percentiles read low; time batches of calls instead.\n");
+        output
+    }
+
+    fn format_by_category(&self) -> String {
+        let mut output = String::new();
 
-        let mut categorized: HashMap<C, Vec<(&String, &OperationStats)>> = HashMap::new();
+        // Group operations by category name. Operations without a registered
+        // category fall back to the `category::` prefix of their key, split
+        // as configured, or to "Uncategorized" if there is none, so grouping
+        // never drops data
+        let mut categorized: HashMap<&str, CategoryGroup> = HashMap::new();
 
-        // Group operations by category
         for (operation, stats) in &self.stats {
             if stats.count < self.config.min_samples as usize && !self.config.include_empty {
                 continue;
             }
 
-            // Skip operations that don't have categories - don't panic
-            let category = match self.categories.get(operation).cloned() {
-                Some(cat) => cat,
-                None => {
-                    // If no category is found, skip this operation in categorized view
-                    // This prevents panics when operations don't have categories assigned
-                    continue;
-                }
+            let category = self.categories.get(operation);
+            let name = match category {
+                Some(category) => category.get_name(),
+                None => self
+                    .config
+                    .split_mode
+                    .split(operation)
+                    .map(|(prefix, _)| prefix)
+                    .filter(|prefix| !prefix.is_empty())
+                    .unwrap_or(UNCATEGORIZED),
             };
 
-            categorized
-                .entry(category)
-                .or_insert_with(Vec::new)
-                .push((operation, stats));
+            let entry = categorized.entry(name).or_insert((None, Vec::new()));
+            if entry.0.is_none() {
+                entry.0 = category.map(|category| category as &dyn Category);
+            }
+            entry.1.push((operation, stats));
+        }
+
+        // Registered categories fill in metadata for prefix-grouped
+        // operations, and show up empty when asked to
+        for info in &self.registered_categories {
+            match categorized.get_mut(info.name.as_str()) {
+                Some(group) => {
+                    group.0.get_or_insert(info);
+                }
+                None if self.config.include_empty_categories
+                    && self
+                        .config
+                        .only_category
+                        .as_ref()
+                        .is_none_or(|only| *only == info.name) =>
+                {
+                    categorized.insert(&info.name, (Some(info), Vec::new()));
+                }
+                None => {}
+            }
         }
 
-        // If no operations were categorized, fall back to flat operation list
         if categorized.is_empty() {
-            output.push_str("═══ All Operations ═══\n");
-            output.push_str("(No operations could be categorized - showing flat list)\n\n");
             return self.format_all_operations();
         }
 
-        // Sort categories by priority
-        let mut categories: Vec<C> = categorized.keys().cloned().collect();
-        categories.sort_by_key(|c| c.priority());
+        // Sort categories by priority, then name, with uncategorized operations
+        // last unless their priority is overridden
+        let overrides = &self.config.category_priority_overrides;
+        let mut names: Vec<&str> = categorized.keys().copied().collect();
+        names.sort_by_key(|name| match overrides.get(*name) {
+            Some(&priority) => (false, priority, *name),
+            None => {
+                let priority = categorized[name]
+                    .0
+                    .map_or(0, |category| category.priority());
+                (*name == UNCATEGORIZED, priority, *name)
+            }
+        });
+
+        let use_color = self.use_color();
 
-        for category in categories {
-            if let Some(operations) = categorized.get(&category) {
-                output.push_str(&format!("\n═══ {} ═══\n", category.get_name()));
+        for name in names {
+            let (category, operations) = &categorized[name];
+            let header = match category.and_then(|category| category.icon()) {
+                Some(icon) => format!("═══ {} {} ═══", icon, name),
+                None => format!("═══ {} ═══", name),
+            };
+            let color = category
+                .and_then(|category| category.color_hint())
+                .and_then(hex_to_ansi256)
+                .filter(|_| use_color);
+            match color {
+                Some(color) => {
+                    output.push_str(&format!("\n\x1b[1;38;5;{}m{}\x1b[0m\n", color, header))
+                }
+                None => output.push_str(&format!("\n{}\n", header)),
+            }
+            if let Some(category) = category {
                 if !category.get_description().is_empty() {
                     output.push_str(&format!("  {}\n", category.get_description()));
                 }
-                output.push_str(&self.format_operations_table(operations));
             }
+            output.push_str(&self.format_operations_table(operations));
         }
 
         output
     }
 
+    /// Format microseconds with the configured time format and precision
+    fn format_time(&self, micros: u64) -> String {
+        self.config
+            .time_format
+            .format_time_with(micros, self.config.time_precision)
+    }
+
+    /// Whether console output should include ANSI color codes
+    fn use_color(&self) -> bool {
+        self.config
+            .use_color
+            .unwrap_or_else(|| std::io::stdout().is_terminal())
+    }
+
     fn format_all_operations(&self) -> String {
         let operations: Vec<_> = self
             .stats
@@ -302,7 +875,10 @@ impl<C: Category + Clone + std::fmt::Debug + Eq + std::hash::Hash> ProfileReport
             return "  No operations recorded\n".to_string();
         }
 
+        // Start from name order; the metric sorts below are stable, so ties
+        // stay in name order and identical inputs render identically
         let mut sorted_ops = operations.to_vec();
+        sorted_ops.sort_by(|a, b| a.0.cmp(b.0));
 
         // Sort operations
         if self.config.sort_by_time {
@@ -328,6 +904,12 @@ impl<C: Category + Clone + std::fmt::Debug + Eq + std::hash::Hash> ProfileReport
             }
         }
 
+        // The sort is stable, so the metric still orders operations without
+        // a declared position
+        if self.config.respect_operation_order {
+            sorted_ops.sort_by_key(|(key, _)| self.operation_order.get(*key).copied().unwrap_or(0));
+        }
+
         // Apply max operations limit
         if self.config.max_operations > 0 && sorted_ops.len() > self.config.max_operations {
             sorted_ops.truncate(self.config.max_operations);
@@ -335,21 +917,140 @@ impl<C: Category + Clone + std::fmt::Debug + Eq + std::hash::Hash> ProfileReport
 
         let mut output = String::new();
 
+        // Optional EWMA column, inserted after the mean
+        let (ewma_header, ewma_rule) = if self.config.include_ewma {
+            ("        EWMA |", "──────────────")
+        } else {
+            ("", "")
+        };
+
+        // Optional column after the max showing how long ago it was recorded
+        let (max_at_header, max_at_rule) = if self.config.include_max_time_at {
+            (" |      Max At", "──────────────")
+        } else {
+            ("", "")
+        };
+
+        // Optional last column marking operations against their baseline
+        let (baseline_header, baseline_rule) = if self.config.include_baseline {
+            (" | Base", "───────")
+        } else {
+            ("", "")
+        };
+
+        // Change against an attached baseline, after everything else
+        let (change_header, change_rule) = if self.comparison.is_empty() {
+            ("", "")
+        } else {
+            (" |   Change", "───────────")
+        };
+        let baseline_header = format!("{}{}", baseline_header, change_header);
+        let baseline_rule = format!("{}{}", baseline_rule, change_rule);
+
         // Table header
         if self.config.include_percentiles {
-            output.push_str("Operation                          |   Count |        Mean |         P50 |         P95 |         P99 |       P99.9 |         Max\n");
-            output.push_str("  ───────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────\n");
+            output.push_str(&format!("Operation                          |   Count |        Mean |{}         P50 |         P95 |         P99 |       P99.9 |         Max{}{}\n", ewma_header, max_at_header, baseline_header));
+            output.push_str(&format!("  ───────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────{}{}{}\n", ewma_rule, max_at_rule, baseline_rule));
         } else {
-            output.push_str(
-                "Operation                          |   Count |        Mean |         Min |         Max\n",
-            );
-            output.push_str(
-                "  ─────────────────────────────────────────────────────────────────────────────────────────\n",
-            );
+            output.push_str(&format!(
+                "Operation                          |   Count |        Mean |{}         Min |         Max{}{}\n",
+                ewma_header, max_at_header, baseline_header
+            ));
+            output.push_str(&format!(
+                "  ─────────────────────────────────────────────────────────────────────────────────────────{}{}{}\n",
+                ewma_rule, max_at_rule, baseline_rule
+            ));
         }
 
+        let use_color = self.use_color();
+
         // Table rows
         for (operation, stats) in sorted_ops {
+            // Dim operations that are only shown because empty ones are included
+            let dim = use_color && stats.count < self.config.min_samples as usize;
+            if dim {
+                output.push_str("\x1b[2m");
+            }
+
+            // Count-only operations have no durations to show
+            let format_timing = |nanos: u64| {
+                if stats.has_timing() {
+                    self.config
+                        .time_format
+                        .format_nanos_with(nanos, self.config.time_precision)
+                } else {
+                    "—".to_string()
+                }
+            };
+
+            // Percentiles are estimated, or imported, without a histogram
+            let format_percentile = |nanos: u64| {
+                let cell = format_timing(nanos);
+                if stats.has_timing() && !stats.has_percentiles() {
+                    format!("~{}", cell)
+                } else {
+                    cell
+                }
+            };
+
+            let ewma_cell = if self.config.include_ewma {
+                format!(" {:>11} |", format_timing(stats.ewma_nanos()))
+            } else {
+                String::new()
+            };
+
+            let max_at_cell = if self.config.include_max_time_at {
+                let age = stats
+                    .max_time_at()
+                    .map(|at| format_age(self.generated_at.duration_since(at).unwrap_or_default()));
+                format!(" | {:>11}", age.unwrap_or_else(|| "—".to_string()))
+            } else {
+                String::new()
+            };
+
+            let baseline_cell = if self.config.include_baseline {
+                let mark = match self.exceeds_baseline(operation) {
+                    Some(true) => "✗",
+                    Some(false) => "✓",
+                    None => "—",
+                };
+                format!(" | {:>4}", mark)
+            } else {
+                String::new()
+            };
+
+            let change_cell = if self.comparison.is_empty() {
+                String::new()
+            } else {
+                match self.change_vs_baseline(operation) {
+                    // Changes that round to 0% are shown as unchanged, so
+                    // the arrow always agrees with the printed sign
+                    Some(change) if change.round() == 0.0 => {
+                        if use_color {
+                            format!(" | {:>8}", "= 0%")
+                        } else {
+                            format!(" | {:>8}", "0%")
+                        }
+                    }
+                    Some(change) => {
+                        let text = format!("{:+.0}%", change);
+                        let (arrow, color) = if change > 0.0 {
+                            ("↑", "31")
+                        } else {
+                            ("↓", "32")
+                        };
+                        if use_color {
+                            let cell = format!("{:>8}", format!("{} {}", arrow, text));
+                            format!(" | \x1b[{}m{}\x1b[0m", color, cell)
+                        } else {
+                            format!(" | {:>8}", text)
+                        }
+                    }
+                    None => format!(" | {:>8}", "—"),
+                }
+            };
+            let baseline_cell = format!("{}{}", baseline_cell, change_cell);
+
             // Clean up the "None::" prefix and format properly
             let clean_operation = operation.strip_prefix("None::").unwrap_or(operation);
             let name = if clean_operation.len() > 33 {
@@ -360,30 +1061,38 @@ impl<C: Category + Clone + std::fmt::Debug + Eq + std::hash::Hash> ProfileReport
 
             if self.config.include_percentiles {
                 output.push_str(&format!(
-                    "{:<34} | {:>7} | {:>11} | {:>11} | {:>11} | {:>11} | {:>11} | {:>11}\n",
+                    "{:<34} | {:>7} | {:>11} |{} {:>11} | {:>11} | {:>11} | {:>11} | {:>11}{}{}\n",
                     format!("\"{}\"", name),
                     stats.count,
-                    self.config
-                        .time_format
-                        .format_time(stats.mean_time_micros()),
-                    self.config.time_format.format_time(stats.p50_micros()),
-                    self.config.time_format.format_time(stats.p95_micros()),
-                    self.config.time_format.format_time(stats.p99_micros()),
-                    self.config.time_format.format_time(stats.p999_micros()),
-                    self.config.time_format.format_time(stats.max_time_micros),
+                    format_timing(stats.mean_time_nanos()),
+                    ewma_cell,
+                    format_percentile(stats.p50_nanos()),
+                    format_percentile(stats.p95_nanos()),
+                    format_percentile(stats.p99_nanos()),
+                    format_percentile(stats.p999_nanos()),
+                    format_timing(stats.max_time_nanos),
+                    max_at_cell,
+                    baseline_cell,
                 ));
             } else {
                 output.push_str(&format!(
-                    "{:<34} | {:>7} | {:>11} | {:>11} | {:>11}\n",
+                    "{:<34} | {:>7} | {:>11} |{} {:>11} | {:>11}{}{}\n",
                     format!("\"{}\"", name),
                     stats.count,
-                    self.config
-                        .time_format
-                        .format_time(stats.mean_time_micros()),
-                    self.config.time_format.format_time(stats.min_time_micros),
-                    self.config.time_format.format_time(stats.max_time_micros),
+                    format_timing(stats.mean_time_nanos()),
+                    ewma_cell,
+                    format_timing(stats.min_time_nanos),
+                    format_timing(stats.max_time_nanos),
+                    max_at_cell,
+                    baseline_cell,
                 ));
             }
+
+            if dim {
+                // Move the reset before the newline so it doesn't leak
+                output.pop();
+                output.push_str("\x1b[0m\n");
+            }
         }
 
         output
@@ -418,7 +1127,7 @@ impl<C: Category + Clone + std::fmt::Debug + Eq + std::hash::Hash> ProfileReport
                 category,
                 stats.count,
                 stats.mean_time_micros(),
-                stats.min_time_micros,
+                stats.min_time_micros(),
                 stats.max_time_micros,
                 stats.p50_micros(),
                 stats.p95_micros(),
@@ -432,6 +1141,8 @@ impl<C: Category + Clone + std::fmt::Debug + Eq + std::hash::Hash> ProfileReport
     }
 
     /// Get operations sorted by a specific metric
+    ///
+    /// Operations with equal values are ordered by name.
     pub fn top_operations_by(
         &self,
         metric: SortMetric,
@@ -442,9 +1153,11 @@ impl<C: Category + Clone + std::fmt::Debug + Eq + std::hash::Hash> ProfileReport
             .iter()
             .map(|(k, v)| (k.clone(), v.clone()))
             .collect();
+        // Ties keep name order, as the sorts below are stable
+        operations.sort_by(|a, b| a.0.cmp(&b.0));
 
         match metric {
-            SortMetric::Count => {
+            SortMetric::Count | SortMetric::CallCount => {
                 operations.sort_by_key(|(_, stats)| std::cmp::Reverse(stats.count))
             }
             SortMetric::TotalTime => {
@@ -455,6 +1168,9 @@ impl<C: Category + Clone + std::fmt::Debug + Eq + std::hash::Hash> ProfileReport
                     .partial_cmp(&a.1.mean_time_micros())
                     .unwrap()
             }),
+            SortMetric::MaxTime => {
+                operations.sort_by_key(|(_, stats)| std::cmp::Reverse(stats.max_time_nanos))
+            }
             SortMetric::P50 => {
                 operations.sort_by_key(|(_, stats)| std::cmp::Reverse(stats.p50_micros()))
             }
@@ -472,6 +1188,49 @@ impl<C: Category + Clone + std::fmt::Debug + Eq + std::hash::Hash> ProfileReport
         operations.truncate(limit);
         operations
     }
+
+    /// Group operations by a label derived from each operation key
+    ///
+    /// A flexible complement to category grouping: pivot the report by any
+    /// rule without changing your `Operation` types. Operations within a group
+    /// are sorted by key.
+    ///
+    /// # Example
+    /// ```rust
+    /// use quantum_pulse::{ProfileCollector, ProfileReport};
+    ///
+    /// ProfileCollector::record("fetch_user", 120);
+    /// ProfileCollector::record("fetch_orders", 300);
+    /// ProfileCollector::record("update_user", 80);
+    ///
+    /// // Group by the verb before the first underscore
+    /// let report: ProfileReport = ProfileReport::generate();
+    /// let by_verb = report.grouped_by(|key| key.split('_').next().unwrap_or(key).to_string());
+    ///
+    /// for (verb, operations) in &by_verb {
+    ///     let calls: usize = operations.iter().map(|(_, stats)| stats.count).sum();
+    ///     println!("{}: {} operations, {} calls", verb, operations.len(), calls);
+    /// }
+    /// ```
+    pub fn grouped_by(
+        &self,
+        f: impl Fn(&str) -> String,
+    ) -> HashMap<String, Vec<(String, OperationStats)>> {
+        let mut groups: HashMap<String, Vec<(String, OperationStats)>> = HashMap::new();
+
+        for (key, stats) in &self.stats {
+            groups
+                .entry(f(key))
+                .or_default()
+                .push((key.clone(), stats.clone()));
+        }
+
+        for operations in groups.values_mut() {
+            operations.sort_by(|a, b| a.0.cmp(&b.0));
+        }
+
+        groups
+    }
 }
 
 impl<C: Category + Clone + std::fmt::Debug + Eq + std::hash::Hash> fmt::Display
@@ -482,19 +1241,105 @@ impl<C: Category + Clone + std::fmt::Debug + Eq + std::hash::Hash> fmt::Display
     }
 }
 
+/// A guard that prints a profiling report when dropped
+///
+/// Created via [`ProfileCollector::print_on_drop`]. Binding it at the top of
+/// `main` guarantees the report is rendered when the program leaves that scope.
+///
+/// # Example
+/// ```rust
+/// use quantum_pulse::{ProfileCollector, ReportConfig};
+///
+/// fn main() {
+///     let _guard = ProfileCollector::print_on_drop(ReportConfig::default());
+///     // ... profiled work ...
+/// } // report is printed here
+/// ```
+pub struct ReportGuard {
+    config: ReportConfig,
+    use_stderr: bool,
+}
+
+impl ReportGuard {
+    /// Create a guard that prints a report with the given configuration
+    pub fn new(config: ReportConfig) -> Self {
+        Self {
+            config,
+            use_stderr: false,
+        }
+    }
+
+    /// Print the report to stderr instead of stdout
+    pub fn use_stderr(mut self) -> Self {
+        self.use_stderr = true;
+        self
+    }
+
+    /// Render the report that will be printed on drop
+    pub fn render(&self) -> String {
+        if !ProfileCollector::has_data() {
+            return "No profiling data collected\n".to_string();
+        }
+
+        ProfileReport::<crate::category::DefaultCategory>::generate_with_config(self.config.clone())
+            .to_console_string()
+    }
+}
+
+impl Drop for ReportGuard {
+    fn drop(&mut self) {
+        let report = self.render();
+        if self.use_stderr {
+            eprint!("{}", report);
+        } else {
+            print!("{}", report);
+        }
+    }
+}
+
 /// Metrics for sorting operations
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum SortMetric {
     Count,
     TotalTime,
     MeanTime,
+    MaxTime,
     P50,
     P95,
     P99,
     P999,
+    /// Same as [`Self::Count`], kept for code written against the stub's
+    /// earlier variants
+    CallCount,
+}
+
+/// Where grouped reports split `category::operation` keys
+///
+/// Keys without a `::` have no category in either mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SplitMode {
+    /// At the first separator, so `db::users::select` is in category `db`
+    #[default]
+    First,
+    /// At the last separator, so `db::users::select` is in category `db::users`
+    Last,
+}
+
+impl SplitMode {
+    /// Split `key` into its category and operation parts
+    pub fn split<'a>(&self, key: &'a str) -> Option<(&'a str, &'a str)> {
+        match self {
+            SplitMode::First => key.split_once("::"),
+            SplitMode::Last => key.rsplit_once("::"),
+        }
+    }
 }
 
 /// Builder for creating customized reports
+///
+/// Builders are `Clone`, so a base configuration can be shared by several
+/// reports that differ in a single setting.
+#[derive(Debug, Clone)]
 pub struct ReportBuilder<C: Category = crate::category::DefaultCategory> {
     config: ReportConfig,
     _phantom: std::marker::PhantomData<C>,
@@ -509,57 +1354,153 @@ impl<C: Category + Clone + std::fmt::Debug + Eq + std::hash::Hash> ReportBuilder
         }
     }
 
+    /// Get the configuration the report will be built with
+    pub fn config(&self) -> &ReportConfig {
+        &self.config
+    }
+
     /// Set whether to include percentiles
     pub fn include_percentiles(mut self, include: bool) -> Self {
         self.config.include_percentiles = include;
         self
     }
 
-    /// Set whether to sort by time
-    pub fn sort_by_time(mut self, sort: bool) -> Self {
-        self.config.sort_by_time = sort;
+    /// Set a custom title for the report header
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.config.title = Some(title.into());
         self
     }
 
-    /// Sort by a specific percentile
-    pub fn sort_by_percentile(mut self, percentile: Percentile) -> Self {
-        self.config.sort_by_percentile = Some(percentile);
+    /// Force colored console output on or off instead of detecting a terminal
+    pub fn use_color(mut self, enabled: bool) -> Self {
+        self.config.use_color = Some(enabled);
         self
     }
 
-    /// Set minimum samples required
-    pub fn min_samples(mut self, min: u64) -> Self {
-        self.config.min_samples = min;
+    /// Sort a category as if its priority were `priority` (lower appears first)
+    pub fn category_priority(mut self, category_name: impl Into<String>, priority: i32) -> Self {
+        self.config
+            .category_priority_overrides
+            .insert(category_name.into(), priority);
         self
     }
 
-    /// Set whether to group by category
-    pub fn group_by_category(mut self, group: bool) -> Self {
-        self.config.group_by_category = group;
+    /// Set whether to include the moving average (EWMA) column
+    pub fn include_ewma(mut self, include: bool) -> Self {
+        self.config.include_ewma = include;
         self
     }
 
-    /// Set whether to include summary
-    pub fn include_summary(mut self, include: bool) -> Self {
-        self.config.include_summary = include;
+    /// Set whether to show when each operation's max was recorded
+    pub fn include_max_time_at(mut self, include: bool) -> Self {
+        self.config.include_max_time_at = include;
         self
     }
 
-    /// Set time format
-    pub fn time_format(mut self, format: TimeFormat) -> Self {
-        self.config.time_format = format;
+    /// List operations in their declared order within each category
+    ///
+    /// Operations are ordered by [`Operation::sort_hint`](crate::Operation::sort_hint)
+    /// first, and by the sort metric among equal hints. Off by default.
+    pub fn respect_operation_order(mut self, respect: bool) -> Self {
+        self.config.respect_operation_order = respect;
         self
     }
 
-    /// Set maximum operations to display
-    pub fn max_operations(mut self, max: usize) -> Self {
-        self.config.max_operations = max;
+    /// Set whether to list the report's categories at the top, with their colors
+    pub fn include_legend(mut self, include: bool) -> Self {
+        self.config.include_legend = include;
         self
     }
 
-    /// Set whether to include empty operations
-    pub fn include_empty(mut self, include: bool) -> Self {
-        self.config.include_empty = include;
+    /// Set where keys are split into category and operation, see [`SplitMode`]
+    pub fn split_mode(mut self, mode: SplitMode) -> Self {
+        self.config.split_mode = mode;
+        self
+    }
+
+    /// Set whether grouped reports show registered categories without operations
+    ///
+    /// See [`ProfileCollector::register_category`].
+    pub fn include_empty_categories(mut self, include: bool) -> Self {
+        self.config.include_empty_categories = include;
+        self
+    }
+
+    /// Set whether to mark operations against their declared latency baseline
+    pub fn include_baseline(mut self, include: bool) -> Self {
+        self.config.include_baseline = include;
+        self
+    }
+
+    /// Set how far the mean may exceed the baseline before it is flagged
+    ///
+    /// A factor of 1.5 flags operations more than 50% slower than expected.
+    /// Defaults to 1.0.
+    pub fn baseline_tolerance(mut self, factor: f64) -> Self {
+        self.config.baseline_tolerance = factor;
+        self
+    }
+
+    /// Set whether to sort by time
+    pub fn sort_by_time(mut self, sort: bool) -> Self {
+        self.config.sort_by_time = sort;
+        self
+    }
+
+    /// Sort by a specific percentile
+    pub fn sort_by_percentile(mut self, percentile: Percentile) -> Self {
+        self.config.sort_by_percentile = Some(percentile);
+        self
+    }
+
+    /// Set minimum samples required
+    pub fn min_samples(mut self, min: u64) -> Self {
+        self.config.min_samples = min;
+        self
+    }
+
+    /// Set whether to group by category
+    pub fn group_by_category(mut self, group: bool) -> Self {
+        self.config.group_by_category = group;
+        self
+    }
+
+    /// Set whether to include summary
+    pub fn include_summary(mut self, include: bool) -> Self {
+        self.config.include_summary = include;
+        self
+    }
+
+    /// Set time format
+    pub fn time_format(mut self, format: TimeFormat) -> Self {
+        self.config.time_format = format;
+        self
+    }
+
+    /// Set the number of decimal places in displayed durations
+    ///
+    /// By default microseconds get one decimal, milliseconds two and
+    /// seconds three.
+    pub fn time_precision(mut self, decimals: u8) -> Self {
+        self.config.time_precision = Some(decimals);
+        self
+    }
+
+    /// Set maximum operations to display
+    pub fn max_operations(mut self, max: usize) -> Self {
+        self.config.max_operations = max;
+        self
+    }
+
+    /// Set whether to include empty operations
+    pub fn include_empty(mut self, include: bool) -> Self {
+        self.config.include_empty = include;
+        self
+    }
+
+    /// Only include operations in the category with this name
+    pub fn only_category(mut self, category_name: impl Into<String>) -> Self {
+        self.config.only_category = Some(category_name.into());
         self
     }
 
@@ -592,6 +1533,128 @@ mod tests {
         assert_eq!(TimeFormat::Auto.format_time(1_500_000), "1.500 s");
     }
 
+    #[test]
+    fn test_format_time_precision() {
+        assert_eq!(TimeFormat::Auto.format_time_with(1500, Some(0)), "2 ms");
+        assert_eq!(
+            TimeFormat::Auto.format_time_with(1500, Some(4)),
+            "1.5000 ms"
+        );
+        assert_eq!(
+            TimeFormat::Microseconds.format_time_with(500, Some(4)),
+            "500.0000 µs"
+        );
+        assert_eq!(
+            TimeFormat::Seconds.format_time_with(1_500_000, Some(0)),
+            "2 s"
+        );
+        assert_eq!(TimeFormat::Auto.format_nanos_with(1234, Some(0)), "1 µs");
+        assert_eq!(
+            TimeFormat::Auto.format_nanos_with(1234, Some(4)),
+            "1.2340 µs"
+        );
+        // Whole nanoseconds can't be subdivided
+        assert_eq!(TimeFormat::Auto.format_nanos_with(500, Some(4)), "500 ns");
+        // No precision keeps the defaults
+        assert_eq!(TimeFormat::Auto.format_time_with(1500, None), "1.50 ms");
+        assert_eq!(TimeFormat::Auto.format_nanos_with(1500, None), "1.5 µs");
+
+        ProfileCollector::record("precise_op", 1234);
+        let output = ReportBuilder::<DefaultCategory>::new()
+            .time_precision(4)
+            .include_percentiles(false)
+            .group_by_category(false)
+            .build()
+            .to_console_string();
+        let row = output
+            .lines()
+            .find(|l| l.starts_with("\"precise_op\""))
+            .unwrap();
+        assert!(row.contains("1.2340 ms"));
+    }
+
+    #[test]
+    fn test_imported_summary_percentiles() {
+        ProfileCollector::record_summary("imported_op", 500, 120, 100, 250, 400, 900);
+        let stats = ProfileCollector::get_stats("imported_op").unwrap();
+        assert!(stats.is_imported());
+        assert_eq!(stats.count, 500);
+        assert_eq!(stats.mean_time_micros(), 120);
+
+        let output = ReportBuilder::<DefaultCategory>::new()
+            .time_format(TimeFormat::Microseconds)
+            .group_by_category(false)
+            .build()
+            .to_console_string();
+        let row = output
+            .lines()
+            .find(|l| l.starts_with("\"imported_op\""))
+            .unwrap();
+        for expected in ["100.0 µs", "250.0 µs", "400.0 µs", "900.0 µs"] {
+            assert!(row.contains(expected), "{expected} missing from {row}");
+        }
+    }
+
+    #[test]
+    fn test_estimated_percentiles_are_marked() {
+        ProfileCollector::record_summary("marked_imported_op", 10, 120, 100, 250, 400, 900);
+        ProfileCollector::record("marked_native_op", 100);
+
+        let mut stats = HashMap::new();
+        for key in ["marked_imported_op", "marked_native_op"] {
+            stats.insert(key.to_string(), ProfileCollector::get_stats(key).unwrap());
+        }
+        let output = ProfileReport::<DefaultCategory>::from_parts(
+            ReportConfig {
+                time_format: TimeFormat::Microseconds,
+                group_by_category: false,
+                ..Default::default()
+            },
+            stats,
+            HashMap::new(),
+            Vec::new(),
+        )
+        .to_console_string();
+
+        let imported = output
+            .lines()
+            .find(|l| l.starts_with("\"marked_imported_op\""))
+            .unwrap();
+        for expected in ["~100.0 µs", "~250.0 µs", "~400.0 µs", "~900.0 µs"] {
+            assert!(
+                imported.contains(expected),
+                "{expected} missing from {imported}"
+            );
+        }
+        let native = output
+            .lines()
+            .find(|l| l.starts_with("\"marked_native_op\""))
+            .unwrap();
+        assert!(native.contains("100.0 µs"));
+        assert!(!native.contains('~'), "{native}");
+    }
+
+    #[test]
+    fn test_format_nanos() {
+        assert_eq!(TimeFormat::Nanoseconds.format_nanos(500), "500 ns");
+        assert_eq!(TimeFormat::Nanoseconds.format_time(2), "2000 ns");
+        assert_eq!(TimeFormat::Auto.format_nanos(500), "500 ns");
+        assert_eq!(TimeFormat::Auto.format_nanos(1500), "1.5 µs");
+        assert_eq!(TimeFormat::Auto.format_nanos(1_500_000), "1.50 ms");
+    }
+
+    #[test]
+    fn test_report_shows_sub_microsecond_operations() {
+        ProfileCollector::clear_all();
+
+        ProfileCollector::record_nanos("fast_op", 500);
+
+        let report = ReportBuilder::<DefaultCategory>::new()
+            .group_by_category(false)
+            .build();
+        assert!(report.to_console_string().contains("500 ns"));
+    }
+
     #[test]
     fn test_report_generation() {
         ProfileCollector::clear_all();
@@ -608,6 +1671,864 @@ mod tests {
         assert!(summary.contains("Total calls: 3"));
     }
 
+    #[test]
+    fn test_category_icon_in_header() {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        enum IconCategory {
+            Metrics,
+            Plain,
+        }
+
+        impl Category for IconCategory {
+            fn get_name(&self) -> &str {
+                match self {
+                    IconCategory::Metrics => "Metrics",
+                    IconCategory::Plain => "Plain",
+                }
+            }
+
+            fn get_description(&self) -> &str {
+                ""
+            }
+
+            fn icon(&self) -> Option<&str> {
+                match self {
+                    IconCategory::Metrics => Some("📊"),
+                    IconCategory::Plain => None,
+                }
+            }
+        }
+
+        let mut stats = HashMap::new();
+        let mut op_stats = OperationStats::default();
+        op_stats.record(std::time::Duration::from_micros(10));
+        stats.insert("Metrics::collect".to_string(), op_stats.clone());
+        stats.insert("Plain::work".to_string(), op_stats);
+
+        let mut categories = HashMap::new();
+        categories.insert("Metrics::collect".to_string(), IconCategory::Metrics);
+        categories.insert("Plain::work".to_string(), IconCategory::Plain);
+
+        let report =
+            ProfileReport::from_parts(ReportConfig::default(), stats, categories, Vec::new());
+
+        let output = report.to_console_string();
+        assert!(output.contains("═══ 📊 Metrics ═══"));
+        assert!(output.contains("═══ Plain ═══"));
+    }
+
+    #[test]
+    fn test_report_with_count_only_operation() {
+        ProfileCollector::clear_all();
+
+        ProfileCollector::increment("cache_miss");
+        ProfileCollector::increment("cache_miss");
+
+        for include_percentiles in [true, false] {
+            let report = ReportBuilder::<DefaultCategory>::new()
+                .include_percentiles(include_percentiles)
+                .group_by_category(false)
+                .build();
+            let output = report.to_console_string();
+            assert!(output.contains("\"cache_miss\""));
+            assert!(output.contains("—"));
+        }
+
+        let csv = ProfileReport::<DefaultCategory>::generate().to_csv();
+        assert!(csv.contains("cache_miss,Uncategorized,2,0,0,0,"));
+    }
+
+    #[test]
+    fn test_report_scoped_to_category() {
+        ProfileCollector::clear_all();
+
+        ProfileCollector::record("Database::query", 1000);
+        ProfileCollector::record("Database::query", 3000);
+        ProfileCollector::record("Database::insert", 500);
+        ProfileCollector::record("Network::fetch", 2000);
+        ProfileCollector::record("uncategorized", 100);
+
+        let report = ProfileReport::<DefaultCategory>::for_category("Database");
+        assert_eq!(report.stats.len(), 2);
+        assert!(report.stats.contains_key("Database::query"));
+        assert!(!report.stats.contains_key("Network::fetch"));
+        assert!(!report.stats.contains_key("uncategorized"));
+
+        let summary = report.summary_stats();
+        assert_eq!(summary.total_operations, 3);
+        assert_eq!(summary.unique_operations, 2);
+        assert_eq!(summary.total_time_micros, 4500);
+
+        let output = ReportBuilder::<DefaultCategory>::new()
+            .only_category("Network")
+            .group_by_category(false)
+            .build()
+            .to_console_string();
+        assert!(output.contains("Network::fetch"));
+        assert!(!output.contains("Database::query"));
+    }
+
+    #[test]
+    fn test_csv_empty_stats_min_is_zero() {
+        let mut stats = HashMap::new();
+        stats.insert("never_recorded".to_string(), OperationStats::default());
+
+        let report = ProfileReport::<DefaultCategory>::from_parts(
+            ReportConfig {
+                include_empty: true,
+                ..ReportConfig::default()
+            },
+            stats,
+            HashMap::new(),
+            Vec::new(),
+        );
+
+        let csv = report.to_csv();
+        assert!(csv.contains("never_recorded,Uncategorized,0,0,0,0,"));
+        assert!(!csv.contains(&u64::MAX.to_string()));
+    }
+
+    #[test]
+    fn test_grouped_report_from_timer_data() {
+        use crate::{Operation, ProfileTimer};
+
+        ProfileCollector::clear_all();
+
+        #[derive(Debug)]
+        struct DatabaseCategory;
+
+        impl Category for DatabaseCategory {
+            fn get_name(&self) -> &str {
+                "Database"
+            }
+
+            fn get_description(&self) -> &str {
+                "Database operations"
+            }
+        }
+
+        #[derive(Debug)]
+        enum AppOperation {
+            Query,
+            Render,
+        }
+
+        impl Operation for AppOperation {
+            fn get_category(&self) -> &dyn Category {
+                match self {
+                    AppOperation::Query => &DatabaseCategory,
+                    AppOperation::Render => &crate::NoCategory,
+                }
+            }
+        }
+
+        drop(ProfileTimer::new(&AppOperation::Query));
+        drop(ProfileTimer::new(&AppOperation::Render));
+
+        let output = ProfileReport::<DefaultCategory>::generate().to_console_string();
+        assert!(output.contains("═══ Database ═══"));
+        assert!(output.contains("\"Database::Query\""));
+        assert!(output.contains("═══ Uncategorized ═══"));
+        assert!(output.contains("\"::Render\""));
+        assert!(output.find("Database").unwrap() < output.find("Uncategorized").unwrap());
+    }
+
+    #[test]
+    fn test_report_summary_is_a_snapshot() {
+        ProfileCollector::clear_all();
+
+        ProfileCollector::record("snapshot_op", 1000);
+        let report = ProfileReport::<DefaultCategory>::generate();
+        let before = report.quick_summary();
+
+        ProfileCollector::record("snapshot_op", 1000);
+        ProfileCollector::record("later_op", 5000);
+
+        let summary = report.summary_stats();
+        assert_eq!(summary.total_operations, 1);
+        assert_eq!(summary.unique_operations, 1);
+        assert_eq!(summary.total_time_micros, 1000);
+        assert_eq!(report.quick_summary(), before);
+        assert!(report.to_console_string().contains("Total Operations: 1"));
+    }
+
+    #[test]
+    fn test_report_collection_window() {
+        ProfileCollector::clear_all();
+
+        ProfileCollector::record("window_report_op", 1000);
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let report = ProfileReport::<DefaultCategory>::generate();
+
+        let summary = report.summary_stats();
+        assert!(summary.collection_window.is_some());
+        assert!(summary.ops_per_second > 0.0);
+
+        let output = report.to_console_string();
+        assert!(output.contains("Collection Window: 00:00:00"));
+        assert!(output.contains("Throughput: "));
+        assert!(output.contains(" ops/sec"));
+    }
+
+    #[test]
+    fn test_grouped_by() {
+        ProfileCollector::clear_all();
+
+        ProfileCollector::record("fetch_user", 100);
+        ProfileCollector::record("fetch_orders", 200);
+        ProfileCollector::record("fetch_orders", 200);
+        ProfileCollector::record("update_user", 50);
+
+        let report = ProfileReport::<DefaultCategory>::generate();
+        let groups = report.grouped_by(|key| key.split('_').next().unwrap_or(key).to_string());
+
+        assert_eq!(groups.len(), 2);
+        let fetch = &groups["fetch"];
+        assert_eq!(fetch.len(), 2);
+        assert_eq!(fetch[0].0, "fetch_orders");
+        assert_eq!(fetch[0].1.count, 2);
+        assert_eq!(fetch[1].0, "fetch_user");
+        assert_eq!(groups["update"].len(), 1);
+    }
+
+    #[test]
+    fn test_hex_to_ansi256() {
+        assert_eq!(hex_to_ansi256("#000000"), Some(16));
+        assert_eq!(hex_to_ansi256("#ffffff"), Some(231));
+        assert_eq!(hex_to_ansi256("#ff0000"), Some(196));
+        assert_eq!(hex_to_ansi256("#3498db"), Some(68));
+        assert_eq!(hex_to_ansi256("3498db"), None);
+        assert_eq!(hex_to_ansi256("#34"), None);
+        assert_eq!(hex_to_ansi256("#zzzzzz"), None);
+    }
+
+    #[test]
+    fn test_report_color() {
+        let mut stats = HashMap::new();
+        let mut recorded = OperationStats::default();
+        recorded.record(std::time::Duration::from_micros(10));
+        stats.insert("I/O::read".to_string(), recorded);
+        stats.insert("I/O::rare".to_string(), OperationStats::default());
+
+        let mut categories = HashMap::new();
+        categories.insert("I/O::read".to_string(), DefaultCategory::IO);
+        categories.insert("I/O::rare".to_string(), DefaultCategory::IO);
+
+        let config = ReportConfig {
+            min_samples: 1,
+            include_empty: true,
+            ..ReportConfig::default()
+        };
+
+        let plain = ProfileReport::from_parts(
+            ReportConfig {
+                use_color: Some(false),
+                ..config.clone()
+            },
+            stats.clone(),
+            categories.clone(),
+            Vec::new(),
+        );
+        assert!(!plain.to_console_string().contains('\x1b'));
+
+        let colored = ProfileReport::from_parts(
+            ReportConfig {
+                use_color: Some(true),
+                ..config
+            },
+            stats,
+            categories,
+            Vec::new(),
+        )
+        .to_console_string();
+        assert!(colored.contains("\x1b[1;38;5;68m═══ I/O ═══\x1b[0m"));
+        assert!(colored.contains("\x1b[2m\"I/O::rare\""));
+    }
+
+    #[test]
+    fn test_operation_detail() {
+        let mut timed = OperationStats::default();
+        for micros in [100, 200, 300, 400] {
+            timed.record(std::time::Duration::from_micros(micros));
+        }
+        let mut counted = OperationStats::default();
+        counted.increment();
+        let stats = HashMap::from([
+            ("I/O::detail_read".to_string(), timed.clone()),
+            ("I/O::detail_counted".to_string(), counted),
+        ]);
+        let categories = HashMap::from([("I/O::detail_read".to_string(), DefaultCategory::IO)]);
+        let report = ProfileReport::from_parts(
+            ReportConfig {
+                time_format: TimeFormat::Microseconds,
+                ..ReportConfig::default()
+            },
+            stats,
+            categories,
+            Vec::new(),
+        )
+        .with_baseline(HashMap::from([("I/O::detail_read".to_string(), timed)]));
+
+        let detail = report.operation_detail("I/O::detail_read").unwrap();
+        assert!(detail.starts_with("\"I/O::detail_read\"\n"));
+        for label in [
+            "Category:  I/O",
+            "Count:     4",
+            "Total:     1000.0 µs",
+            "Mean:      250.0 µs",
+            "Min:       100.0 µs",
+            "Max:       400.0 µs",
+            "Std Dev:",
+            "P50:",
+            "P95:",
+            "P99:",
+            "P99.9:",
+            "EWMA:",
+            "Max At:",
+            "Change:    +0%",
+        ] {
+            assert!(detail.contains(label), "{label} missing from\n{detail}");
+        }
+
+        let counted = report.operation_detail("I/O::detail_counted").unwrap();
+        assert!(counted.contains("Count:     1"));
+        assert!(counted.contains("Mean:      —"));
+        assert!(!counted.contains("Category:"));
+
+        assert!(report.operation_detail("I/O::missing").is_none());
+    }
+
+    #[test]
+    fn test_zero_durations_are_surfaced() {
+        let mut coarse = OperationStats::default();
+        for _ in 0..50 {
+            coarse.record(std::time::Duration::ZERO);
+        }
+        coarse.record(std::time::Duration::from_micros(1));
+        let mut measured = OperationStats::default();
+        measured.record(std::time::Duration::from_micros(100));
+        assert_eq!(coarse.zero_duration_count(), 50);
+        assert_eq!(measured.zero_duration_count(), 0);
+
+        let mut merged = coarse.clone();
+        merged.merge(&coarse);
+        assert_eq!(merged.zero_duration_count(), 100);
+
+        let stats = HashMap::from([
+            ("Compute::zero_coarse".to_string(), coarse),
+            ("Compute::zero_measured".to_string(), measured),
+        ]);
+        let report = ProfileReport::<DefaultCategory>::from_parts(
+            ReportConfig::default(),
+            stats,
+            HashMap::new(),
+            Vec::new(),
+        );
+
+        let output = report.to_console_string();
+        assert!(output.contains("Durations below clock resolution"));
+        assert!(output.contains("  Compute::zero_coarse: 50 of 51\n"));
+        assert!(!output.contains("Compute::zero_measured: 0"));
+        let detail = report.operation_detail("Compute::zero_coarse").unwrap();
+        assert!(detail.contains("Zero:      50 below clock resolution"));
+
+        // Nothing is shown when every duration was measurable
+        let detail = report.operation_detail("Compute::zero_measured").unwrap();
+        assert!(!detail.contains("Zero:"));
+    }
+
+    #[test]
+    fn test_split_mode_groups_hierarchical_keys() {
+        let mut recorded = OperationStats::default();
+        recorded.record(std::time::Duration::from_micros(10));
+        let stats = HashMap::from([
+            ("Split::Sub::deep".to_string(), recorded.clone()),
+            ("Split::shallow".to_string(), recorded),
+        ]);
+
+        let report_with = |split_mode: SplitMode| {
+            ProfileReport::<DefaultCategory>::from_parts(
+                ReportConfig {
+                    split_mode,
+                    include_summary: false,
+                    use_color: Some(false),
+                    ..ReportConfig::default()
+                },
+                stats.clone(),
+                HashMap::new(),
+                Vec::new(),
+            )
+            .to_console_string()
+        };
+
+        // The section under each category header, up to the next header
+        let section = |output: &str, name: &str| {
+            let header = format!("═══ {} ═══\n", name);
+            let (_, rest) = output.split_once(&header)?;
+            Some(rest.split("═══").next().unwrap_or(rest).to_string())
+        };
+
+        let first = report_with(SplitMode::First);
+        let split = section(&first, "Split").unwrap();
+        assert!(split.contains("Split::Sub::deep") && split.contains("Split::shallow"));
+        assert!(section(&first, "Split::Sub").is_none());
+
+        let last = report_with(SplitMode::Last);
+        let split = section(&last, "Split").unwrap();
+        assert!(split.contains("Split::shallow") && !split.contains("Split::Sub::deep"));
+        let sub = section(&last, "Split::Sub").unwrap();
+        assert!(sub.contains("Split::Sub::deep"));
+
+        assert_eq!(SplitMode::default(), SplitMode::First);
+        assert_eq!(SplitMode::Last.split("A::B::c"), Some(("A::B", "c")));
+        assert_eq!(SplitMode::First.split("A::B::c"), Some(("A", "B::c")));
+        assert_eq!(SplitMode::Last.split("plain"), None);
+    }
+
+    #[test]
+    fn test_registered_empty_categories() {
+        let registered = vec![
+            CategoryInfo {
+                name: "RegisteredIdle".to_string(),
+                description: "Never exercised in this run".to_string(),
+                color: None,
+                icon: None,
+                priority: 50,
+            },
+            CategoryInfo {
+                name: "RegisteredBusy".to_string(),
+                description: "Declared before recording".to_string(),
+                color: None,
+                icon: None,
+                priority: 40,
+            },
+        ];
+        let mut recorded = OperationStats::default();
+        recorded.record(std::time::Duration::from_micros(10));
+        let stats = HashMap::from([("RegisteredBusy::op".to_string(), recorded)]);
+
+        let report_with = |include_empty_categories: bool| {
+            ProfileReport::<DefaultCategory>::from_parts(
+                ReportConfig {
+                    include_empty_categories,
+                    include_summary: false,
+                    use_color: Some(false),
+                    ..ReportConfig::default()
+                },
+                stats.clone(),
+                HashMap::new(),
+                registered.clone(),
+            )
+            .to_console_string()
+        };
+
+        let output = report_with(true);
+        let idle = output.find("═══ RegisteredIdle ═══").unwrap();
+        let busy = output.find("═══ RegisteredBusy ═══").unwrap();
+        assert!(busy < idle, "priority order:\n{output}");
+        assert!(output[idle..].starts_with(
+            "═══ RegisteredIdle ═══\n  Never exercised in this run\n  No operations recorded\n"
+        ));
+        // Prefix-grouped operations pick up the registered metadata
+        assert!(output.contains("═══ RegisteredBusy ═══\n  Declared before recording\n"));
+
+        let output = report_with(false);
+        assert!(!output.contains("RegisteredIdle"));
+        assert!(output.contains("Declared before recording"));
+    }
+
+    #[test]
+    fn test_report_legend() {
+        let mut recorded = OperationStats::default();
+        recorded.record(std::time::Duration::from_micros(10));
+        let mut stats = HashMap::new();
+        let mut categories = HashMap::new();
+        for (key, category) in [
+            ("I/O::read", DefaultCategory::IO),
+            ("I/O::write", DefaultCategory::IO),
+            ("Compute::hash", DefaultCategory::Compute),
+            ("Memory::alloc", DefaultCategory::Memory),
+        ] {
+            stats.insert(key.to_string(), recorded.clone());
+            categories.insert(key.to_string(), category);
+        }
+        stats.insert("unregistered::op".to_string(), recorded);
+
+        let legend_of = |use_color: bool| {
+            let output = ProfileReport::from_parts(
+                ReportConfig {
+                    include_legend: true,
+                    include_summary: false,
+                    use_color: Some(use_color),
+                    ..ReportConfig::default()
+                },
+                stats.clone(),
+                categories.clone(),
+                Vec::new(),
+            )
+            .to_console_string();
+            let (_, legend) = output.split_once("═══ Legend ═══\n").unwrap();
+            let (legend, _) = legend.split_once("\n\n").unwrap();
+            legend.lines().map(str::to_string).collect::<Vec<_>>()
+        };
+
+        let plain = legend_of(false);
+        assert_eq!(
+            plain,
+            [
+                "  I/O (#3498db) — Input/Output operations (database, network, file system)",
+                "  Compute (#e74c3c) — Computational operations (calculations, data processing)",
+                "  Memory (#f39c12) — Memory operations (allocation, deallocation)",
+            ]
+        );
+
+        let colored = legend_of(true);
+        assert_eq!(colored.len(), 3);
+        assert!(colored[0].starts_with("  \x1b[38;5;68m██\x1b[0m I/O — "));
+
+        let without =
+            ProfileReport::from_parts(ReportConfig::default(), stats, categories, Vec::new());
+        assert!(!without.to_console_string().contains("Legend"));
+    }
+
+    #[test]
+    fn test_report_change_vs_baseline() {
+        let stats_with = |micros: u64| {
+            let mut stats = OperationStats::default();
+            stats.record(std::time::Duration::from_micros(micros));
+            stats
+        };
+        let current = HashMap::from([
+            ("Db::faster".to_string(), stats_with(92)),
+            ("Db::slower".to_string(), stats_with(112)),
+            ("Db::added".to_string(), stats_with(50)),
+            ("Db::same".to_string(), stats_with(100)),
+        ]);
+        let baseline = HashMap::from([
+            ("Db::faster".to_string(), stats_with(100)),
+            ("Db::slower".to_string(), stats_with(100)),
+            ("Db::same".to_string(), stats_with(100)),
+        ]);
+        let config = ReportConfig {
+            group_by_category: false,
+            use_color: Some(false),
+            ..ReportConfig::default()
+        };
+
+        let report = ProfileReport::<DefaultCategory>::from_parts(
+            config.clone(),
+            current.clone(),
+            HashMap::new(),
+            Vec::new(),
+        )
+        .with_baseline(baseline.clone());
+        assert_eq!(
+            report.change_vs_baseline("Db::faster").map(f64::round),
+            Some(-8.0)
+        );
+        assert!(report.change_vs_baseline("Db::added").is_none());
+
+        let output = report.to_console_string();
+        assert!(output.contains("Change"));
+        let row = |key: &str| {
+            output
+                .lines()
+                .find(|line| line.starts_with(&format!("\"{}\"", key)))
+                .unwrap()
+                .to_string()
+        };
+        assert!(row("Db::faster").ends_with("-8%"));
+        assert!(row("Db::slower").ends_with("+12%"));
+        assert!(row("Db::added").ends_with("—"));
+        assert!(row("Db::same").ends_with("|       0%"));
+
+        let colored = ProfileReport::<DefaultCategory>::from_parts(
+            ReportConfig {
+                use_color: Some(true),
+                ..config
+            },
+            current,
+            HashMap::new(),
+            Vec::new(),
+        )
+        .with_baseline(baseline)
+        .to_console_string();
+        assert!(colored.contains("\x1b[32m   ↓ -8%\x1b[0m"));
+        assert!(colored.contains("\x1b[31m  ↑ +12%\x1b[0m"));
+        assert!(colored.contains("|     = 0%"));
+        assert!(!colored.contains("↓ +0%"));
+    }
+
+    #[test]
+    fn test_respect_operation_order() {
+        use crate::{Operation, ProfileTimer};
+
+        #[derive(Debug)]
+        enum Lifecycle {
+            Validate,
+            Submit,
+            Execute,
+        }
+
+        impl Operation for Lifecycle {
+            fn to_str(&self) -> String {
+                format!("order_{:?}", self)
+            }
+
+            fn sort_hint(&self) -> i32 {
+                match self {
+                    Lifecycle::Validate => 1,
+                    Lifecycle::Submit => 2,
+                    Lifecycle::Execute => 3,
+                }
+            }
+        }
+
+        // Timers register the declared order
+        for operation in [Lifecycle::Validate, Lifecycle::Submit, Lifecycle::Execute] {
+            ProfileTimer::new(&operation).record();
+        }
+        assert_eq!(
+            ProfileCollector::operation_order("::order_Validate"),
+            Some(1)
+        );
+        assert_eq!(ProfileCollector::operation_order("::order_Submit"), Some(2));
+        assert_eq!(
+            ProfileCollector::operation_order("::order_Execute"),
+            Some(3)
+        );
+
+        let mut stats = HashMap::new();
+        for (key, micros) in [
+            ("::order_Validate", 10),
+            ("::order_Submit", 200),
+            ("::order_Execute", 300),
+        ] {
+            let mut op_stats = OperationStats::default();
+            op_stats.record(std::time::Duration::from_micros(micros));
+            stats.insert(key.to_string(), op_stats);
+        }
+
+        let row_order = |config: ReportConfig| -> Vec<String> {
+            ProfileReport::<DefaultCategory>::from_parts(
+                config,
+                stats.clone(),
+                HashMap::new(),
+                Vec::new(),
+            )
+            .to_console_string()
+            .lines()
+            .filter(|line| line.starts_with("\"::order_"))
+            .map(|line| line.split('"').nth(1).unwrap().to_string())
+            .collect()
+        };
+
+        // Slowest first by default
+        let builder = ReportBuilder::<DefaultCategory>::new()
+            .sort_by_time(true)
+            .group_by_category(false);
+        assert_eq!(
+            row_order(builder.config().clone()),
+            ["::order_Execute", "::order_Submit", "::order_Validate"]
+        );
+
+        let declared = builder.respect_operation_order(true);
+        assert_eq!(
+            row_order(declared.config().clone()),
+            ["::order_Validate", "::order_Submit", "::order_Execute"]
+        );
+    }
+
+    #[test]
+    fn test_category_priority_overrides() {
+        let mut stats = HashMap::new();
+        let mut op_stats = OperationStats::default();
+        op_stats.record(std::time::Duration::from_micros(10));
+        for key in ["I/O::read", "Compute::hash", "UI::render", "misc"] {
+            stats.insert(key.to_string(), op_stats.clone());
+        }
+
+        let mut categories = HashMap::new();
+        categories.insert("I/O::read".to_string(), DefaultCategory::IO);
+        categories.insert("Compute::hash".to_string(), DefaultCategory::Compute);
+        categories.insert("UI::render".to_string(), DefaultCategory::UI);
+
+        let header_order = |config: ReportConfig| -> Vec<String> {
+            ProfileReport::from_parts(config, stats.clone(), categories.clone(), Vec::new())
+                .to_console_string()
+                .lines()
+                .filter(|line| line.starts_with("═══ ") && !line.contains("Summary"))
+                .map(|line| line.trim_matches(|c| c == '═' || c == ' ').to_string())
+                .collect()
+        };
+
+        // Intrinsic priorities: I/O (1), Compute (2), UI (5), then uncategorized
+        assert_eq!(
+            header_order(ReportConfig::default()),
+            ["I/O", "Compute", "UI", "Uncategorized"]
+        );
+
+        let config = ReportBuilder::<DefaultCategory>::new()
+            .category_priority("UI", 0)
+            .category_priority("Uncategorized", 1)
+            .config;
+        assert_eq!(
+            header_order(config),
+            ["UI", "I/O", "Uncategorized", "Compute"]
+        );
+    }
+
+    #[test]
+    fn test_format_age() {
+        use std::time::Duration;
+
+        assert_eq!(format_age(Duration::from_millis(900)), "0s ago");
+        assert_eq!(format_age(Duration::from_secs(59)), "59s ago");
+        assert_eq!(format_age(Duration::from_secs(150)), "2m ago");
+        assert_eq!(format_age(Duration::from_secs(7300)), "2h ago");
+    }
+
+    #[test]
+    fn test_report_max_time_at_column() {
+        ProfileCollector::clear_all();
+
+        ProfileCollector::record("spiky_op", 10);
+        ProfileCollector::increment("counted_op");
+
+        for include_percentiles in [true, false] {
+            let output = ReportBuilder::<DefaultCategory>::new()
+                .include_percentiles(include_percentiles)
+                .include_max_time_at(true)
+                .group_by_category(false)
+                .build()
+                .to_console_string();
+            assert!(output.contains("         Max |      Max At\n"));
+            let spiky = output
+                .lines()
+                .find(|l| l.starts_with("\"spiky_op\""))
+                .unwrap();
+            assert!(spiky.ends_with(" |      0s ago"));
+            let counted = output
+                .lines()
+                .find(|l| l.starts_with("\"counted_op\""))
+                .unwrap();
+            assert!(counted.ends_with(" |           —"));
+        }
+
+        let output = ReportBuilder::<DefaultCategory>::new()
+            .build()
+            .to_console_string();
+        assert!(!output.contains("Max At"));
+    }
+
+    #[test]
+    fn test_report_baseline_column() {
+        use crate::{Operation, ProfileTimer};
+
+        #[derive(Debug)]
+        struct Fast;
+
+        impl Operation for Fast {
+            fn expected_micros(&self) -> Option<u64> {
+                Some(100)
+            }
+        }
+
+        ProfileCollector::reset_operation("::Slow");
+        ProfileCollector::reset_operation("::Fast");
+
+        // Timers register the operation's baseline
+        ProfileTimer::new(&Fast).record();
+        assert_eq!(ProfileCollector::expected_micros("::Fast"), Some(100));
+        ProfileCollector::reset_operation("::Fast");
+
+        ProfileCollector::set_expected_micros("::Slow", 100);
+        ProfileCollector::record("::Slow", 150);
+        ProfileCollector::record("::Fast", 50);
+        ProfileCollector::record("baseline_free_op", 50);
+
+        let report = ReportBuilder::<DefaultCategory>::new()
+            .include_baseline(true)
+            .group_by_category(false)
+            .build();
+        assert_eq!(report.exceeds_baseline("::Slow"), Some(true));
+        assert_eq!(report.exceeds_baseline("::Fast"), Some(false));
+        assert_eq!(report.exceeds_baseline("baseline_free_op"), None);
+        assert!(report.over_baseline().contains(&"::Slow".to_string()));
+        assert!(!report.over_baseline().contains(&"::Fast".to_string()));
+
+        let output = report.to_console_string();
+        assert!(output.contains("         Max | Base\n"));
+        let row = |key: &str| {
+            output
+                .lines()
+                .find(|l| l.starts_with(&format!("\"{}\"", key)))
+                .unwrap()
+                .to_string()
+        };
+        assert!(row("::Slow").ends_with(" |    ✗"));
+        assert!(row("::Fast").ends_with(" |    ✓"));
+        assert!(row("baseline_free_op").ends_with(" |    —"));
+
+        // A tolerance lets the slow operation pass
+        let tolerant = ReportBuilder::<DefaultCategory>::new()
+            .baseline_tolerance(2.0)
+            .build();
+        assert_eq!(tolerant.exceeds_baseline("::Slow"), Some(false));
+    }
+
+    #[test]
+    fn test_report_title() {
+        ProfileCollector::clear_all();
+        ProfileCollector::record("titled_op", 10);
+
+        let default_output = ReportBuilder::<DefaultCategory>::new()
+            .build()
+            .to_console_string();
+        assert!(default_output.starts_with(
+            "╔════════════════════════════════════════════════╗\n\
+             ║           PROFILING REPORT                     ║\n"
+        ));
+
+        let output = ReportBuilder::<DefaultCategory>::new()
+            .title("Trading System — Order Path")
+            .build()
+            .to_console_string();
+        assert!(output.contains("║          Trading System — Order Path           ║\n"));
+        assert!(!output.contains("PROFILING REPORT"));
+
+        // Titles longer than the default box widen it
+        let long_title = "X".repeat(60);
+        let output = ReportBuilder::<DefaultCategory>::new()
+            .title(long_title.clone())
+            .build()
+            .to_console_string();
+        assert!(output.contains(&format!("║  {}  ║", long_title)));
+    }
+
+    #[test]
+    fn test_report_ewma_column() {
+        ProfileCollector::clear_all();
+
+        ProfileCollector::record("ewma_op", 1000);
+
+        for include_percentiles in [true, false] {
+            let output = ReportBuilder::<DefaultCategory>::new()
+                .include_percentiles(include_percentiles)
+                .include_ewma(true)
+                .group_by_category(false)
+                .build()
+                .to_console_string();
+            assert!(output.contains("EWMA"));
+        }
+
+        let output = ReportBuilder::<DefaultCategory>::new()
+            .group_by_category(false)
+            .build()
+            .to_console_string();
+        assert!(!output.contains("EWMA"));
+    }
+
     #[test]
     fn test_report_builder() {
         ProfileCollector::clear_all();
@@ -628,6 +2549,20 @@ mod tests {
         assert_eq!(report.config.time_format, TimeFormat::Milliseconds);
     }
 
+    #[test]
+    fn test_report_builder_clone() {
+        let base = ReportBuilder::<DefaultCategory>::new()
+            .include_percentiles(true)
+            .min_samples(5);
+
+        let variant = base.clone().include_percentiles(false);
+
+        assert!(base.config().include_percentiles);
+        assert!(!variant.config().include_percentiles);
+        assert_eq!(variant.config().min_samples, 5);
+        assert!(format!("{:?}", base).contains("min_samples: 5"));
+    }
+
     #[test]
     fn test_csv_output() {
         ProfileCollector::clear_all();
@@ -660,4 +2595,121 @@ mod tests {
         assert_eq!(top_by_count[0].0, "op1");
         assert_eq!(top_by_count[0].1.count, 5);
     }
+
+    #[test]
+    fn test_report_output_is_deterministic() {
+        let build = |reverse: bool| {
+            let mut keys: Vec<String> = (0..20).map(|i| format!("Tie::op_{:02}", i)).collect();
+            if reverse {
+                keys.reverse();
+            }
+            let mut stats = HashMap::new();
+            for key in keys {
+                let mut op_stats = OperationStats::default();
+                op_stats.record(std::time::Duration::from_micros(100));
+                stats.insert(key, op_stats);
+            }
+            ProfileReport::<DefaultCategory>::from_parts(
+                ReportConfig {
+                    sort_by_time: true,
+                    use_color: Some(false),
+                    ..ReportConfig::default()
+                },
+                stats,
+                HashMap::new(),
+                Vec::new(),
+            )
+        };
+
+        // Timestamps and the collection window differ between any two reports
+        let first = build(false);
+        let mut second = build(true);
+        second.generated_at = first.generated_at;
+        second.summary = first.summary.clone();
+
+        assert_eq!(first.to_console_string(), second.to_console_string());
+        assert_eq!(first.to_csv(), second.to_csv());
+        let top = |report: &ProfileReport<DefaultCategory>| {
+            report
+                .top_operations_by(SortMetric::MeanTime, 5)
+                .into_iter()
+                .map(|(key, _)| key)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(top(&first), top(&second));
+        assert_eq!(top(&first)[0], "Tie::op_00");
+        assert_eq!(
+            first.summary_stats().slowest_operation.as_deref(),
+            Some("Tie::op_00")
+        );
+
+        // Tied rows are listed by name
+        let output = first.to_console_string();
+        let rows: Vec<&str> = output
+            .lines()
+            .filter(|line| line.starts_with("\"Tie::"))
+            .collect();
+        assert_eq!(rows.len(), 20);
+        assert!(rows.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_top_operations_by_each_metric() {
+        let mut stats = HashMap::new();
+        for (key, samples) in [
+            ("sort_many", vec![10; 10]),
+            ("sort_spiky", vec![50, 50, 400]),
+            ("sort_slow", vec![300]),
+        ] {
+            let mut op_stats = OperationStats::default();
+            for micros in samples {
+                op_stats.record(std::time::Duration::from_micros(micros));
+            }
+            stats.insert(key.to_string(), op_stats);
+        }
+        let report = ProfileReport::<DefaultCategory>::from_parts(
+            ReportConfig::default(),
+            stats,
+            HashMap::new(),
+            Vec::new(),
+        );
+
+        type Case = (SortMetric, &'static str, fn(&OperationStats) -> u64);
+        let cases: [Case; 9] = [
+            (SortMetric::Count, "sort_many", |s| s.count as u64),
+            (SortMetric::CallCount, "sort_many", |s| s.count as u64),
+            (SortMetric::TotalTime, "sort_spiky", |s| {
+                s.total_time_micros()
+            }),
+            (SortMetric::MeanTime, "sort_slow", |s| s.mean_time_micros()),
+            (SortMetric::MaxTime, "sort_spiky", |s| s.max_time_micros),
+            (SortMetric::P50, "sort_slow", |s| s.p50_micros()),
+            (SortMetric::P95, "sort_spiky", |s| s.p95_micros()),
+            (SortMetric::P99, "sort_spiky", |s| s.p99_micros()),
+            (SortMetric::P999, "sort_spiky", |s| s.p999_micros()),
+        ];
+        for (metric, top, value) in cases {
+            let sorted = report.top_operations_by(metric, 3);
+            assert_eq!(sorted[0].0, top, "{metric:?}");
+            assert!(
+                sorted.windows(2).all(|w| value(&w[0].1) >= value(&w[1].1)),
+                "{metric:?}"
+            );
+        }
+        assert_eq!(report.top_operations_by(SortMetric::MaxTime, 1).len(), 1);
+    }
+
+    #[test]
+    fn test_report_guard_without_data() {
+        ProfileCollector::clear_all();
+
+        let guard = ProfileCollector::print_on_drop(ReportConfig::default());
+        assert_eq!(guard.render(), "No profiling data collected\n");
+
+        ProfileCollector::record("guard_op", 1000);
+        assert!(guard.render().contains("guard_op"));
+
+        ProfileCollector::clear_all();
+        assert_eq!(guard.render(), "No profiling data collected\n");
+    }
 }
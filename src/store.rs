@@ -0,0 +1,434 @@
+//! # Stats Storage
+//!
+//! Pluggable backends holding the collector's per-operation statistics.
+//!
+//! The collector stores everything through the [`StatsStore`] trait. The
+//! default [`HashMapStore`] keeps a single `RwLock<HashMap>`; with the
+//! `dashmap` feature, [`DashMapStore`] shards the map to reduce contention
+//! when many threads record at once. Install a backend with
+//! [`ProfileCollector::set_stats_store`](crate::ProfileCollector::set_stats_store)
+//! before the first recording.
+
+use crate::collector::{intern, InternedKey, OperationStats};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
+
+/// Storage backend for per-operation statistics
+///
+/// Each method must be atomic for the key it touches. [`Self::rename`]
+/// spans two keys; see there for when it is atomic.
+pub trait StatsStore: Send + Sync {
+    /// Apply `update` to the stats under `key`, first inserting `create()`
+    /// if the key is new
+    ///
+    /// Returns `true` if the key was inserted.
+    fn record(
+        &self,
+        key: &str,
+        create: &mut dyn FnMut() -> OperationStats,
+        update: &mut dyn FnMut(&mut OperationStats),
+    ) -> bool;
+
+    /// Apply `update` to the stats under `key`, returning `false` if there are none
+    fn update(&self, key: &str, update: &mut dyn FnMut(&mut OperationStats)) -> bool;
+
+    /// Get a copy of the stats under `key`
+    fn get(&self, key: &str) -> Option<OperationStats>;
+
+    /// Check whether there are stats under `key`
+    ///
+    /// The default clones the stats via [`Self::get`]; backends should
+    /// override it with a plain lookup.
+    fn contains(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Remove and return the stats under `key`
+    fn remove(&self, key: &str) -> Option<OperationStats>;
+
+    /// Move the stats under `from` to `to`, merging them into stats already
+    /// under `to` if `merge` is set
+    ///
+    /// Returns `false` and changes nothing if `from` has no stats, or if
+    /// `to` has some and `merge` is off. The default is built from
+    /// [`Self::contains`], [`Self::remove`] and [`Self::record`], so a
+    /// recording racing with it can land in between; [`HashMapStore`]
+    /// overrides it to move the stats under a single lock.
+    fn rename(&self, from: &str, to: &str, merge: bool) -> bool {
+        if !merge && self.contains(to) {
+            return false;
+        }
+        let Some(moved) = self.remove(from) else {
+            return false;
+        };
+
+        // Whichever closure runs first takes the stats
+        let pending = Cell::new(Some(moved));
+        self.record(
+            to,
+            &mut || pending.take().unwrap_or_default(),
+            &mut |existing| {
+                if let Some(moved) = pending.take() {
+                    existing.merge(&moved);
+                }
+            },
+        );
+        true
+    }
+
+    /// Visit every stored operation
+    fn for_each(&self, visit: &mut dyn FnMut(&str, &OperationStats));
+
+    /// Remove all stats
+    fn clear(&self);
+
+    /// Get the number of stored operations
+    ///
+    /// The default counts via [`Self::for_each`]; backends should override
+    /// it with their own length.
+    fn len(&self) -> usize {
+        let mut len = 0;
+        self.for_each(&mut |_, _| len += 1);
+        len
+    }
+
+    /// Check whether no operations are stored
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the number of writes that had to wait for another thread's lock
+    /// since the store was last cleared
+    ///
+    /// Backends without a single lock to measure return 0.
+    fn lock_contention_count(&self) -> u64 {
+        0
+    }
+
+    /// Get every stored key, without copying any stats
+    fn keys(&self) -> Vec<String> {
+        let mut keys = Vec::new();
+        self.for_each(&mut |key, _| keys.push(key.to_string()));
+        keys
+    }
+
+    /// Get a copy of every stored operation
+    fn all(&self) -> HashMap<String, OperationStats> {
+        let mut all = HashMap::new();
+        self.for_each(&mut |key, stats| {
+            all.insert(key.to_string(), stats.clone());
+        });
+        all
+    }
+}
+
+/// Default backend: a single `RwLock<HashMap>` keyed by interned keys
+#[derive(Debug, Default)]
+pub struct HashMapStore {
+    stats: RwLock<HashMap<InternedKey, OperationStats>>,
+    /// Writes that found the lock held and had to block
+    contended: AtomicU64,
+}
+
+impl HashMapStore {
+    /// Lock the map for reading
+    ///
+    /// A panic while the lock was held only poisons it; the stats are still
+    /// consistent, so recording carries on instead of being lost for good.
+    fn read(&self) -> RwLockReadGuard<'_, HashMap<InternedKey, OperationStats>> {
+        self.stats.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Lock the map for writing, see [`Self::read`]
+    ///
+    /// Tries the lock first so that writes which have to block can be
+    /// counted, see [`StatsStore::lock_contention_count`].
+    fn write(&self) -> RwLockWriteGuard<'_, HashMap<InternedKey, OperationStats>> {
+        match self.stats.try_write() {
+            Ok(stats) => stats,
+            Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+            Err(TryLockError::WouldBlock) => {
+                self.contended.fetch_add(1, Ordering::Relaxed);
+                self.stats.write().unwrap_or_else(PoisonError::into_inner)
+            }
+        }
+    }
+}
+
+impl StatsStore for HashMapStore {
+    fn record(
+        &self,
+        key: &str,
+        create: &mut dyn FnMut() -> OperationStats,
+        update: &mut dyn FnMut(&mut OperationStats),
+    ) -> bool {
+        let mut stats = self.write();
+        if let Some(existing) = stats.get_mut(key) {
+            update(existing);
+            false
+        } else {
+            let mut new_stats = create();
+            update(&mut new_stats);
+            stats.insert(intern(key), new_stats);
+            true
+        }
+    }
+
+    fn update(&self, key: &str, update: &mut dyn FnMut(&mut OperationStats)) -> bool {
+        match self.write().get_mut(key) {
+            Some(existing) => {
+                update(existing);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<OperationStats> {
+        self.read().get(key).cloned()
+    }
+
+    fn contains(&self, key: &str) -> bool {
+        self.read().contains_key(key)
+    }
+
+    fn remove(&self, key: &str) -> Option<OperationStats> {
+        self.write().remove(key)
+    }
+
+    fn rename(&self, from: &str, to: &str, merge: bool) -> bool {
+        let mut stats = self.write();
+        if !merge && stats.contains_key(to) {
+            return false;
+        }
+        let Some(moved) = stats.remove(from) else {
+            return false;
+        };
+        match stats.get_mut(to) {
+            Some(existing) => existing.merge(&moved),
+            None => {
+                stats.insert(intern(to), moved);
+            }
+        }
+        true
+    }
+
+    fn for_each(&self, visit: &mut dyn FnMut(&str, &OperationStats)) {
+        for (key, operation_stats) in self.read().iter() {
+            visit(key, operation_stats);
+        }
+    }
+
+    fn clear(&self) {
+        self.write().clear();
+        self.contended.store(0, Ordering::Relaxed);
+    }
+
+    fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    fn lock_contention_count(&self) -> u64 {
+        self.contended.load(Ordering::Relaxed)
+    }
+}
+
+/// Sharded backend for heavily concurrent recording (requires the `dashmap` feature)
+///
+/// Keys may live in different shards, so [`StatsStore::rename`] keeps the
+/// default, non-atomic implementation here.
+#[cfg(feature = "dashmap")]
+#[derive(Debug, Default)]
+pub struct DashMapStore {
+    stats: dashmap::DashMap<InternedKey, OperationStats>,
+}
+
+#[cfg(feature = "dashmap")]
+impl StatsStore for DashMapStore {
+    fn record(
+        &self,
+        key: &str,
+        create: &mut dyn FnMut() -> OperationStats,
+        update: &mut dyn FnMut(&mut OperationStats),
+    ) -> bool {
+        // Existing keys only take a shard lock, without interning
+        if let Some(mut existing) = self.stats.get_mut(key) {
+            update(&mut existing);
+            return false;
+        }
+
+        let mut created = false;
+        let mut entry = self.stats.entry(intern(key)).or_insert_with(|| {
+            created = true;
+            create()
+        });
+        update(&mut entry);
+        created
+    }
+
+    fn update(&self, key: &str, update: &mut dyn FnMut(&mut OperationStats)) -> bool {
+        match self.stats.get_mut(key) {
+            Some(mut existing) => {
+                update(&mut existing);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<OperationStats> {
+        self.stats.get(key).map(|stats| stats.clone())
+    }
+
+    fn contains(&self, key: &str) -> bool {
+        self.stats.contains_key(key)
+    }
+
+    fn remove(&self, key: &str) -> Option<OperationStats> {
+        self.stats.remove(key).map(|(_, stats)| stats)
+    }
+
+    fn for_each(&self, visit: &mut dyn FnMut(&str, &OperationStats)) {
+        for entry in self.stats.iter() {
+            visit(entry.key(), entry.value());
+        }
+    }
+
+    fn clear(&self) {
+        self.stats.clear();
+    }
+
+    fn len(&self) -> usize {
+        self.stats.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn record(store: &dyn StatsStore, key: &str, micros: u64) -> bool {
+        store.record(key, &mut OperationStats::default, &mut |stats| {
+            stats.record(Duration::from_micros(micros))
+        })
+    }
+
+    fn exercise(store: &dyn StatsStore) {
+        assert!(record(store, "store_a", 100));
+        assert!(!record(store, "store_a", 300));
+        assert!(record(store, "store_b", 50));
+
+        let a = store.get("store_a").unwrap();
+        assert_eq!(a.count, 2);
+        assert_eq!(a.max_time_micros, 300);
+
+        assert!(store.update("store_b", &mut |stats| stats.increment()));
+        assert!(!store.update("store_missing", &mut |stats| stats.increment()));
+        assert_eq!(store.get("store_b").unwrap().count, 2);
+
+        assert!(store.contains("store_a"));
+        assert!(!store.contains("store_missing"));
+        let mut keys = store.keys();
+        keys.sort();
+        assert_eq!(keys, ["store_a", "store_b"]);
+
+        let all = store.all();
+        assert_eq!(all.len(), 2);
+        assert!(all.contains_key("store_a") && all.contains_key("store_b"));
+
+        assert!(store.rename("store_b", "store_c", false));
+        assert!(!store.contains("store_b"));
+        assert!(!store.rename("store_c", "store_a", false));
+        assert!(!store.rename("store_missing", "store_d", true));
+        assert!(store.rename("store_c", "store_a", true));
+        assert_eq!(store.get("store_a").unwrap().count, 4);
+        assert!(record(store, "store_b", 50));
+        assert_eq!(store.get("store_b").unwrap().count, 1);
+
+        assert_eq!(store.remove("store_b").unwrap().count, 1);
+        assert!(store.get("store_b").is_none());
+
+        store.clear();
+        assert!(store.all().is_empty());
+    }
+
+    #[test]
+    fn test_hash_map_store() {
+        exercise(&HashMapStore::default());
+    }
+
+    #[test]
+    fn test_hash_map_store_interns_keys() {
+        let store = HashMapStore::default();
+        record(&store, "store_interned", 10);
+
+        let stats = store.stats.read().unwrap();
+        let (key, _) = stats.get_key_value("store_interned").unwrap();
+        assert!(key.ptr_eq(&intern("store_interned")));
+    }
+
+    #[test]
+    fn test_hash_map_store_survives_poisoning() {
+        let store = HashMapStore::default();
+        record(&store, "store_poisoned", 10);
+        let poisoned = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            store.update("store_poisoned", &mut |_| panic!("poison the lock"));
+        }));
+        assert!(poisoned.is_err());
+        assert!(store.stats.is_poisoned());
+
+        record(&store, "store_poisoned", 20);
+        assert_eq!(store.get("store_poisoned").unwrap().count, 2);
+    }
+
+    #[test]
+    fn test_hash_map_store_counts_contention() {
+        let store = HashMapStore::default();
+        record(&store, "store_contended", 10);
+        assert_eq!(store.lock_contention_count(), 0);
+
+        // A writer arriving while the lock is held has to block
+        let held = store.stats.write().unwrap();
+        std::thread::scope(|scope| {
+            let writer = scope.spawn(|| record(&store, "store_contended", 20));
+            while store.contended.load(Ordering::Relaxed) == 0 {
+                std::thread::yield_now();
+            }
+            drop(held);
+            writer.join().unwrap();
+        });
+        assert_eq!(store.lock_contention_count(), 1);
+        assert_eq!(store.get("store_contended").unwrap().count, 2);
+
+        // Many threads hammering one store only ever add to the count, at
+        // most once per write
+        let before = store.lock_contention_count();
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    for micros in 0..1000 {
+                        record(&store, "store_contended", micros);
+                    }
+                });
+            }
+        });
+        let contended = store
+            .lock_contention_count()
+            .checked_sub(before)
+            .expect("contention count decreased");
+        assert!(contended <= 8000, "{contended} contended writes");
+        assert_eq!(store.get("store_contended").unwrap().count, 8002);
+
+        store.clear();
+        assert_eq!(store.lock_contention_count(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "dashmap")]
+    fn test_dash_map_store() {
+        exercise(&DashMapStore::default());
+    }
+}
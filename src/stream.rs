@@ -0,0 +1,273 @@
+//! # Stream Recorder
+//!
+//! Appends every recording as a JSON line to a user-provided sink, preserving
+//! the timeline that in-memory aggregation discards.
+
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::collector::ProfileCollector;
+
+/// Flush after this many buffered records
+const FLUSH_EVERY_RECORDS: usize = 256;
+
+/// Flush at least this often while records keep arriving
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A sink plus the records buffered for it since the last flush
+struct StreamSink {
+    writer: Box<dyn Write + Send>,
+    buffer: Vec<u8>,
+    /// Number of records in `buffer`
+    pending: usize,
+    last_flush: Instant,
+}
+
+impl StreamSink {
+    /// Write out buffered records, dropping them if the sink fails
+    fn flush(&mut self) {
+        let result = self
+            .writer
+            .write_all(&self.buffer)
+            .and_then(|_| self.writer.flush());
+        if result.is_err() {
+            DROPPED_RECORDS.fetch_add(self.pending as u64, Ordering::Relaxed);
+        }
+
+        self.buffer.clear();
+        self.pending = 0;
+        self.last_flush = Instant::now();
+    }
+}
+
+/// The active sink, if any
+///
+/// A `Mutex` rather than the usual `RwLock`: every access mutates the buffer,
+/// and the sink is only required to be `Send`.
+static STREAM_SINK: LazyLock<Arc<Mutex<Option<StreamSink>>>> =
+    LazyLock::new(|| Arc::new(Mutex::new(None)));
+
+/// Fast check so recording skips the lock when no sink is configured
+static STREAM_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Number of records lost to write or flush errors
+static DROPPED_RECORDS: AtomicU64 = AtomicU64::new(0);
+
+/// Whether `ts` counts from the collection start rather than the Unix epoch
+static RELATIVE_TIMESTAMPS: AtomicBool = AtomicBool::new(true);
+
+/// Choose between collection-relative and Unix epoch timestamps
+pub(crate) fn set_relative_timestamps(relative: bool) {
+    RELATIVE_TIMESTAMPS.store(relative, Ordering::Relaxed);
+}
+
+/// Replace the active sink, flushing the previous one
+pub(crate) fn set_sink(sink: Option<Box<dyn Write + Send>>) {
+    if let Ok(mut current) = STREAM_SINK.lock() {
+        if let Some(previous) = current.as_mut() {
+            previous.flush();
+        }
+
+        STREAM_ENABLED.store(sink.is_some(), Ordering::Release);
+        *current = sink.map(|writer| StreamSink {
+            writer,
+            buffer: Vec::new(),
+            pending: 0,
+            last_flush: Instant::now(),
+        });
+    }
+}
+
+/// Flush buffered records to the active sink
+pub(crate) fn flush() {
+    if let Ok(mut current) = STREAM_SINK.lock() {
+        if let Some(sink) = current.as_mut() {
+            sink.flush();
+        }
+    }
+}
+
+/// Number of records dropped because the sink returned an error
+pub(crate) fn dropped_records() -> u64 {
+    DROPPED_RECORDS.load(Ordering::Relaxed)
+}
+
+/// Forget records dropped so far
+pub(crate) fn reset_dropped_records() {
+    DROPPED_RECORDS.store(0, Ordering::Relaxed);
+}
+
+/// Append one recording to the active sink, if any
+pub(crate) fn write_record(key: &str, duration_nanos: u64) {
+    if !STREAM_ENABLED.load(Ordering::Acquire) {
+        return;
+    }
+
+    // The first recording is written before it marks the collection start
+    let epoch = if RELATIVE_TIMESTAMPS.load(Ordering::Relaxed) {
+        ProfileCollector::started_at()
+    } else {
+        Some(UNIX_EPOCH)
+    };
+    let ts = epoch.map_or(0, |epoch| {
+        SystemTime::now()
+            .duration_since(epoch)
+            .unwrap_or_default()
+            .as_micros()
+    });
+    let line = format!(
+        "{{\"key\":\"{}\",\"micros\":{}.{:03},\"ts\":{}}}\n",
+        escape_json(key),
+        duration_nanos / 1000,
+        duration_nanos % 1000,
+        ts
+    );
+
+    if let Ok(mut current) = STREAM_SINK.lock() {
+        if let Some(sink) = current.as_mut() {
+            sink.buffer.extend_from_slice(line.as_bytes());
+            sink.pending += 1;
+            if sink.pending >= FLUSH_EVERY_RECORDS || sink.last_flush.elapsed() >= FLUSH_INTERVAL {
+                sink.flush();
+            }
+        }
+    }
+}
+
+/// Escape a string for use inside a JSON string literal
+fn escape_json(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+
+    /// A cloneable in-memory sink for inspecting streamed output
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl SharedBuffer {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// A sink that rejects every write
+    struct FailingSink;
+
+    impl Write for FailingSink {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::other("sink closed"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Err(io::Error::other("sink closed"))
+        }
+    }
+
+    #[test]
+    fn test_escape_json() {
+        assert_eq!(escape_json("IO::read"), "IO::read");
+        assert_eq!(escape_json("a\"b\\c\n"), "a\\\"b\\\\c\\n");
+        assert_eq!(escape_json("\u{1}"), "\\u0001");
+    }
+
+    #[test]
+    fn test_stream_sink_receives_json_lines() {
+        ProfileCollector::clear_all();
+
+        let buffer = SharedBuffer::default();
+        ProfileCollector::set_stream_sink(Box::new(buffer.clone()));
+
+        ProfileCollector::record("stream_op", 12);
+        ProfileCollector::record_nanos("stream \"quoted\"", 1500);
+        ProfileCollector::flush_stream();
+        ProfileCollector::clear_stream_sink();
+
+        let contents = buffer.contents();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("{\"key\":\"stream_op\",\"micros\":12.000,\"ts\":"));
+        assert!(lines[1].starts_with("{\"key\":\"stream \\\"quoted\\\"\",\"micros\":1.500,"));
+
+        // The in-memory aggregate is still maintained
+        assert_eq!(ProfileCollector::get_stats("stream_op").unwrap().count, 1);
+    }
+
+    #[test]
+    fn test_stream_timestamps_are_relative() {
+        ProfileCollector::clear_all();
+
+        let buffer = SharedBuffer::default();
+        ProfileCollector::set_stream_sink(Box::new(buffer.clone()));
+        for _ in 0..5 {
+            ProfileCollector::record("relative_ts_op", 10);
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        ProfileCollector::clear_stream_sink();
+
+        // Other tests share the sink and the collection start, so only look
+        // at this test's records
+        let timestamps: Vec<u128> = buffer
+            .contents()
+            .lines()
+            .filter(|line| line.contains("\"relative_ts_op\""))
+            .map(|line| {
+                let (_, ts) = line.rsplit_once("\"ts\":").unwrap();
+                ts.trim_end_matches('}').parse().unwrap()
+            })
+            .collect();
+        assert_eq!(timestamps.len(), 5);
+        // Near the start of the test run rather than 1970
+        assert!(timestamps[0] < 60_000_000);
+        assert!(timestamps.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn test_stream_sink_errors_are_counted() {
+        ProfileCollector::clear_all();
+
+        let before = ProfileCollector::stream_dropped_records();
+        ProfileCollector::set_stream_sink(Box::new(FailingSink));
+
+        for _ in 0..10 {
+            ProfileCollector::record("failing_stream_op", 10);
+        }
+        ProfileCollector::clear_stream_sink();
+
+        assert_eq!(ProfileCollector::stream_dropped_records() - before, 10);
+        assert_eq!(
+            ProfileCollector::get_stats("failing_stream_op")
+                .unwrap()
+                .count,
+            10
+        );
+    }
+}
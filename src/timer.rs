@@ -4,17 +4,90 @@
 //! Timers automatically record their duration when dropped.
 
 use std::cell::RefCell;
-use std::time::Instant;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
 
-use crate::collector::ProfileCollector;
+use crate::category::{Category, CategoryInfo};
+use crate::collector::{admit_key, is_admitted, ProfileCollector, OVERFLOW_KEY};
 use crate::operation::Operation;
+use crate::store::StatsStore;
+
+/// Timers currently on the call stack and the subset paused by `pause_stack`
+#[derive(Default)]
+struct TimerStack {
+    active: Vec<usize>,
+    paused: HashSet<usize>,
+    /// Time spent in `segment!`s, by the enclosing timer's ID
+    excluded: HashMap<usize, Duration>,
+    /// Operation keys by timer ID, kept only while call paths are captured
+    keys: HashMap<usize, String>,
+}
 
 thread_local! {
-    /// Thread-local stack of timers, storing unique IDs
-    static TIMER_STACK: RefCell<Vec<usize>> = RefCell::new(Vec::new());
+    /// Thread-local timer stack used by synchronous code
+    ///
+    /// A future running on a multi-threaded runtime can resume on a different
+    /// thread after each `.await`, so this stack cannot follow async timers.
+    /// Enable the `tokio` feature to track them per task instead.
+    static TIMER_STACK: RefCell<TimerStack> = RefCell::new(TimerStack::default());
+}
+
+#[cfg(feature = "tokio")]
+tokio::task_local! {
+    /// Task-local timer stack that follows a future across thread hops
+    static TASK_TIMER_STACK: RefCell<TimerStack>;
+}
+
+/// Run `f` against the timer stack for the current task, or the current
+/// thread when not inside a task-local scope
+fn with_timer_stack<R>(f: impl FnOnce(&mut TimerStack) -> R) -> R {
+    #[cfg(feature = "tokio")]
+    if TASK_TIMER_STACK.try_with(|_| ()).is_ok() {
+        return TASK_TIMER_STACK.with(|stack| f(&mut stack.borrow_mut()));
+    }
 
-    /// Thread-local set of paused timer IDs (for stack-based pausing)
-    static PAUSED_TIMERS: RefCell<std::collections::HashSet<usize>> = RefCell::new(std::collections::HashSet::new());
+    TIMER_STACK.with(|stack| f(&mut stack.borrow_mut()))
+}
+
+/// Push a timer onto the current stack
+fn register_timer(timer_id: usize) {
+    with_timer_stack(|stack| stack.active.push(timer_id));
+}
+
+/// Remove a timer from the current stack
+fn unregister_timer(timer_id: usize) {
+    with_timer_stack(|stack| stack.active.retain(|&id| id != timer_id));
+}
+
+/// Forget any stack-based pause, excluded segment time and key kept for a timer
+fn clear_timer_pause(timer_id: usize) {
+    with_timer_stack(|stack| {
+        stack.paused.remove(&timer_id);
+        stack.excluded.remove(&timer_id);
+        stack.keys.remove(&timer_id);
+    });
+}
+
+/// Keys of the timer and the timers enclosing it, outermost first
+///
+/// `None` unless the keys of the timer and of every enclosing timer were
+/// kept when they started, since a path with gaps would attribute time to
+/// the wrong parent.
+fn call_path(timer_id: usize) -> Option<Vec<String>> {
+    with_timer_stack(|stack| {
+        let key = stack.keys.get(&timer_id)?;
+        // A dropped timer is already off the stack, so all of it encloses it
+        let enclosing = match stack.active.iter().position(|&id| id == timer_id) {
+            Some(position) => &stack.active[..position],
+            None => &stack.active[..],
+        };
+        let mut path = enclosing
+            .iter()
+            .map(|id| stack.keys.get(id).cloned())
+            .collect::<Option<Vec<String>>>()?;
+        path.push(key.clone());
+        Some(path)
+    })
 }
 
 /// Global counter for generating unique timer IDs
@@ -26,16 +99,10 @@ static TIMER_ID_COUNTER: std::sync::atomic::AtomicUsize = std::sync::atomic::Ato
 /// Timers created after this call will not be paused unless they are on the stack
 /// when pause_stack is called again.
 pub fn pause_stack() {
-    TIMER_STACK.with(|stack| {
-        PAUSED_TIMERS.with(|paused| {
-            let stack_ids = stack.borrow();
-            let mut paused_set = paused.borrow_mut();
-
-            // Mark all timers currently on the stack as paused
-            for &timer_id in stack_ids.iter() {
-                paused_set.insert(timer_id);
-            }
-        });
+    with_timer_stack(|stack| {
+        // Mark all timers currently on the stack as paused
+        let TimerStack { active, paused, .. } = stack;
+        paused.extend(active.iter().copied());
     });
 }
 
@@ -44,28 +111,166 @@ pub fn pause_stack() {
 /// This removes timers from the paused set based on the current stack.
 /// Only timers currently on the stack will be resumed.
 pub fn unpause_stack() {
-    TIMER_STACK.with(|stack| {
-        PAUSED_TIMERS.with(|paused| {
-            let stack_ids = stack.borrow();
-            let mut paused_set = paused.borrow_mut();
+    with_timer_stack(|stack| {
+        // Remove timers currently on the stack from the paused set
+        let TimerStack { active, paused, .. } = stack;
+        for timer_id in active.iter() {
+            paused.remove(timer_id);
+        }
+    });
+}
 
-            // Remove timers currently on the stack from the paused set
-            for &timer_id in stack_ids.iter() {
-                paused_set.remove(&timer_id);
-            }
-        });
+/// Resume every timer paused on this thread or task, including ones no
+/// longer on the stack
+pub(crate) fn clear_paused() {
+    with_timer_stack(|stack| {
+        stack.paused.clear();
+    });
+}
+
+/// Record an elapsed duration for an operation under its `category::operation` key
+///
+/// Returns `false` without recording if profiling is globally paused, the
+/// operation's category has been disabled via
+/// [`ProfileCollector::set_category_enabled`], or its key is left out of
+/// [`ProfileCollector::set_operation_whitelist`].
+pub(crate) fn record_operation(operation: &dyn Operation, duration_nanos: u64) -> bool {
+    record_operation_in(operation, duration_nanos, None)
+}
+
+/// Like [`record_operation`], but into `target` instead of the global collector
+/// when one is given
+fn record_operation_in(
+    operation: &dyn Operation,
+    duration_nanos: u64,
+    target: Option<&dyn StatsStore>,
+) -> bool {
+    let category = operation.get_category();
+    if !ProfileCollector::is_category_enabled(category.get_name()) {
+        return false;
+    }
+
+    let mut key = operation_key(operation);
+    if !ProfileCollector::is_operation_whitelisted(&key) {
+        return false;
+    }
+    // Only recordings that would otherwise have been kept count as suppressed
+    if ProfileCollector::is_paused() {
+        ProfileCollector::note_suppressed();
+        return false;
+    }
+    // The registries describe the global collector; isolated stores keep
+    // only their statistics. Keys beyond the key limit aren't registered,
+    // so the registries stay bounded too.
+    let global = target.is_none();
+    if global && admit_key(&key) == OVERFLOW_KEY {
+        key = OVERFLOW_KEY.to_string();
+    }
+    if global && key != OVERFLOW_KEY {
+        ProfileCollector::register_operation_category(&key, category);
+        if let Some(expected) = operation.expected_micros() {
+            ProfileCollector::set_expected_micros(&key, expected);
+        }
+        let order = operation.sort_hint();
+        if order != 0 {
+            ProfileCollector::set_operation_order(&key, order);
+        }
+    }
+    // A rollup beyond the key limit is dropped rather than counted again
+    // under the overflow key
+    let aggregate_key = operation
+        .aggregate_key()
+        .map(|aggregate| {
+            let key = format!("{}::{}", category.get_name(), aggregate);
+            truncate_key(key, ProfileCollector::max_key_length())
+        })
+        .filter(|key| !global || is_admitted(key));
+    if global {
+        if let Some(key) = &aggregate_key {
+            ProfileCollector::mark_aggregate(key);
+            ProfileCollector::register_operation_category(key, category);
+        }
+    }
+    for key in std::iter::once(&key).chain(&aggregate_key) {
+        match target {
+            Some(store) => ProfileCollector::record_nanos_in(store, key, duration_nanos),
+            None => ProfileCollector::record_nanos(key, duration_nanos),
+        }
+    }
+    true
+}
+
+/// Count a stack-paused recording of `operation` as suppressed, unless its
+/// category is disabled or its key is left out of the whitelist
+fn note_suppressed(operation: &dyn Operation) {
+    if ProfileCollector::is_category_enabled(operation.get_category().get_name())
+        && ProfileCollector::is_operation_whitelisted(&operation_key(operation))
+    {
+        ProfileCollector::note_suppressed();
+    }
+}
+
+/// The `category::operation` key an operation is recorded under
+pub(crate) fn operation_key(operation: &dyn Operation) -> String {
+    let key = format!(
+        "{}::{}",
+        operation.get_category().get_name(),
+        operation.normalized_key()
+    );
+    truncate_key(key, ProfileCollector::max_key_length())
+}
+
+/// Shorten `key` to at most `max_bytes`, ending it with `…` and a hash of the
+/// full key so that distinct long keys stay distinct
+fn truncate_key(key: String, max_bytes: usize) -> String {
+    if key.len() <= max_bytes {
+        return key;
+    }
+
+    // FNV-1a, which unlike `DefaultHasher` is stable across Rust releases
+    let hash = key.bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+        (hash ^ byte as u32).wrapping_mul(0x0100_0193)
+    });
+    let suffix = format!("…{:08x}", hash);
+
+    let mut end = max_bytes.saturating_sub(suffix.len());
+    while !key.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}{}", &key[..end], suffix)
+}
+
+/// Time spent in segments directly inside a timer
+fn excluded_time(timer_id: usize) -> Duration {
+    with_timer_stack(|stack| stack.excluded.get(&timer_id).copied().unwrap_or_default())
+}
+
+/// Charge a finished segment's time to the timer enclosing it
+fn exclude_from_parent(timer_id: usize, duration: Duration) {
+    with_timer_stack(|stack| {
+        // A dropped segment is already off the stack, so its parent is on top
+        let parent = match stack.active.iter().position(|&id| id == timer_id) {
+            Some(position) => position.checked_sub(1).map(|p| stack.active[p]),
+            None => stack.active.last().copied(),
+        };
+        if let Some(parent) = parent {
+            *stack.excluded.entry(parent).or_default() += duration;
+        }
     });
 }
 
 /// Check if a specific timer is paused (by timer ID)
 pub(crate) fn is_timer_paused(timer_id: usize) -> bool {
-    PAUSED_TIMERS.with(|paused| paused.borrow().contains(&timer_id))
+    with_timer_stack(|stack| stack.paused.contains(&timer_id))
 }
 
 /// A timer that automatically records duration when dropped
 ///
 /// This timer uses RAII (Resource Acquisition Is Initialization) to ensure
 /// that timing measurements are always recorded, even if the code panics.
+/// Recording on drop never panics itself: a lock poisoned by an earlier
+/// panic is recovered rather than unwrapped, so a timer dropped during
+/// unwinding can't turn the panic into an abort.
 ///
 /// # Example
 /// ```rust
@@ -100,6 +305,10 @@ pub struct ProfileTimer<'a> {
     id: usize,
     /// Whether this timer is individually paused (for stack-based pausing)
     individually_paused: bool,
+    /// Whether this timer's time is excluded from its enclosing timer
+    segment: bool,
+    /// Store recorded into instead of the global collector
+    target: Option<&'a dyn StatsStore>,
 }
 
 impl<'a> ProfileTimer<'a> {
@@ -111,9 +320,11 @@ impl<'a> ProfileTimer<'a> {
         let id = TIMER_ID_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
 
         // Register this timer on the stack
-        TIMER_STACK.with(|stack| {
-            stack.borrow_mut().push(id);
-        });
+        register_timer(id);
+        if ProfileCollector::call_paths_enabled() {
+            let key = operation_key(operation);
+            with_timer_stack(|stack| stack.keys.insert(id, key));
+        }
 
         Self {
             operation,
@@ -121,9 +332,33 @@ impl<'a> ProfileTimer<'a> {
             recorded: false,
             id,
             individually_paused: false,
+            segment: false,
+            target: None,
         }
     }
 
+    /// Create a timer that records into `store` instead of the global collector
+    ///
+    /// Use a [`HashMapStore`](crate::HashMapStore) as an isolated collector,
+    /// e.g. per test or per tenant. Call paths are only captured for the
+    /// global collector.
+    pub fn new_in(operation: &'a dyn Operation, store: &'a dyn StatsStore) -> Self {
+        let mut timer = Self::new(operation);
+        timer.target = Some(store);
+        timer
+    }
+
+    /// Create a timer for a segment of an enclosing timer
+    ///
+    /// This is what [`segment!`](crate::segment) expands to. With
+    /// [`ProfileCollector::set_exclusive_time`] enabled, the segment's time
+    /// is subtracted from the timer it is nested in when it records.
+    pub fn segment(operation: &'a dyn Operation) -> Self {
+        let mut timer = Self::new(operation);
+        timer.segment = true;
+        timer
+    }
+
     /// Get the operation being timed
     pub fn operation(&self) -> &dyn Operation {
         self.operation
@@ -134,6 +369,33 @@ impl<'a> ProfileTimer<'a> {
         self.start_time.elapsed()
     }
 
+    /// Get the elapsed time minus the time spent in nested segments
+    ///
+    /// Equal to [`Self::elapsed`] unless exclusive time is enabled.
+    pub fn exclusive_elapsed(&self) -> std::time::Duration {
+        self.elapsed().saturating_sub(excluded_time(self.id))
+    }
+
+    /// Subtract a recorded segment from its enclosing timer, if enabled
+    ///
+    /// Takes the segment's full elapsed time: segments nested in it are part
+    /// of it, so they are excluded from the enclosing timer too.
+    fn exclude_from_parent(&self, duration: Duration) {
+        if self.segment && ProfileCollector::is_exclusive_time() {
+            exclude_from_parent(self.id, duration);
+        }
+    }
+
+    /// Add the full elapsed time to this timer's call path, if captured
+    fn record_call_path(&self) {
+        if self.target.is_some() {
+            return;
+        }
+        if let Some(path) = call_path(self.id) {
+            ProfileCollector::add_call_path(path, self.elapsed());
+        }
+    }
+
     /// Get the elapsed time in microseconds
     pub fn elapsed_micros(&self) -> u64 {
         self.elapsed().as_micros() as u64
@@ -150,12 +412,16 @@ impl<'a> ProfileTimer<'a> {
         let is_paused = self.individually_paused || is_timer_paused(self.id);
 
         if !self.recorded && !is_paused {
-            let category_name = self.operation.get_category().get_name();
-            let key = format!("{}::{}", category_name, self.operation.to_str());
-            ProfileCollector::record(&key, self.elapsed_micros());
+            let elapsed = self.elapsed();
+            let duration = elapsed.saturating_sub(excluded_time(self.id));
+            if record_operation_in(self.operation, duration.as_nanos() as u64, self.target) {
+                self.exclude_from_parent(elapsed);
+                self.record_call_path();
+            }
             self.recorded = true;
-        } else if is_paused {
+        } else if is_paused && !self.recorded {
             // Mark as recorded so we don't try again
+            note_suppressed(self.operation);
             self.recorded = true;
         }
     }
@@ -180,6 +446,35 @@ impl<'a> ProfileTimer<'a> {
         self.id
     }
 
+    /// Stop the timer, record it, and bundle the result with its metadata
+    ///
+    /// This is what [`profile_detailed!`](crate::profile_detailed) expands to.
+    pub fn finish<T>(mut self, value: T) -> Profiled<T> {
+        let elapsed = self.elapsed();
+        let duration = elapsed.saturating_sub(excluded_time(self.id));
+        let key = operation_key(self.operation);
+        let recorded = if self.recorded {
+            false
+        } else if self.is_individually_paused() {
+            note_suppressed(self.operation);
+            false
+        } else {
+            record_operation_in(self.operation, duration.as_nanos() as u64, self.target)
+        };
+        if recorded {
+            self.exclude_from_parent(elapsed);
+            self.record_call_path();
+        }
+        self.recorded = true;
+
+        Profiled {
+            value,
+            duration,
+            key,
+            recorded,
+        }
+    }
+
     /// Stop the timer and return the elapsed duration without recording
     ///
     /// This consumes the timer and prevents automatic recording on drop.
@@ -200,9 +495,7 @@ impl<'a> ProfileTimer<'a> {
 impl<'a> Drop for ProfileTimer<'a> {
     fn drop(&mut self) {
         // Unregister from stack
-        TIMER_STACK.with(|stack| {
-            stack.borrow_mut().retain(|&timer_id| timer_id != self.id);
-        });
+        unregister_timer(self.id);
 
         // Record first (which will check if we're paused)
         if !self.recorded {
@@ -210,9 +503,121 @@ impl<'a> Drop for ProfileTimer<'a> {
         }
 
         // Then remove from paused set
-        PAUSED_TIMERS.with(|paused| {
-            paused.borrow_mut().remove(&self.id);
-        });
+        clear_timer_pause(self.id);
+    }
+}
+
+/// The result of a profiled block together with its timing metadata
+///
+/// Returned by [`profile_detailed!`](crate::profile_detailed). Unlike `profile!`,
+/// which only yields the block's value, this exposes the measured duration, the
+/// key it was recorded under, and whether it was actually recorded.
+#[derive(Debug, Clone)]
+pub struct Profiled<T> {
+    /// The value produced by the profiled block
+    pub value: T,
+    /// The measured duration of the block
+    pub duration: std::time::Duration,
+    /// The `category::operation` key used for recording
+    pub key: String,
+    /// Whether the measurement was recorded (false if paused or disabled)
+    pub recorded: bool,
+}
+
+/// An in-flight measurement started by [`ProfileCollector::start`]
+///
+/// For operations whose start and end live in different scopes, such as a
+/// request received in one handler and answered in another. The token is
+/// `Send`, so it can travel in a request context across threads and await
+/// points. Hand it to [`ProfileCollector::stop`] to record; a dropped token
+/// records nothing.
+#[derive(Debug)]
+#[must_use = "pass the token to `ProfileCollector::stop` to record the operation"]
+pub struct TimerToken {
+    key: String,
+    operation: CapturedOperation,
+    start_time: Instant,
+}
+
+/// What recording needs from an operation, copied so a [`TimerToken`] can
+/// outlive the operation it measures
+#[derive(Debug)]
+struct CapturedOperation {
+    category: CategoryInfo,
+    normalized_key: String,
+    expected_micros: Option<u64>,
+    sort_hint: i32,
+    aggregate_key: Option<String>,
+}
+
+impl CapturedOperation {
+    fn new(operation: &dyn Operation) -> Self {
+        Self {
+            category: CategoryInfo::from_category(operation.get_category()),
+            normalized_key: operation.normalized_key(),
+            expected_micros: operation.expected_micros(),
+            sort_hint: operation.sort_hint(),
+            aggregate_key: operation.aggregate_key(),
+        }
+    }
+}
+
+impl Operation for CapturedOperation {
+    fn get_category(&self) -> &dyn Category {
+        &self.category
+    }
+
+    fn to_str(&self) -> String {
+        self.normalized_key.clone()
+    }
+
+    fn normalized_key(&self) -> String {
+        self.normalized_key.clone()
+    }
+
+    fn expected_micros(&self) -> Option<u64> {
+        self.expected_micros
+    }
+
+    fn sort_hint(&self) -> i32 {
+        self.sort_hint
+    }
+
+    fn aggregate_key(&self) -> Option<String> {
+        self.aggregate_key.clone()
+    }
+}
+
+impl TimerToken {
+    /// Start measuring `operation` now
+    pub(crate) fn start(operation: &dyn Operation) -> Self {
+        let key = operation_key(operation);
+        Self {
+            key,
+            operation: CapturedOperation::new(operation),
+            start_time: Instant::now(),
+        }
+    }
+
+    /// Record the elapsed time, returning it
+    ///
+    /// Recorded like a [`ProfileTimer`], so nothing is recorded if profiling
+    /// is paused, the category is disabled or the operation isn't
+    /// whitelisted.
+    pub(crate) fn stop(self) -> std::time::Duration {
+        let elapsed = self.start_time.elapsed();
+        record_operation(&self.operation, elapsed.as_nanos() as u64);
+        elapsed
+    }
+
+    /// Get the `category::operation` key this token will record under
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Get the time elapsed since the operation started
+    pub fn elapsed(&self) -> std::time::Duration {
+        self.start_time.elapsed()
     }
 }
 
@@ -249,9 +654,15 @@ impl<'a> Drop for ProfileTimer<'a> {
 /// }).await;
 /// # }
 /// ```
+///
+/// Cloning a timer gives a handle with the same start time, which can be
+/// moved into the future to record [checkpoints](Self::checkpoint).
+#[derive(Clone)]
 pub struct ProfileTimerAsync<'a> {
     operation: &'a dyn Operation,
     start_time: Instant,
+    /// Where to record; the global collector when `None`
+    target: Option<&'a dyn StatsStore>,
 }
 
 impl<'a> ProfileTimerAsync<'a> {
@@ -260,26 +671,77 @@ impl<'a> ProfileTimerAsync<'a> {
         Self {
             operation,
             start_time: Instant::now(),
+            target: None,
+        }
+    }
+
+    /// Create an async timer that records into `store` instead of the global collector
+    ///
+    /// Use a [`HashMapStore`](crate::HashMapStore) as an isolated collector,
+    /// e.g. per test or per tenant. The store is borrowed for as long as the
+    /// future returned by [`Self::run`].
+    pub fn new_in(operation: &'a dyn Operation, store: &'a dyn StatsStore) -> Self {
+        Self {
+            operation,
+            start_time: Instant::now(),
+            target: Some(store),
         }
     }
 
     /// Run an async operation and record its duration
+    ///
+    /// The recording happens in a drop guard, so the elapsed time is still
+    /// recorded if the future panics (or is dropped before completion),
+    /// mirroring the RAII guarantee of [`ProfileTimer`].
+    ///
+    /// With the `tokio` feature enabled, the timer also joins a task-local
+    /// timer stack so that `pause_stack!()` inside the future still applies
+    /// after the task has migrated to another worker thread.
     pub fn run<F, R>(self, fut: F) -> impl std::future::Future<Output = R> + 'a
     where
         F: std::future::Future<Output = R> + 'a,
     {
-        async move {
-            let result = fut.await;
-            let elapsed = self.start_time.elapsed();
-
-            let key = format!(
-                "{}::{}",
-                self.operation.get_category().get_name(),
-                self.operation.to_str()
-            );
-            ProfileCollector::record(&key, elapsed.as_micros() as u64);
+        let operation = self.operation;
+        let start_time = self.start_time;
+        let target = self.target;
+
+        #[cfg(not(feature = "tokio"))]
+        {
+            async move {
+                let _guard = AsyncRecordGuard {
+                    operation,
+                    start_time,
+                    target,
+                };
+                fut.await
+            }
+        }
 
-            result
+        #[cfg(feature = "tokio")]
+        {
+            async move {
+                let timed = async move {
+                    let id = TIMER_ID_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    register_timer(id);
+                    let _guard = AsyncRecordGuard {
+                        operation,
+                        start_time,
+                        target,
+                        id,
+                    };
+                    fut.await
+                };
+
+                // Reuse the enclosing task's stack so outer async timers are
+                // paused together with this one
+                if TASK_TIMER_STACK.try_with(|_| ()).is_ok() {
+                    timed.await
+                } else {
+                    TASK_TIMER_STACK
+                        .scope(RefCell::new(TimerStack::default()), timed)
+                        .await
+                }
+            }
         }
     }
 
@@ -292,6 +754,94 @@ impl<'a> ProfileTimerAsync<'a> {
     pub fn elapsed(&self) -> std::time::Duration {
         self.start_time.elapsed()
     }
+
+    /// Record the time elapsed so far under `category::operation::suffix`
+    ///
+    /// The main recording still happens when the run completes. Checkpoints
+    /// show how far into a long operation each stage finishes.
+    ///
+    /// ```rust
+    /// use quantum_pulse::{Operation, ProfileTimerAsync};
+    ///
+    /// #[derive(Debug)]
+    /// struct Pipeline;
+    ///
+    /// impl Operation for Pipeline {}
+    ///
+    /// # async fn example() {
+    /// let timer = ProfileTimerAsync::new(&Pipeline);
+    /// let checkpoints = timer.clone();
+    /// timer
+    ///     .run(async move {
+    ///         // fetch().await;
+    ///         checkpoints.checkpoint("fetched");
+    ///         // transform().await;
+    ///         checkpoints.checkpoint("transformed");
+    ///     })
+    ///     .await;
+    /// # }
+    /// ```
+    pub fn checkpoint(&self, suffix: &str) {
+        let category = self.operation.get_category();
+        if !ProfileCollector::is_category_enabled(category.get_name()) {
+            return;
+        }
+
+        let parent_key = operation_key(self.operation);
+        if !ProfileCollector::is_operation_whitelisted(&parent_key) {
+            return;
+        }
+        if ProfileCollector::is_paused() {
+            ProfileCollector::note_suppressed();
+            return;
+        }
+        let key = truncate_key(
+            format!("{}::{}", parent_key, suffix),
+            ProfileCollector::max_key_length(),
+        );
+        let duration_nanos = self.elapsed().as_nanos() as u64;
+        match self.target {
+            Some(store) => ProfileCollector::record_nanos_in(store, &key, duration_nanos),
+            None => {
+                let key = admit_key(&key);
+                if key != OVERFLOW_KEY {
+                    ProfileCollector::register_operation_category(key, category);
+                }
+                ProfileCollector::record_nanos(key, duration_nanos);
+            }
+        }
+    }
+}
+
+/// Records the elapsed time of a [`ProfileTimerAsync`] run when dropped
+struct AsyncRecordGuard<'a> {
+    operation: &'a dyn Operation,
+    start_time: Instant,
+    target: Option<&'a dyn StatsStore>,
+    /// ID on the task-local timer stack
+    #[cfg(feature = "tokio")]
+    id: usize,
+}
+
+impl<'a> Drop for AsyncRecordGuard<'a> {
+    fn drop(&mut self) {
+        #[cfg(feature = "tokio")]
+        {
+            unregister_timer(self.id);
+            let paused = is_timer_paused(self.id);
+            clear_timer_pause(self.id);
+            if paused {
+                note_suppressed(self.operation);
+                return;
+            }
+        }
+
+        record_operation_in(
+            self.operation,
+            self.start_time.elapsed().as_nanos() as u64,
+            self.target,
+        );
+    }
 }
 
 /// A timer that can be paused and resumed
@@ -328,15 +878,27 @@ impl<'a> ProfileTimerAsync<'a> {
 ///
 /// // Timer records total time excluding the paused period
 /// ```
+///
+/// # Lifecycle
+///
+/// A timer is [`Running`](TimerState::Running) or
+/// [`Paused`](TimerState::Paused) until it is recorded or stopped, after
+/// which it is [`Recorded`](TimerState::Recorded): its duration is frozen,
+/// [`total_elapsed`](Self::total_elapsed) keeps returning the recorded value,
+/// [`resume`](Self::resume) has no effect, and dropping it records nothing
+/// more. Call [`reset`](Self::reset) to start a new measurement.
 pub struct PausableTimer<'a> {
     operation: &'a dyn Operation,
     total_duration: std::time::Duration,
     start_time: Option<Instant>,
-    recorded: bool,
+    /// Duration frozen when the timer was recorded or stopped
+    recorded: Option<std::time::Duration>,
     /// Unique ID for this timer instance
     id: usize,
     /// Whether this timer is individually paused (for stack-based pausing)
     individually_paused: bool,
+    /// Store recorded into instead of the global collector
+    target: Option<&'a dyn StatsStore>,
 }
 
 impl<'a> PausableTimer<'a> {
@@ -345,36 +907,42 @@ impl<'a> PausableTimer<'a> {
         let id = TIMER_ID_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
 
         // Register this timer on the stack
-        TIMER_STACK.with(|stack| {
-            stack.borrow_mut().push(id);
-        });
+        register_timer(id);
 
         Self {
             operation,
             total_duration: std::time::Duration::ZERO,
             start_time: Some(Instant::now()),
-            recorded: false,
+            recorded: None,
             id,
             individually_paused: false,
+            target: None,
         }
     }
 
+    /// Create a pausable timer that records into `store` instead of the
+    /// global collector, see [`ProfileTimer::new_in`]
+    pub fn new_in(operation: &'a dyn Operation, store: &'a dyn StatsStore) -> Self {
+        let mut timer = Self::new(operation);
+        timer.target = Some(store);
+        timer
+    }
+
     /// Create a new pausable timer that starts paused
     pub fn new_paused(operation: &'a dyn Operation) -> Self {
         let id = TIMER_ID_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
 
         // Register this timer on the stack
-        TIMER_STACK.with(|stack| {
-            stack.borrow_mut().push(id);
-        });
+        register_timer(id);
 
         Self {
             operation,
             total_duration: std::time::Duration::ZERO,
             start_time: None,
-            recorded: false,
+            recorded: None,
             id,
             individually_paused: false,
+            target: None,
         }
     }
 
@@ -389,15 +957,21 @@ impl<'a> PausableTimer<'a> {
 
     /// Resume the timer
     ///
-    /// If the timer is already running, this has no effect.
+    /// If the timer is already running or has been recorded, this has no
+    /// effect.
     pub fn resume(&mut self) {
-        if self.start_time.is_none() {
+        if self.start_time.is_none() && self.recorded.is_none() {
             self.start_time = Some(Instant::now());
         }
     }
 
     /// Get the total elapsed time (excluding paused periods)
+    ///
+    /// Once the timer is recorded, this is the recorded duration.
     pub fn total_elapsed(&self) -> std::time::Duration {
+        if let Some(recorded) = self.recorded {
+            return recorded;
+        }
         let mut total = self.total_duration;
         if let Some(start) = self.start_time {
             total += start.elapsed();
@@ -417,7 +991,18 @@ impl<'a> PausableTimer<'a> {
 
     /// Check if the timer is currently running
     pub fn is_running(&self) -> bool {
-        self.start_time.is_some()
+        self.state() == TimerState::Running
+    }
+
+    /// Get where the timer is in its lifecycle
+    pub fn state(&self) -> TimerState {
+        if self.recorded.is_some() {
+            TimerState::Recorded
+        } else if self.start_time.is_some() {
+            TimerState::Running
+        } else {
+            TimerState::Paused
+        }
     }
 
     /// Get the operation being timed
@@ -425,22 +1010,25 @@ impl<'a> PausableTimer<'a> {
         self.operation
     }
 
-    /// Record the current total duration
+    /// Record the current total duration and stop the timer
+    ///
+    /// Only the first call records; see the [lifecycle](Self#lifecycle).
     pub fn record(&mut self) {
-        // Check both individual pause state and if this timer ID is in the paused set
-        let is_paused = self.individually_paused || is_timer_paused(self.id);
+        if self.recorded.is_some() {
+            return;
+        }
+        self.pause();
+        self.recorded = Some(self.total_duration);
 
-        if !self.recorded && !is_paused {
-            let key = format!(
-                "{}::{}",
-                self.operation.get_category().get_name(),
-                self.operation.to_str()
+        // Check both individual pause state and if this timer ID is in the paused set
+        if self.individually_paused || is_timer_paused(self.id) {
+            note_suppressed(self.operation);
+        } else {
+            record_operation_in(
+                self.operation,
+                self.total_duration.as_nanos() as u64,
+                self.target,
             );
-            ProfileCollector::record(&key, self.total_elapsed_micros());
-            self.recorded = true;
-        } else if is_paused {
-            // Mark as recorded so we don't try again
-            self.recorded = true;
         }
     }
 
@@ -464,53 +1052,116 @@ impl<'a> PausableTimer<'a> {
         self.id
     }
 
+    /// Stop the timer, record it, and bundle the result with its metadata
+    ///
+    /// Mirrors [`ProfileTimer::finish`]; the duration excludes paused periods.
+    pub fn finish<T>(mut self, value: T) -> Profiled<T> {
+        let duration = self.total_elapsed();
+        let key = operation_key(self.operation);
+        let recorded = if self.recorded.is_some() {
+            false
+        } else if self.is_individually_paused() {
+            note_suppressed(self.operation);
+            false
+        } else {
+            record_operation_in(self.operation, duration.as_nanos() as u64, self.target)
+        };
+        self.recorded = Some(duration);
+
+        Profiled {
+            value,
+            duration,
+            key,
+            recorded,
+        }
+    }
+
     /// Stop the timer and return the total elapsed duration without recording
     pub fn stop(mut self) -> std::time::Duration {
-        self.pause();
-        let duration = self.total_duration;
-        self.recorded = true; // Prevent recording on drop
+        let duration = self.total_elapsed();
+        self.recorded = Some(duration); // Prevent recording on drop
         duration
     }
 
     /// Stop the timer, record it, and return the total elapsed duration
     pub fn stop_and_record(mut self) -> std::time::Duration {
-        self.pause();
-        let duration = self.total_duration;
         self.record();
-        duration
+        self.total_elapsed()
     }
 
     /// Reset the timer to zero and start it
+    ///
+    /// This also starts a new measurement after the timer was recorded.
     pub fn reset(&mut self) {
         self.total_duration = std::time::Duration::ZERO;
         self.start_time = Some(Instant::now());
-        self.recorded = false;
+        self.recorded = None;
     }
 
     /// Reset the timer to zero and pause it
     pub fn reset_paused(&mut self) {
         self.total_duration = std::time::Duration::ZERO;
         self.start_time = None;
-        self.recorded = false;
+        self.recorded = None;
     }
 }
 
+/// Where a [`PausableTimer`] is in its lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerState {
+    /// Accumulating time
+    Running,
+    /// Not accumulating time until resumed
+    Paused,
+    /// Recorded or stopped, with its duration frozen until reset
+    Recorded,
+}
+
 impl<'a> Drop for PausableTimer<'a> {
     fn drop(&mut self) {
         // Unregister from stack
-        TIMER_STACK.with(|stack| {
-            stack.borrow_mut().retain(|&timer_id| timer_id != self.id);
-        });
+        unregister_timer(self.id);
 
         // Record first (which will check if we're paused)
-        if !self.recorded {
-            self.record();
-        }
+        self.record();
 
         // Then remove from paused set
-        PAUSED_TIMERS.with(|paused| {
-            paused.borrow_mut().remove(&self.id);
-        });
+        clear_timer_pause(self.id);
+    }
+}
+
+/// One iteration of a bucketed [`profile_loop!`](crate::profile_loop), keyed
+/// `{operation}::iter_{index}`
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct LoopIteration<'a> {
+    pub operation: &'a dyn Operation,
+    pub index: usize,
+}
+
+impl Operation for LoopIteration<'_> {
+    fn get_category(&self) -> &dyn crate::category::Category {
+        self.operation.get_category()
+    }
+
+    fn to_str(&self) -> String {
+        format!("{}::iter_{}", self.operation.to_str(), self.index)
+    }
+
+    fn normalized_key(&self) -> String {
+        format!("{}::iter_{}", self.operation.normalized_key(), self.index)
+    }
+
+    fn expected_micros(&self) -> Option<u64> {
+        self.operation.expected_micros()
+    }
+
+    fn sort_hint(&self) -> i32 {
+        self.operation.sort_hint()
+    }
+
+    fn aggregate_key(&self) -> Option<String> {
+        self.operation.aggregate_key()
     }
 }
 
@@ -626,6 +1277,50 @@ mod tests {
         assert_eq!(stats.unwrap().count, 1);
     }
 
+    #[test]
+    fn test_pausable_timer_record_then_continue() {
+        #[derive(Debug)]
+        struct RecordedOp;
+
+        impl Operation for RecordedOp {
+            fn to_str(&self) -> String {
+                "pausable_record_then_continue".to_string()
+            }
+        }
+
+        let operation = RecordedOp;
+        let mut timer = PausableTimer::new(&operation);
+        assert_eq!(timer.state(), TimerState::Running);
+        thread::sleep(Duration::from_millis(1));
+        timer.record();
+        assert_eq!(timer.state(), TimerState::Recorded);
+        let recorded = timer.total_elapsed();
+        assert!(recorded >= Duration::from_millis(1));
+
+        // Further work neither counts nor records again
+        timer.resume();
+        assert!(!timer.is_running());
+        thread::sleep(Duration::from_millis(5));
+        assert_eq!(timer.total_elapsed(), recorded);
+        timer.record();
+        drop(timer);
+
+        let stats = ProfileCollector::get_stats("::pausable_record_then_continue").unwrap();
+        assert_eq!(stats.count, 1);
+        assert_eq!(stats.total, recorded);
+
+        // Reset starts a new measurement
+        let mut timer = PausableTimer::new(&operation);
+        timer.record();
+        timer.reset();
+        assert_eq!(timer.state(), TimerState::Running);
+        timer.pause();
+        assert_eq!(timer.state(), TimerState::Paused);
+        drop(timer);
+        let stats = ProfileCollector::get_stats("::pausable_record_then_continue").unwrap();
+        assert_eq!(stats.count, 3);
+    }
+
     #[test]
     fn test_pausable_timer() {
         ProfileCollector::clear_all();
@@ -664,6 +1359,372 @@ mod tests {
         assert!(stats.is_some());
     }
 
+    #[test]
+    fn test_pausable_timer_finish() {
+        ProfileCollector::clear_all();
+
+        #[derive(Debug)]
+        struct TestOp;
+
+        impl Operation for TestOp {
+            fn to_str(&self) -> String {
+                "pausable_finish".to_string()
+            }
+        }
+
+        let operation = TestOp;
+        let mut timer = PausableTimer::new(&operation);
+        timer.pause();
+        thread::sleep(Duration::from_millis(20));
+        timer.resume();
+
+        let p = timer.finish("done");
+        assert_eq!(p.value, "done");
+        assert_eq!(p.key, "::pausable_finish");
+        assert!(p.recorded);
+        assert!(p.duration < Duration::from_millis(20));
+        assert_eq!(
+            ProfileCollector::get_stats("::pausable_finish")
+                .unwrap()
+                .count,
+            1
+        );
+
+        // Finishing while globally paused reports that nothing was recorded
+        ProfileCollector::pause();
+        let p = PausableTimer::new(&operation).finish(());
+        ProfileCollector::unpause();
+        assert!(!p.recorded);
+        assert_eq!(
+            ProfileCollector::get_stats("::pausable_finish")
+                .unwrap()
+                .count,
+            1
+        );
+    }
+
+    #[test]
+    fn test_segment_excluded_from_enclosing_timer() {
+        #[derive(Debug)]
+        enum SegmentOp {
+            Outer,
+            Inner,
+            OuterInclusive,
+            NestedOuter,
+            SegA,
+            SegB,
+        }
+
+        impl Operation for SegmentOp {}
+
+        ProfileCollector::set_exclusive_time(true);
+        crate::profile!(SegmentOp::Outer, {
+            thread::sleep(Duration::from_millis(5));
+            crate::segment!(SegmentOp::Inner, {
+                thread::sleep(Duration::from_millis(30));
+            });
+        });
+
+        // A segment's own segments are excluded from its enclosing timer too
+        crate::profile!(SegmentOp::NestedOuter, {
+            thread::sleep(Duration::from_millis(5));
+            crate::segment!(SegmentOp::SegA, {
+                thread::sleep(Duration::from_millis(10));
+                crate::segment!(SegmentOp::SegB, {
+                    thread::sleep(Duration::from_millis(30));
+                });
+            });
+        });
+
+        // Without exclusive time the enclosing timer keeps its wall time
+        ProfileCollector::set_exclusive_time(false);
+        crate::profile!(SegmentOp::OuterInclusive, {
+            crate::segment!(SegmentOp::Inner, {
+                thread::sleep(Duration::from_millis(30));
+            });
+        });
+
+        let outer = ProfileCollector::get_stats("::Outer").unwrap();
+        assert!(outer.total >= Duration::from_millis(5));
+        assert!(outer.total < Duration::from_millis(30));
+
+        let inner = ProfileCollector::get_stats("::Inner").unwrap();
+        assert_eq!(inner.count, 2);
+        assert!(inner.total >= Duration::from_millis(60));
+
+        let inclusive = ProfileCollector::get_stats("::OuterInclusive").unwrap();
+        assert!(inclusive.total >= Duration::from_millis(30));
+
+        let nested_outer = ProfileCollector::get_stats("::NestedOuter").unwrap();
+        assert!(nested_outer.total >= Duration::from_millis(5));
+        assert!(nested_outer.total < Duration::from_millis(30));
+        let seg_a = ProfileCollector::get_stats("::SegA").unwrap();
+        assert!(seg_a.total >= Duration::from_millis(10));
+        assert!(seg_a.total < Duration::from_millis(30));
+        let seg_b = ProfileCollector::get_stats("::SegB").unwrap();
+        assert!(seg_b.total >= Duration::from_millis(30));
+    }
+
+    #[tokio::test]
+    async fn test_async_timer_records_into_local_store() {
+        #[derive(Debug)]
+        struct LocalOp;
+
+        impl Operation for LocalOp {
+            fn expected_micros(&self) -> Option<u64> {
+                Some(1_000)
+            }
+
+            fn aggregate_key(&self) -> Option<String> {
+                Some("LocalOps".to_string())
+            }
+        }
+
+        let local = crate::HashMapStore::default();
+        let timer = ProfileTimerAsync::new_in(&LocalOp, &local);
+        let checkpoints = timer.clone();
+        let value = timer
+            .run(async move {
+                tokio::time::sleep(Duration::from_millis(2)).await;
+                checkpoints.checkpoint("halfway");
+                7
+            })
+            .await;
+
+        assert_eq!(value, 7);
+        let stats = local.get("::LocalOp").unwrap();
+        assert_eq!(stats.count, 1);
+        assert!(stats.total >= Duration::from_millis(2));
+        assert_eq!(local.get("::LocalOps").unwrap().count, 1);
+        assert_eq!(local.get("::LocalOp::halfway").unwrap().count, 1);
+        assert!(ProfileCollector::get_stats("::LocalOp").is_none());
+
+        // Nothing about the isolated recordings leaks into the global registries
+        assert!(ProfileCollector::category_of("::LocalOp").is_none());
+        assert!(ProfileCollector::category_of("::LocalOp::halfway").is_none());
+        assert!(ProfileCollector::expected_micros("::LocalOp").is_none());
+        assert!(!ProfileCollector::is_aggregate("::LocalOps"));
+    }
+
+    #[test]
+    fn test_timer_records_into_local_store() {
+        #[derive(Debug)]
+        struct LocalSyncOp;
+
+        impl Operation for LocalSyncOp {
+            fn aggregate_key(&self) -> Option<String> {
+                Some("LocalSyncOps".to_string())
+            }
+        }
+
+        let local = crate::HashMapStore::default();
+        {
+            let _timer = ProfileTimer::new_in(&LocalSyncOp, &local);
+            thread::sleep(Duration::from_millis(2));
+        }
+        let profiled = ProfileTimer::new_in(&LocalSyncOp, &local).finish(3);
+        assert!(profiled.recorded);
+
+        let stats = local.get("::LocalSyncOp").unwrap();
+        assert_eq!(stats.count, 2);
+        assert!(stats.total >= Duration::from_millis(2));
+        assert_eq!(local.get("::LocalSyncOps").unwrap().count, 2);
+        assert!(ProfileCollector::get_stats("::LocalSyncOp").is_none());
+        assert!(ProfileCollector::category_of("::LocalSyncOp").is_none());
+        assert!(!ProfileCollector::is_aggregate("::LocalSyncOps"));
+    }
+
+    #[test]
+    fn test_pausable_timer_records_into_local_store() {
+        #[derive(Debug)]
+        struct LocalPausableOp;
+
+        impl Operation for LocalPausableOp {}
+
+        let local = crate::HashMapStore::default();
+        {
+            let mut timer = PausableTimer::new_in(&LocalPausableOp, &local);
+            thread::sleep(Duration::from_millis(2));
+            timer.pause();
+            thread::sleep(Duration::from_millis(20));
+        }
+        assert!(
+            PausableTimer::new_in(&LocalPausableOp, &local)
+                .finish(())
+                .recorded
+        );
+
+        let stats = local.get("::LocalPausableOp").unwrap();
+        assert_eq!(stats.count, 2);
+        assert!(stats.max_time_nanos >= 2_000_000);
+        assert!(stats.max_time_nanos < 20_000_000);
+        assert!(ProfileCollector::get_stats("::LocalPausableOp").is_none());
+        assert!(ProfileCollector::category_of("::LocalPausableOp").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_async_timer_checkpoint() {
+        #[derive(Debug)]
+        struct CheckpointOp;
+
+        impl Operation for CheckpointOp {}
+
+        ProfileCollector::reset_operation("::CheckpointOp");
+        ProfileCollector::reset_operation("::CheckpointOp::halfway");
+
+        let timer = ProfileTimerAsync::new(&CheckpointOp);
+        let checkpoints = timer.clone();
+        timer
+            .run(async move {
+                tokio::time::sleep(Duration::from_millis(2)).await;
+                checkpoints.checkpoint("halfway");
+                checkpoints.checkpoint(&"z".repeat(400));
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            })
+            .await;
+
+        let main = ProfileCollector::get_stats("::CheckpointOp").unwrap();
+        let halfway = ProfileCollector::get_stats("::CheckpointOp::halfway").unwrap();
+        assert_eq!(main.count, 1);
+        assert_eq!(halfway.count, 1);
+        assert!(halfway.total >= Duration::from_millis(2));
+        assert!(halfway.total < main.total);
+
+        // Checkpoint keys are truncated like operation keys
+        let long_key = truncate_key(
+            format!("::CheckpointOp::{}", "z".repeat(400)),
+            ProfileCollector::max_key_length(),
+        );
+        assert!(long_key.len() <= ProfileCollector::max_key_length());
+        assert!(ProfileCollector::get_stats(&long_key).is_some());
+    }
+
+    #[test]
+    fn test_truncate_key() {
+        assert_eq!(truncate_key("short".to_string(), 16), "short");
+
+        let first = format!("Cat::{}a", "x".repeat(300));
+        let second = format!("Cat::{}b", "x".repeat(300));
+        let first_truncated = truncate_key(first.clone(), 64);
+        let second_truncated = truncate_key(second, 64);
+
+        assert!(first_truncated.len() <= 64);
+        assert!(first_truncated.starts_with("Cat::xxx"));
+        assert!(first_truncated.contains('…'));
+        assert_ne!(first_truncated, second_truncated);
+        // The same key always truncates the same way
+        assert_eq!(truncate_key(first, 64), first_truncated);
+
+        // Multi-byte characters are never split
+        let wide = "é".repeat(100);
+        assert!(truncate_key(wide, 33).len() <= 33);
+    }
+
+    #[test]
+    fn test_long_operation_keys_are_truncated() {
+        #[derive(Debug)]
+        struct Payload(String);
+
+        impl Operation for Payload {
+            fn to_str(&self) -> String {
+                self.0.clone()
+            }
+        }
+
+        let first = Payload(format!("{}1", "y".repeat(400)));
+        let second = Payload(format!("{}2", "y".repeat(400)));
+        ProfileTimer::new(&first).record();
+        ProfileTimer::new(&second).record();
+
+        let first_key = operation_key(&first);
+        let second_key = operation_key(&second);
+        assert!(first_key.len() <= ProfileCollector::max_key_length());
+        assert_ne!(first_key, second_key);
+        assert!(ProfileCollector::get_stats(&first_key).unwrap().count >= 1);
+        assert!(ProfileCollector::get_stats(&second_key).unwrap().count >= 1);
+    }
+
+    #[test]
+    fn test_timer_token_across_scopes() {
+        #[derive(Debug)]
+        struct RequestOp;
+
+        impl Operation for RequestOp {
+            fn to_str(&self) -> String {
+                "token_request".to_string()
+            }
+
+            fn expected_micros(&self) -> Option<u64> {
+                Some(2_000)
+            }
+
+            fn sort_hint(&self) -> i32 {
+                4
+            }
+
+            fn aggregate_key(&self) -> Option<String> {
+                Some("token_requests".to_string())
+            }
+        }
+
+        fn receive_request() -> TimerToken {
+            ProfileCollector::start(&RequestOp)
+        }
+
+        fn send_response(token: TimerToken) -> Duration {
+            ProfileCollector::stop(token)
+        }
+
+        ProfileCollector::reset_operation("::token_request");
+
+        // A token dropped without stopping leaves nothing behind
+        #[derive(Debug)]
+        struct AbandonedOp;
+
+        impl Operation for AbandonedOp {
+            fn to_str(&self) -> String {
+                "token_abandoned".to_string()
+            }
+        }
+
+        drop(ProfileCollector::start(&AbandonedOp));
+        assert!(ProfileCollector::category_of("::token_abandoned").is_none());
+
+        let token = receive_request();
+        assert_eq!(token.key(), "::token_request");
+
+        // The token can be handed to another thread before stopping
+        let elapsed = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(5));
+            send_response(token)
+        })
+        .join()
+        .unwrap();
+
+        assert!(elapsed >= Duration::from_millis(5));
+        let stats = ProfileCollector::get_stats("::token_request").unwrap();
+        assert_eq!(stats.count, 1);
+        assert!(stats.total >= Duration::from_millis(5));
+
+        // The operation's settings are registered as for any other timer
+        assert!(ProfileCollector::category_of("::token_request").is_some());
+        assert_eq!(
+            ProfileCollector::expected_micros("::token_request"),
+            Some(2_000)
+        );
+        assert_eq!(
+            ProfileCollector::operation_order("::token_request"),
+            Some(4)
+        );
+        assert!(ProfileCollector::is_aggregate("::token_requests"));
+        assert!(
+            ProfileCollector::get_stats("::token_requests")
+                .unwrap()
+                .count
+                >= 1
+        );
+    }
+
     #[test]
     fn test_pausable_timer_start_paused() {
         #[derive(Debug)]
@@ -708,4 +1769,314 @@ mod tests {
         assert!(stats.is_some());
         assert_eq!(stats.unwrap().count, 1);
     }
+
+    #[test]
+    fn test_async_timer_records_on_panic() {
+        ProfileCollector::clear_all();
+
+        #[derive(Debug)]
+        struct PanickingOp;
+
+        impl Operation for PanickingOp {
+            fn to_str(&self) -> String {
+                "async_panicking".to_string()
+            }
+        }
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            runtime.block_on(async {
+                let operation = PanickingOp;
+                ProfileTimerAsync::new(&operation)
+                    .run(async {
+                        tokio::time::sleep(Duration::from_millis(1)).await;
+                        panic!("future failed");
+                    })
+                    .await
+            })
+        }));
+
+        assert!(result.is_err());
+        let stats = ProfileCollector::get_stats("::async_panicking");
+        assert!(stats.is_some());
+        assert_eq!(stats.unwrap().count, 1);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_pause_stack_follows_async_task_across_threads() {
+        use std::future::Future;
+        use std::pin::Pin;
+        use std::task::{Context, Poll, Waker};
+
+        ProfileCollector::clear_all();
+
+        #[derive(Debug)]
+        struct HoppingOp;
+
+        impl Operation for HoppingOp {
+            fn to_str(&self) -> String {
+                "async_hopping".to_string()
+            }
+        }
+
+        static OPERATION: HoppingOp = HoppingOp;
+
+        /// Returns `Pending` once so the future can be moved to another thread
+        struct YieldOnce(bool);
+
+        impl Future for YieldOnce {
+            type Output = ();
+
+            fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+                if self.0 {
+                    Poll::Ready(())
+                } else {
+                    self.0 = true;
+                    Poll::Pending
+                }
+            }
+        }
+
+        let mut fut = Box::pin(ProfileTimerAsync::new(&OPERATION).run(async {
+            pause_stack();
+            YieldOnce(false).await;
+            thread::current().id()
+        }));
+
+        let first_thread = thread::current().id();
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(fut.as_mut().poll(&mut cx).is_pending());
+
+        // Finish the future on another thread, whose thread-local stack has
+        // never seen the paused timer
+        let resumed_on = thread::spawn(move || {
+            let mut cx = Context::from_waker(Waker::noop());
+            match fut.as_mut().poll(&mut cx) {
+                Poll::Ready(thread_id) => thread_id,
+                Poll::Pending => panic!("future should complete on second poll"),
+            }
+        })
+        .join()
+        .unwrap();
+
+        assert_ne!(resumed_on, first_thread);
+        assert!(ProfileCollector::get_stats("::async_hopping").is_none());
+    }
+
+    #[test]
+    fn test_normalized_key_collapses_parameterized_operations() {
+        ProfileCollector::clear_all();
+
+        #[derive(Debug)]
+        struct Query(String);
+
+        impl Operation for Query {
+            fn to_str(&self) -> String {
+                self.0.clone()
+            }
+
+            fn normalized_key(&self) -> String {
+                let mut key = String::new();
+                for c in self.0.chars() {
+                    if !c.is_ascii_digit() {
+                        key.push(c);
+                    } else if !key.ends_with('?') {
+                        key.push('?');
+                    }
+                }
+                key
+            }
+        }
+
+        let first = Query("SELECT * FROM users WHERE id=123".to_string());
+        let second = Query("SELECT * FROM users WHERE id=98765".to_string());
+        drop(ProfileTimer::new(&first));
+        let profiled = ProfileTimer::new(&second).finish(());
+
+        assert_eq!(profiled.key, "::SELECT * FROM users WHERE id=?");
+        assert!(ProfileCollector::get_stats("::SELECT * FROM users WHERE id=123").is_none());
+        assert_eq!(
+            ProfileCollector::get_stats("::SELECT * FROM users WHERE id=?")
+                .unwrap()
+                .count,
+            2
+        );
+    }
+
+    #[test]
+    fn test_aggregate_key() {
+        #[derive(Debug)]
+        enum FileOp {
+            ReadFile,
+            ReadFileCached,
+        }
+
+        impl Operation for FileOp {
+            fn to_str(&self) -> String {
+                format!("aggregate_{:?}", self)
+            }
+
+            fn aggregate_key(&self) -> Option<String> {
+                Some("aggregate_file_read".to_string())
+            }
+        }
+
+        crate::profile!(FileOp::ReadFile, {});
+        crate::profile!(FileOp::ReadFile, {});
+        crate::profile!(FileOp::ReadFileCached, {});
+
+        let count = |key: &str| ProfileCollector::get_stats(key).map_or(0, |stats| stats.count);
+        assert_eq!(count("::aggregate_ReadFile"), 2);
+        assert_eq!(count("::aggregate_ReadFileCached"), 1);
+        assert_eq!(count("::aggregate_file_read"), 3);
+        assert!(ProfileCollector::is_aggregate("::aggregate_file_read"));
+        assert!(!ProfileCollector::is_aggregate("::aggregate_ReadFile"));
+
+        // The summary counts the granular recordings only
+        let stats: std::collections::HashMap<_, _> = [
+            "::aggregate_ReadFile",
+            "::aggregate_ReadFileCached",
+            "::aggregate_file_read",
+        ]
+        .into_iter()
+        .filter_map(|key| Some((key.to_string(), ProfileCollector::get_stats(key)?)))
+        .collect();
+        let summary = crate::SummaryStats::from_stats(&stats);
+        assert_eq!(summary.total_operations, 3);
+        assert_eq!(summary.unique_operations, 2);
+    }
+
+    #[test]
+    fn test_aggregate_key_of_loop_iterations() {
+        struct LoopCategory;
+        impl crate::Category for LoopCategory {
+            fn get_name(&self) -> &str {
+                "AggregateLoop"
+            }
+            fn get_description(&self) -> &str {
+                "Bucketed loop with a rollup"
+            }
+        }
+
+        #[derive(Debug)]
+        struct Batch;
+
+        impl Operation for Batch {
+            fn get_category(&self) -> &dyn crate::Category {
+                &LoopCategory
+            }
+
+            fn aggregate_key(&self) -> Option<String> {
+                Some("all_batches".to_string())
+            }
+        }
+
+        crate::profile_loop!(bucketed Batch, 0..3, |_| {});
+
+        let count = |key: &str| ProfileCollector::get_stats(key).map_or(0, |stats| stats.count);
+        assert_eq!(count("AggregateLoop::Batch::iter_0"), 1);
+        assert_eq!(count("AggregateLoop::all_batches"), 3);
+        // The rollup isn't counted again in the category
+        assert_eq!(
+            ProfileCollector::category_summary("AggregateLoop")
+                .unwrap()
+                .count,
+            3
+        );
+    }
+
+    #[test]
+    fn test_category_of_recorded_operation() {
+        struct CacheCategory;
+        impl crate::Category for CacheCategory {
+            fn get_name(&self) -> &str {
+                "CategoryOfCache"
+            }
+            fn get_description(&self) -> &str {
+                "Cache lookups"
+            }
+            fn color_hint(&self) -> Option<&str> {
+                Some("#1abc9c")
+            }
+            fn priority(&self) -> i32 {
+                7
+            }
+        }
+
+        #[derive(Debug)]
+        struct CacheLookup;
+        impl Operation for CacheLookup {
+            fn get_category(&self) -> &dyn crate::Category {
+                &CacheCategory
+            }
+        }
+
+        crate::profile!(CacheLookup, {});
+
+        let info = ProfileCollector::category_of("CategoryOfCache::CacheLookup").unwrap();
+        assert_eq!(info.name, "CategoryOfCache");
+        assert_eq!(info.description, "Cache lookups");
+        assert_eq!(info.color.as_deref(), Some("#1abc9c"));
+        assert_eq!(info.priority, 7);
+        assert!(ProfileCollector::category_of("CategoryOfCache::Missing").is_none());
+    }
+
+    #[test]
+    fn test_category_disabled_skips_recording() {
+        ProfileCollector::clear_all();
+
+        struct NetworkCategory;
+        impl crate::Category for NetworkCategory {
+            fn get_name(&self) -> &str {
+                "Network"
+            }
+            fn get_description(&self) -> &str {
+                "Network operations"
+            }
+        }
+
+        struct DatabaseCategory;
+        impl crate::Category for DatabaseCategory {
+            fn get_name(&self) -> &str {
+                "Database"
+            }
+            fn get_description(&self) -> &str {
+                "Database operations"
+            }
+        }
+
+        #[derive(Debug)]
+        enum AppOp {
+            Fetch,
+            Query,
+        }
+
+        impl Operation for AppOp {
+            fn get_category(&self) -> &dyn crate::Category {
+                match self {
+                    AppOp::Fetch => &NetworkCategory,
+                    AppOp::Query => &DatabaseCategory,
+                }
+            }
+        }
+
+        ProfileCollector::set_category_enabled("Network", false);
+        assert!(!ProfileCollector::is_category_enabled("Network"));
+
+        drop(ProfileTimer::new(&AppOp::Fetch));
+        drop(ProfileTimer::new(&AppOp::Query));
+
+        ProfileCollector::set_category_enabled("Network", true);
+
+        assert!(ProfileCollector::get_stats("Network::Fetch").is_none());
+        assert!(ProfileCollector::get_stats("Database::Query").is_some());
+
+        drop(ProfileTimer::new(&AppOp::Fetch));
+        assert!(ProfileCollector::get_stats("Network::Fetch").is_some());
+    }
 }
@@ -0,0 +1,718 @@
+//! Tests for global collector settings and state
+//!
+//! Every test starts with [`guard`], which holds
+//! `ProfileCollector::test_guard()` and resets all settings with
+//! `ProfileCollector::reset_for_test()`, so these tests can change global
+//! settings without affecting each other. The binary records through a
+//! [`CountingStore`], installed before the first recording.
+
+#![cfg(all(feature = "full", feature = "testing"))]
+
+use quantum_pulse::{
+    count, pause, pause_stack, profile, profile_detailed, unpause, CategoryInfo, HashMapStore,
+    Operation, OperationStats, PausableTimer, ProfileCollector, ProfileOp, ReportBuilder,
+    StatsStore, TestGuard, OVERFLOW_KEY,
+};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Once;
+use std::thread::sleep;
+use std::time::Duration;
+
+/// Recordings that went through [`CountingStore`]
+static RECORDS: AtomicUsize = AtomicUsize::new(0);
+
+/// Wraps the default backend and counts recordings
+struct CountingStore {
+    inner: HashMapStore,
+}
+
+impl StatsStore for CountingStore {
+    fn record(
+        &self,
+        key: &str,
+        create: &mut dyn FnMut() -> OperationStats,
+        update: &mut dyn FnMut(&mut OperationStats),
+    ) -> bool {
+        RECORDS.fetch_add(1, Ordering::Relaxed);
+        self.inner.record(key, create, update)
+    }
+
+    fn update(&self, key: &str, update: &mut dyn FnMut(&mut OperationStats)) -> bool {
+        self.inner.update(key, update)
+    }
+
+    fn get(&self, key: &str) -> Option<OperationStats> {
+        self.inner.get(key)
+    }
+
+    fn remove(&self, key: &str) -> Option<OperationStats> {
+        self.inner.remove(key)
+    }
+
+    fn for_each(&self, visit: &mut dyn FnMut(&str, &OperationStats)) {
+        self.inner.for_each(visit)
+    }
+
+    fn clear(&self) {
+        self.inner.clear()
+    }
+}
+
+/// Serialize the test and start it from the collector's default state
+fn guard() -> TestGuard {
+    // The backend can only be installed before anything is recorded
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        assert!(ProfileCollector::set_stats_store(Box::new(CountingStore {
+            inner: HashMapStore::default(),
+        })));
+    });
+
+    let guard = ProfileCollector::test_guard();
+    ProfileCollector::reset_for_test();
+    guard
+}
+
+#[derive(Debug, ProfileOp)]
+enum HotPathOp {
+    #[category(name = "Http")]
+    Request,
+    #[category(name = "Http")]
+    Auth,
+    #[category(name = "Db")]
+    Query,
+    #[category(name = "Db")]
+    Parse,
+    #[category(name = "Db")]
+    Execute,
+    #[category(name = "Jobs")]
+    Cleanup,
+}
+
+#[derive(Debug, ProfileOp)]
+enum WhitelistOp {
+    #[category(name = "Whitelist")]
+    Focused,
+    #[category(name = "Whitelist")]
+    Ignored,
+}
+
+#[derive(Debug)]
+enum RollupOp {
+    Read,
+    ReadCached,
+}
+
+impl Operation for RollupOp {
+    fn aggregate_key(&self) -> Option<String> {
+        Some("AnyRead".to_string())
+    }
+}
+
+#[derive(Debug, ProfileOp)]
+enum SuppressedOp {
+    #[category(name = "Suppressed")]
+    Work,
+}
+
+#[test]
+fn test_custom_stats_store() {
+    let _g = guard();
+    // Only the first installation takes effect
+    assert!(!ProfileCollector::set_stats_store(Box::new(
+        HashMapStore::default()
+    )));
+
+    let before = RECORDS.load(Ordering::Relaxed);
+    ProfileCollector::record("custom_store_op", 100);
+    ProfileCollector::record("custom_store_op", 300);
+    ProfileCollector::increment("custom_store_counter");
+    assert_eq!(RECORDS.load(Ordering::Relaxed) - before, 3);
+
+    let stats = ProfileCollector::get_stats("custom_store_op").unwrap();
+    assert_eq!(stats.count, 2);
+    assert_eq!(ProfileCollector::total_operations(), 3);
+
+    assert!(ProfileCollector::alias(
+        "custom_store_counter",
+        "custom_store_op"
+    ));
+    assert_eq!(
+        ProfileCollector::get_stats("custom_store_op")
+            .unwrap()
+            .count,
+        3
+    );
+    assert!(ProfileCollector::rename(
+        "custom_store_op",
+        "custom_store_renamed"
+    ));
+    assert_eq!(ProfileCollector::get_all_stats().len(), 1);
+
+    ProfileCollector::clear_all();
+    assert!(!ProfileCollector::has_data());
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_baseline_file_round_trip() {
+    use quantum_pulse::ProfileReport;
+
+    let _g = guard();
+    let path = std::env::temp_dir().join(format!(
+        "quantum_pulse_baseline_{}.json",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+
+    ProfileCollector::record("Db::fast", 100);
+    ProfileCollector::record("Db::fast", 300);
+    ProfileCollector::record("Db::slow", 100);
+
+    // Without a baseline everything is new and nothing regresses
+    let report: ProfileReport = ProfileReport::generate();
+    let comparison = report.compare_to_baseline_file(&path, 1.2).unwrap();
+    assert!(comparison.is_ok());
+    assert_eq!(comparison.new, ["Db::fast", "Db::slow"]);
+
+    report.save_baseline(&path).unwrap();
+    let baseline = ProfileReport::load_baseline(&path).unwrap();
+    assert_eq!(baseline.len(), 2);
+    assert_eq!(baseline["Db::fast"].count, 2);
+    assert_eq!(baseline["Db::fast"].mean_time_micros(), 200);
+    assert_eq!(baseline["Db::fast"].min_time_micros(), 100);
+
+    ProfileCollector::clear_all();
+    ProfileCollector::record("Db::fast", 50);
+    ProfileCollector::record("Db::slow", 500);
+    ProfileCollector::record("Db::added", 10);
+
+    let report: ProfileReport = ProfileReport::generate();
+    let comparison = report.compare_to_baseline_file(&path, 1.2).unwrap();
+    assert!(!comparison.is_ok());
+    assert_eq!(comparison.regressed, ["Db::slow"]);
+    assert_eq!(comparison.new, ["Db::added"]);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_aggregates_not_counted_twice() {
+    let _g = guard();
+    profile!(RollupOp::Read, {});
+    profile!(RollupOp::Read, {});
+    profile!(RollupOp::ReadCached, {});
+    assert_eq!(ProfileCollector::get_stats("::AnyRead").unwrap().count, 3);
+
+    assert_eq!(ProfileCollector::total_operations(), 3);
+    assert_eq!(ProfileCollector::global_histogram().unwrap().count, 3);
+    assert_eq!(ProfileCollector::category_summary("").unwrap().count, 3);
+
+    // Rollups aren't operations, so they don't need to be known
+    assert!(ProfileCollector::validate_known(&["::Read", "::ReadCached"]).is_ok());
+}
+
+#[test]
+fn test_binary_round_trip() {
+    let _g = guard();
+    for micros in [120, 250, 250, 900, 4_000, 15_000] {
+        ProfileCollector::record("Binary::measured", micros);
+    }
+    ProfileCollector::record_nanos("Binary::fast", 350);
+    ProfileCollector::record_nanos("Binary::fast", 0);
+    ProfileCollector::increment("Binary::counted");
+    ProfileCollector::record("Binary::counted", 500);
+    ProfileCollector::record_summary("Binary::imported", 40, 120, 100, 250, 400, 900);
+
+    let exported = ProfileCollector::get_all_stats();
+    let bytes = ProfileCollector::export_binary();
+
+    ProfileCollector::clear_all();
+    assert_eq!(ProfileCollector::import_binary(&bytes).unwrap(), 4);
+
+    let imported = ProfileCollector::get_all_stats();
+    assert_eq!(imported.len(), exported.len());
+    for (key, before) in &exported {
+        let after = &imported[key];
+        assert_eq!(after.count, before.count, "{key}");
+        assert_eq!(after.total, before.total, "{key}");
+        assert_eq!(after.timed_count(), before.timed_count(), "{key}");
+        assert_eq!(after.min_time_nanos, before.min_time_nanos, "{key}");
+        assert_eq!(after.max_time_nanos, before.max_time_nanos, "{key}");
+        assert_eq!(after.ewma_nanos(), before.ewma_nanos(), "{key}");
+        assert_eq!(after.max_time_at(), before.max_time_at(), "{key}");
+        assert_eq!(after.has_percentiles(), before.has_percentiles(), "{key}");
+        assert_eq!(after.is_imported(), before.is_imported(), "{key}");
+        assert_eq!(
+            after.zero_duration_count(),
+            before.zero_duration_count(),
+            "{key}"
+        );
+        assert_eq!(after.p50_nanos(), before.p50_nanos(), "{key}");
+        assert_eq!(after.p95_nanos(), before.p95_nanos(), "{key}");
+        assert_eq!(after.p99_nanos(), before.p99_nanos(), "{key}");
+        assert_eq!(after.p999_nanos(), before.p999_nanos(), "{key}");
+    }
+
+    // Importing again merges into the existing operations
+    ProfileCollector::import_binary(&bytes).unwrap();
+    let merged = ProfileCollector::get_stats("Binary::measured").unwrap();
+    assert_eq!(merged.count, 12);
+    assert_eq!(merged.p50_nanos(), exported["Binary::measured"].p50_nanos());
+
+    // Imported histograms keep resizing for new samples
+    ProfileCollector::record("Binary::measured", 60_000_000);
+    let stats = ProfileCollector::get_stats("Binary::measured").unwrap();
+    assert!(stats.p999_micros() >= 59_000_000);
+}
+
+#[test]
+fn test_binary_import_rejects_malformed_input() {
+    let _g = guard();
+    assert!(ProfileCollector::import_binary(b"").is_err());
+    assert!(ProfileCollector::import_binary(b"{\"json\": true}").is_err());
+
+    // A truncated export is rejected as a whole
+    let mut bytes = b"QPB\x01".to_vec();
+    bytes.extend_from_slice(&1u32.to_le_bytes());
+    bytes.extend_from_slice(&5u32.to_le_bytes());
+    bytes.extend_from_slice(b"Bin");
+    let error = ProfileCollector::import_binary(&bytes).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_histogram_bounds() {
+    let _g = guard();
+    assert!(ProfileCollector::histogram_bounds().is_none());
+    assert!(!ProfileCollector::set_histogram_bounds(Some((
+        Duration::ZERO,
+        Duration::from_secs(1)
+    ))));
+    assert!(!ProfileCollector::set_histogram_bounds(Some((
+        Duration::from_secs(1),
+        Duration::from_millis(1500)
+    ))));
+    assert!(ProfileCollector::histogram_bounds().is_none());
+
+    let bounds = (Duration::from_micros(1), Duration::from_secs(10));
+    assert!(ProfileCollector::set_histogram_bounds(Some(bounds)));
+    assert_eq!(ProfileCollector::histogram_bounds(), Some(bounds));
+
+    // Near the upper bound, percentiles keep their precision
+    for seconds in 1..=9 {
+        ProfileCollector::record("Bounds::slow", seconds * 1_000_000 + 900_000);
+    }
+    let stats = ProfileCollector::get_stats("Bounds::slow").unwrap();
+    assert!(stats.p999_micros().abs_diff(9_900_000) <= 9_900);
+    assert!(stats.p50_micros().abs_diff(5_900_000) <= 5_900);
+
+    // Beyond it, durations are clamped in the percentiles only
+    ProfileCollector::record("Bounds::overflow", 5_000_000);
+    ProfileCollector::record("Bounds::overflow", 60_000_000);
+    let stats = ProfileCollector::get_stats("Bounds::overflow").unwrap();
+    assert_eq!(stats.count, 2);
+    assert_eq!(stats.max(), Duration::from_secs(60));
+    assert_eq!(stats.total, Duration::from_secs(65));
+    assert!(stats.p999_micros().abs_diff(10_000_000) <= 10_000);
+    assert_eq!(stats.distribution(2).iter().sum::<u64>(), 2);
+
+    assert!(ProfileCollector::set_histogram_bounds(None));
+    ProfileCollector::record("Bounds::unbounded", 60_000_000);
+    let stats = ProfileCollector::get_stats("Bounds::unbounded").unwrap();
+    assert!(stats.p999_micros().abs_diff(60_000_000) <= 60_000);
+}
+
+fn handle_request() {
+    profile!(HotPathOp::Request, {
+        profile!(HotPathOp::Auth, sleep(Duration::from_millis(4)));
+        profile!(HotPathOp::Query, {
+            profile!(HotPathOp::Parse, sleep(Duration::from_millis(1)));
+            profile!(HotPathOp::Execute, sleep(Duration::from_millis(6)));
+        });
+    });
+}
+
+#[test]
+fn test_hot_path() {
+    let _g = guard();
+    handle_request();
+    // Not captured while disabled
+    assert!(ProfileCollector::hot_path().is_empty());
+
+    ProfileCollector::set_call_paths_enabled(true);
+    for _ in 0..3 {
+        handle_request();
+    }
+    profile!(HotPathOp::Cleanup, sleep(Duration::from_millis(10)));
+    ProfileCollector::set_call_paths_enabled(false);
+
+    let hot = ProfileCollector::hot_path();
+    let keys: Vec<&str> = hot.iter().map(|(key, _)| key.as_str()).collect();
+    assert_eq!(keys, ["Http::Request", "Db::Query", "Db::Execute"]);
+
+    // Time shrinks down the path and covers the three requests
+    assert!(hot[0].1 >= Duration::from_millis(33));
+    assert!(hot[0].1 >= hot[1].1 && hot[1].1 >= hot[2].1);
+    assert!(hot[2].1 >= Duration::from_millis(18));
+
+    ProfileCollector::reset_all();
+    assert!(ProfileCollector::hot_path().is_empty());
+
+    // A timer started before call paths were enabled leaves a gap, so the
+    // nested timer isn't reported as a root
+    profile!(HotPathOp::Cleanup, {
+        ProfileCollector::set_call_paths_enabled(true);
+        profile!(HotPathOp::Parse, sleep(Duration::from_millis(1)));
+    });
+    ProfileCollector::set_call_paths_enabled(false);
+    assert!(ProfileCollector::hot_path().is_empty());
+}
+
+#[test]
+fn test_new_keys_beyond_limit_overflow() {
+    let _g = guard();
+    assert_eq!(ProfileCollector::max_keys(), 10_000);
+    ProfileCollector::set_max_keys(3);
+
+    for key in ["Cardinality::a", "Cardinality::b", "Cardinality::c"] {
+        ProfileCollector::record(key, 100);
+    }
+    assert_eq!(ProfileCollector::cardinality_overflowed(), 0);
+
+    // New keys are rerouted, existing ones keep recording
+    ProfileCollector::record("Cardinality::d", 200);
+    ProfileCollector::increment("Cardinality::e");
+    ProfileCollector::record("Cardinality::a", 300);
+
+    assert!(ProfileCollector::get_stats("Cardinality::d").is_none());
+    assert!(ProfileCollector::get_stats("Cardinality::e").is_none());
+    assert_eq!(
+        ProfileCollector::get_stats("Cardinality::a").unwrap().count,
+        2
+    );
+    let overflow = ProfileCollector::get_stats(OVERFLOW_KEY).unwrap();
+    assert_eq!(overflow.count, 2);
+    assert_eq!(ProfileCollector::cardinality_overflowed(), 2);
+
+    // Timers for new keys register nothing, and drop their rollup
+    profile!(RollupOp::Read, {});
+    assert!(ProfileCollector::category_of("::Read").is_none());
+    assert!(ProfileCollector::category_of(OVERFLOW_KEY).is_none());
+    assert!(!ProfileCollector::is_aggregate("::AnyRead"));
+    assert_eq!(ProfileCollector::get_stats(OVERFLOW_KEY).unwrap().count, 3);
+    assert_eq!(ProfileCollector::cardinality_overflowed(), 3);
+
+    // Lifting the limit lets new keys in again
+    ProfileCollector::set_max_keys(0);
+    ProfileCollector::record("Cardinality::f", 100);
+    assert!(ProfileCollector::get_stats("Cardinality::f").is_some());
+    assert_eq!(ProfileCollector::cardinality_overflowed(), 3);
+
+    ProfileCollector::clear_all();
+    assert_eq!(ProfileCollector::cardinality_overflowed(), 0);
+
+    // Imports are held to the limit too
+    for key in ["Import::a", "Import::b", "Import::c", "Import::d"] {
+        ProfileCollector::record(key, 100);
+    }
+    let bytes = ProfileCollector::export_binary();
+    ProfileCollector::clear_all();
+    ProfileCollector::set_max_keys(2);
+    assert_eq!(ProfileCollector::import_binary(&bytes).unwrap(), 4);
+    ProfileCollector::record_summary("Import::summary", 40, 120, 100, 250, 400, 900);
+
+    let stats = ProfileCollector::get_all_stats();
+    assert_eq!(stats.len(), 3);
+    assert!(stats.contains_key("Import::a") && stats.contains_key("Import::b"));
+    assert_eq!(stats[OVERFLOW_KEY].count, 42);
+    assert_eq!(ProfileCollector::cardinality_overflowed(), 3);
+    assert!(ProfileCollector::validate_known(&["Import::a", "Import::b"]).is_ok());
+}
+
+#[test]
+fn test_operation_whitelist() {
+    let _g = guard();
+    assert!(ProfileCollector::operation_whitelist().is_none());
+
+    let whitelist = HashSet::from(["Whitelist::Focused".to_string()]);
+    ProfileCollector::set_operation_whitelist(Some(whitelist.clone()));
+    assert_eq!(ProfileCollector::operation_whitelist(), Some(whitelist));
+
+    profile!(WhitelistOp::Focused, {});
+    profile!(WhitelistOp::Ignored, {});
+    count!(WhitelistOp::Ignored);
+    let token = ProfileCollector::start(&WhitelistOp::Ignored);
+    ProfileCollector::stop(token);
+
+    assert_eq!(
+        ProfileCollector::get_stats("Whitelist::Focused")
+            .unwrap()
+            .count,
+        1
+    );
+    assert!(ProfileCollector::get_stats("Whitelist::Ignored").is_none());
+
+    ProfileCollector::set_operation_whitelist(None);
+    profile!(WhitelistOp::Ignored, {});
+    assert!(ProfileCollector::get_stats("Whitelist::Ignored").is_some());
+}
+
+#[test]
+fn test_percentiles_disabled() {
+    let _g = guard();
+    assert!(ProfileCollector::percentiles_enabled());
+    ProfileCollector::record("with_percentiles", 100);
+    ProfileCollector::record("with_percentiles", 300);
+
+    ProfileCollector::set_percentiles_enabled(false);
+    assert!(!ProfileCollector::percentiles_enabled());
+    ProfileCollector::record("without_percentiles", 100);
+    ProfileCollector::record("without_percentiles", 300);
+
+    let with = ProfileCollector::get_stats("with_percentiles").unwrap();
+    let without = ProfileCollector::get_stats("without_percentiles").unwrap();
+    assert!(with.has_percentiles());
+    assert!(!without.has_percentiles());
+    assert!(without.memory_footprint() < with.memory_footprint());
+
+    // Aggregates stay exact
+    assert_eq!(without.count, 2);
+    assert_eq!(without.mean_time_micros(), 200);
+    assert_eq!(without.min_time_micros(), 100);
+    assert_eq!(without.max_time_micros, 300);
+
+    // Percentiles fall back to estimates from mean and max
+    assert_eq!(without.p50_micros(), 200);
+    assert_eq!(without.p95_micros(), 250);
+    assert_eq!(without.p99_micros(), 225);
+    assert_eq!(without.p999_micros(), 300);
+    assert!(without.distribution(4).iter().all(|&count| count == 0));
+    let _ = ProfileCollector::export_prometheus_histogram(&[200]);
+
+    // Merging into stats with a histogram keeps working
+    assert!(ProfileCollector::alias(
+        "without_percentiles",
+        "with_percentiles"
+    ));
+    assert_eq!(
+        ProfileCollector::get_stats("with_percentiles")
+            .unwrap()
+            .count,
+        4
+    );
+}
+
+#[test]
+fn test_reset_for_test_restores_pristine_state() {
+    let _g = guard();
+    ProfileCollector::record("Reset::recorded", 100);
+    ProfileCollector::set_category_enabled("Reset", false);
+    ProfileCollector::set_operation_whitelist(Some(HashSet::from(["Reset::only".to_string()])));
+    ProfileCollector::set_max_keys(5);
+    ProfileCollector::set_percentiles_enabled(false);
+    ProfileCollector::set_call_paths_enabled(true);
+    ProfileCollector::set_warmup(2, false);
+    ProfileCollector::register_category(CategoryInfo {
+        name: "Reset".to_string(),
+        description: String::new(),
+        color: None,
+        icon: None,
+        priority: 0,
+    });
+    ProfileCollector::set_slow_event_threshold(Some(Duration::from_millis(1)));
+    pause!();
+    ProfileCollector::record("Reset::paused", 100);
+    assert!(ProfileCollector::is_paused());
+    assert_eq!(ProfileCollector::suppressed_count(), 1);
+
+    ProfileCollector::reset_for_test();
+    ProfileCollector::reset_for_test();
+
+    assert!(!ProfileCollector::is_paused());
+    assert!(ProfileCollector::get_all_stats().is_empty());
+    assert_eq!(ProfileCollector::suppressed_count(), 0);
+    assert!(ProfileCollector::is_category_enabled("Reset"));
+    assert!(ProfileCollector::operation_whitelist().is_none());
+    assert_eq!(ProfileCollector::max_keys(), 10_000);
+    assert!(ProfileCollector::percentiles_enabled());
+    assert!(!ProfileCollector::call_paths_enabled());
+    assert_eq!(ProfileCollector::warmup(), (0, true));
+    assert!(ProfileCollector::registered_categories().is_empty());
+    assert_eq!(ProfileCollector::slow_event_threshold(), None);
+
+    ProfileCollector::record("Reset::after", 100);
+    let stats = ProfileCollector::get_stats("Reset::after").unwrap();
+    assert!(stats.has_percentiles());
+}
+
+#[test]
+fn test_report_shows_registered_categories() {
+    let _g = guard();
+    ProfileCollector::register_category(CategoryInfo {
+        name: "Declared".to_string(),
+        description: "Registered before any recording".to_string(),
+        color: None,
+        icon: None,
+        priority: 0,
+    });
+
+    let builder: ReportBuilder = ReportBuilder::new();
+    let output = builder
+        .include_empty_categories(true)
+        .build()
+        .to_console_string();
+    assert!(output.contains("═══ Declared ═══\n  Registered before any recording\n"));
+}
+
+#[test]
+fn test_slow_events() {
+    let _g = guard();
+    assert!(ProfileCollector::slow_event_threshold().is_none());
+    ProfileCollector::record_with_note("query", 5_000, "before threshold");
+    assert!(ProfileCollector::slow_events().is_empty());
+
+    ProfileCollector::set_slow_event_threshold(Some(Duration::from_millis(1)));
+    ProfileCollector::record_with_note("query", 500, "fast");
+    ProfileCollector::record("query", 5_000);
+    ProfileCollector::record_with_note("query", 2_000, "SELECT * FROM users WHERE id = 42");
+
+    let events = ProfileCollector::slow_events();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].key, "query");
+    assert_eq!(events[0].micros, 2_000);
+    assert_eq!(events[0].note, "SELECT * FROM users WHERE id = 42");
+    // Every measurement is still recorded
+    assert_eq!(ProfileCollector::get_stats("query").unwrap().count, 4);
+
+    // The buffer keeps only the most recent events
+    ProfileCollector::set_slow_event_capacity(2);
+    for i in 0..3 {
+        ProfileCollector::record_with_note("query", 3_000 + i, &format!("slow {i}"));
+    }
+    let notes: Vec<_> = ProfileCollector::slow_events()
+        .into_iter()
+        .map(|event| event.note)
+        .collect();
+    assert_eq!(notes, ["slow 1", "slow 2"]);
+}
+
+#[test]
+fn test_suppressed_count() {
+    let _g = guard();
+    assert_eq!(ProfileCollector::suppressed_count(), 0);
+
+    pause!();
+    for _ in 0..5 {
+        profile!(SuppressedOp::Work, {});
+    }
+    ProfileCollector::record("direct", 10);
+    unpause!();
+    assert_eq!(ProfileCollector::suppressed_count(), 6);
+    assert!(ProfileCollector::get_stats("Suppressed::Work").is_none());
+
+    // Stack-paused timers are counted once each
+    for _ in 0..3 {
+        profile!(SuppressedOp::Work, {
+            pause_stack!();
+        });
+    }
+    assert_eq!(ProfileCollector::suppressed_count(), 9);
+
+    profile!(SuppressedOp::Work, {});
+    assert_eq!(ProfileCollector::suppressed_count(), 9);
+    assert_eq!(
+        ProfileCollector::get_stats("Suppressed::Work")
+            .unwrap()
+            .count,
+        1
+    );
+
+    // Counts are dropped too; disabled categories would not have recorded
+    pause!();
+    count!(SuppressedOp::Work);
+    ProfileCollector::set_category_enabled("Suppressed", false);
+    profile!(SuppressedOp::Work, {});
+    count!(SuppressedOp::Work);
+    profile!(SuppressedOp::Work, {
+        pause_stack!();
+    });
+    ProfileCollector::set_category_enabled("Suppressed", true);
+    unpause!();
+    assert_eq!(ProfileCollector::suppressed_count(), 10);
+
+    // Finishing a stack-paused timer counts as well
+    let profiled = profile_detailed!(SuppressedOp::Work, {
+        pause_stack!();
+    });
+    assert!(!profiled.recorded);
+    let timer = PausableTimer::new(&SuppressedOp::Work);
+    pause_stack!();
+    assert!(!timer.finish(()).recorded);
+    assert_eq!(ProfileCollector::suppressed_count(), 12);
+
+    #[cfg(feature = "tokio")]
+    {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        runtime.block_on(quantum_pulse::profile_async!(SuppressedOp::Work, async {
+            pause_stack!();
+        }));
+        assert_eq!(ProfileCollector::suppressed_count(), 13);
+    }
+
+    ProfileCollector::clear_all();
+    assert_eq!(ProfileCollector::suppressed_count(), 0);
+}
+
+#[test]
+fn test_warmup_samples_are_excluded() {
+    let _g = guard();
+    ProfileCollector::set_warmup(3, true);
+    assert_eq!(ProfileCollector::warmup(), (3, true));
+
+    for _ in 0..3 {
+        ProfileCollector::record("Warmup::counted", 50_000);
+    }
+    let warming = ProfileCollector::get_stats("Warmup::counted").unwrap();
+    assert_eq!(warming.count, 3);
+    assert!(!warming.has_timing());
+
+    for _ in 0..10 {
+        ProfileCollector::record("Warmup::counted", 100);
+    }
+    let stats = ProfileCollector::get_stats("Warmup::counted").unwrap();
+    assert_eq!(stats.count, 13);
+    assert_eq!(stats.total.as_micros(), 3 * 50_000 + 10 * 100);
+    assert_eq!(stats.max_time_micros, 100);
+    assert!(stats.p99_micros() <= 101, "p99 {}", stats.p99_micros());
+    // Counted warmup stays in the mean
+    assert_eq!(stats.timed_count(), 13);
+    assert_eq!(stats.mean(), stats.total / 13);
+
+    // Stats recorded directly don't warm up
+    let mut direct = OperationStats::default();
+    direct.record(Duration::from_micros(50_000));
+    assert_eq!(direct.max_time_micros, 50_000);
+
+    // Uncounted warmup leaves no trace at all
+    ProfileCollector::set_warmup(3, false);
+    for micros in [50_000, 50_000, 50_000, 200, 100] {
+        ProfileCollector::record("Warmup::dropped", micros);
+    }
+    let stats = ProfileCollector::get_stats("Warmup::dropped").unwrap();
+    assert_eq!(stats.count, 2);
+    assert_eq!(stats.total.as_micros(), 300);
+    assert!((199..=201).contains(&stats.p99_micros()));
+
+    ProfileCollector::set_warmup(0, true);
+    ProfileCollector::record("Warmup::off", 50_000);
+    assert_eq!(
+        ProfileCollector::get_stats("Warmup::off")
+            .unwrap()
+            .max_time_micros,
+        50_000
+    );
+}
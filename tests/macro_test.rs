@@ -279,3 +279,163 @@ fn test_single_variant_enum() {
     assert_eq!(op.get_category().get_name(), "Single");
     assert_eq!(op.get_category().get_description(), "Single operation");
 }
+
+#[test]
+fn test_category_icon() {
+    #[derive(Debug, ProfileOp)]
+    enum IconOp {
+        #[category(name = "Metrics", icon = "📊")]
+        Collect,
+
+        // The first icon for a category wins
+        #[category(name = "Metrics", icon = "📈")]
+        Aggregate,
+
+        #[category(name = "Network")]
+        Fetch,
+    }
+
+    assert_eq!(IconOp::Collect.get_category().icon(), Some("📊"));
+    assert_eq!(IconOp::Aggregate.get_category().icon(), Some("📊"));
+    assert_eq!(IconOp::Fetch.get_category().icon(), None);
+}
+
+#[test]
+fn test_operations_const() {
+    #[allow(dead_code)]
+    #[derive(Debug, ProfileOp)]
+    enum RegistryOp {
+        #[category(name = "IO")]
+        ReadFile,
+
+        #[category(name = "IO")]
+        WriteFile,
+
+        Compute,
+
+        // Keys of data-carrying variants depend on their values
+        #[category(name = "Database")]
+        Query(String),
+    }
+
+    assert_eq!(
+        RegistryOp::OPERATIONS,
+        &["IO::ReadFile", "IO::WriteFile", "Compute::Compute"]
+    );
+
+    // The listed keys are the ones timers record under
+    for (op, key) in [
+        RegistryOp::ReadFile,
+        RegistryOp::WriteFile,
+        RegistryOp::Compute,
+    ]
+    .iter()
+    .zip(RegistryOp::OPERATIONS)
+    {
+        assert_eq!(
+            format!("{}::{}", op.get_category().get_name(), op.to_str()),
+            *key
+        );
+    }
+}
+
+#[test]
+fn test_operation_ids() {
+    #[allow(dead_code)]
+    #[derive(Debug, ProfileOp)]
+    enum WireOp {
+        #[category(name = "IO", id = 7)]
+        ReadFile,
+
+        #[category(name = "IO", description = "File operations", id = 8)]
+        WriteFile,
+
+        #[category(id = 42)]
+        Query(String),
+
+        // Variants without an id report 0
+        #[category(name = "IO")]
+        Unassigned,
+    }
+
+    assert_eq!(WireOp::ReadFile.op_id(), 7);
+    assert_eq!(WireOp::WriteFile.op_id(), 8);
+    assert_eq!(WireOp::Query("SELECT 1".to_string()).op_id(), 42);
+    assert_eq!(WireOp::Unassigned.op_id(), 0);
+
+    // Enums that don't use ids keep the default
+    #[derive(Debug, ProfileOp)]
+    enum UnnumberedOp {
+        #[category(name = "IO")]
+        ReadFile,
+    }
+    assert_eq!(UnnumberedOp::ReadFile.op_id(), 0);
+}
+
+#[test]
+fn test_expected_micros() {
+    #[derive(Debug, ProfileOp)]
+    enum BaselineOp {
+        #[category(name = "Database", expected_us = 500)]
+        Query,
+
+        #[category(name = "Cache", id = 3, expected_us = 20)]
+        Lookup,
+
+        #[category(name = "Cache")]
+        Evict,
+    }
+
+    assert_eq!(BaselineOp::Query.expected_micros(), Some(500));
+    assert_eq!(BaselineOp::Lookup.expected_micros(), Some(20));
+    assert_eq!(BaselineOp::Evict.expected_micros(), None);
+}
+
+#[test]
+fn test_sort_hint() {
+    #[allow(dead_code)]
+    #[derive(Debug, ProfileOp)]
+    enum OrderOp {
+        #[category(name = "Orders", order = 1)]
+        Validate,
+
+        #[category(name = "Orders", order = 2)]
+        Submit(u64),
+
+        // Variants without an order report 0
+        #[category(name = "Orders")]
+        Audit,
+    }
+
+    assert_eq!(OrderOp::Validate.sort_hint(), 1);
+    assert_eq!(OrderOp::Submit(5).sort_hint(), 2);
+    assert_eq!(OrderOp::Audit.sort_hint(), 0);
+}
+
+#[test]
+fn test_default_category() {
+    #[derive(Debug, ProfileOp)]
+    #[profile(default_category = "Misc")]
+    enum DefaultedOp {
+        #[category(name = "IO")]
+        Read,
+
+        Cleanup,
+
+        // A description alone doesn't name the category
+        #[category(description = "Leftover work")]
+        Sweep,
+    }
+
+    assert_eq!(DefaultedOp::Read.get_category().get_name(), "IO");
+    assert_eq!(DefaultedOp::Cleanup.get_category().get_name(), "Misc");
+    assert_eq!(DefaultedOp::Sweep.get_category().get_name(), "Misc");
+    assert_eq!(
+        DefaultedOp::Sweep.get_category().get_description(),
+        "Leftover work"
+    );
+    assert_eq!(
+        DefaultedOp::OPERATIONS,
+        ["IO::Read", "Misc::Cleanup", "Misc::Sweep"]
+    );
+}
@@ -0,0 +1,117 @@
+//! Tests for the `#[profiled]` function attribute
+//!
+//! These tests compile in both stub and full modes; recordings are only
+//! checked when the `full` feature is enabled.
+
+use quantum_pulse::{is_full_enabled, profiled, ProfileCollector};
+use std::thread;
+use std::time::Duration;
+
+#[profiled("Database::fetch")]
+fn fetch(id: u32) -> Result<u32, String> {
+    if id == 0 {
+        return Err("missing id".to_string());
+    }
+    let parsed: u32 = id.to_string().parse().map_err(|_| "bad id".to_string())?;
+    thread::sleep(Duration::from_millis(2));
+    Ok(parsed * 2)
+}
+
+#[profiled("uncategorized_step")]
+fn uncategorized_step() {}
+
+#[profiled]
+fn inferred_name() {}
+
+#[profiled(category = "DB")]
+fn query() -> usize {
+    7
+}
+
+#[profiled(category = "DB", name = "lookup")]
+async fn find_user() {}
+
+#[profiled("Network::download")]
+async fn download(size: usize) -> Result<Vec<u8>, String> {
+    let checked = validate(size)?;
+    tokio::time::sleep(Duration::from_millis(2)).await;
+    Ok(vec![0; checked])
+}
+
+fn validate(size: usize) -> Result<usize, String> {
+    if size > 1024 {
+        Err("too large".to_string())
+    } else {
+        Ok(size)
+    }
+}
+
+fn count(key: &str) -> usize {
+    ProfileCollector::get_stats(key).map_or(0, |stats| stats.count)
+}
+
+#[test]
+fn test_profiled_sync_function() {
+    ProfileCollector::reset_operation("Database::fetch");
+
+    assert_eq!(fetch(21), Ok(42));
+    // Early returns are recorded too
+    assert!(fetch(0).is_err());
+
+    if is_full_enabled() {
+        assert_eq!(count("Database::fetch"), 2);
+        let stats = ProfileCollector::get_stats("Database::fetch").unwrap();
+        assert!(stats.total >= Duration::from_millis(2));
+    }
+}
+
+#[test]
+fn test_profiled_without_category() {
+    ProfileCollector::reset_operation("::uncategorized_step");
+
+    uncategorized_step();
+
+    if is_full_enabled() {
+        assert_eq!(count("::uncategorized_step"), 1);
+    }
+}
+
+#[test]
+fn test_profiled_infers_name_from_function() {
+    ProfileCollector::reset_operation("::inferred_name");
+    ProfileCollector::reset_operation("DB::query");
+
+    inferred_name();
+    assert_eq!(query(), 7);
+
+    if is_full_enabled() {
+        assert_eq!(count("::inferred_name"), 1);
+        assert_eq!(count("DB::query"), 1);
+    }
+}
+
+#[tokio::test]
+async fn test_profiled_async_with_category_and_name() {
+    ProfileCollector::reset_operation("DB::lookup");
+
+    find_user().await;
+
+    if is_full_enabled() {
+        assert_eq!(count("DB::lookup"), 1);
+    }
+}
+
+#[tokio::test]
+async fn test_profiled_async_function() {
+    ProfileCollector::reset_operation("Network::download");
+
+    assert_eq!(download(16).await.map(|data| data.len()), Ok(16));
+    // `?` inside the instrumented future still records the call
+    assert!(download(4096).await.is_err());
+
+    if is_full_enabled() {
+        assert_eq!(count("Network::download"), 2);
+        let stats = ProfileCollector::get_stats("Network::download").unwrap();
+        assert!(stats.total >= Duration::from_millis(2));
+    }
+}
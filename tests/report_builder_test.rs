@@ -0,0 +1,48 @@
+//! Cross-feature tests for the report builder API
+//!
+//! These tests compile and run in both stub and full modes to guarantee
+//! that the builder exposes the same methods regardless of features.
+
+use quantum_pulse::{Percentile, ProfileCollector, ReportBuilder, SortMetric, TimeFormat};
+
+#[test]
+fn test_builder_chains_all_setters() {
+    ProfileCollector::clear_all();
+
+    ProfileCollector::record("builder_op", 1000);
+
+    let builder: ReportBuilder = ReportBuilder::new();
+    let report = builder
+        .include_percentiles(true)
+        .sort_by_time(true)
+        .sort_by_percentile(Percentile::P99)
+        .min_samples(1)
+        .group_by_category(false)
+        .include_summary(true)
+        .time_format(TimeFormat::Milliseconds)
+        .max_operations(10)
+        .include_empty(false)
+        .category_priority("IO", 0)
+        .build();
+
+    let _ = report.quick_summary();
+}
+
+#[test]
+fn test_sort_metrics_match_across_features() {
+    let builder: ReportBuilder = ReportBuilder::new();
+    let report = builder.build();
+    for metric in [
+        SortMetric::Count,
+        SortMetric::TotalTime,
+        SortMetric::MeanTime,
+        SortMetric::MaxTime,
+        SortMetric::P50,
+        SortMetric::P95,
+        SortMetric::P99,
+        SortMetric::P999,
+        SortMetric::CallCount,
+    ] {
+        assert!(report.top_operations_by(metric, 0).is_empty());
+    }
+}